@@ -1,23 +1,180 @@
-use crate::core::types::{Candle, Config};
+use crate::core::realized_vol::RealizedVol;
+use crate::adapters::kalshi::retry::{self, RetryPolicy};
+use crate::core::types::{Candle, Config, PerpQuote, PriceFeedError};
 use crate::ports::price_feed::PriceFeed;
 use anyhow::Result;
 use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 5-second returns over the last 10 minutes.
+const REALIZED_VOL_BUCKET_SECS: u64 = 5;
+const REALIZED_VOL_WINDOW_SECS: u64 = 600;
+
+/// How long a host that refuses our region is left alone.
+const GEO_BLOCK_PAUSE: Duration = Duration::from_secs(3600);
 
 pub struct BinanceClient {
     client: reqwest::Client,
-    base_url: String,
+    /// Spot REST roots in fallback order (`BINANCE_REST_URL`).
+    base_urls: Vec<String>,
+    /// Index of the root currently served from; moves on when it fails.
+    active_url: AtomicUsize,
+    /// USDⓈ-M futures REST root; None skips funding/basis.
+    futures_url: Option<String>,
+    realized_vol: Mutex<RealizedVol>,
+    retry: RetryPolicy,
+    /// Per host: requests are refused until this instant after a 418 ban, a
+    /// 429 whose Retry-After outlasts our retries, or a geo-block.
+    banned_until: Mutex<HashMap<String, Instant>>,
 }
 
 impl BinanceClient {
-    pub fn new(_config: &Config) -> Result<Self> {
+    pub fn new(config: &Config) -> Result<Self> {
         Ok(Self {
             client: reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(5))
+                .timeout(Duration::from_secs(5))
                 .build()?,
-            base_url: "https://api.binance.us".into(),
+            base_urls: config.binance_rest_urls.clone(),
+            active_url: AtomicUsize::new(0),
+            futures_url: config.binance_futures_url.clone(),
+            realized_vol: Mutex::new(RealizedVol::new(
+                REALIZED_VOL_BUCKET_SECS,
+                REALIZED_VOL_WINDOW_SECS,
+            )),
+            retry: RetryPolicy {
+                max_retries: config.binance_max_retries,
+                base_delay: Duration::from_millis(config.kalshi_retry_base_ms),
+                max_delay: Duration::from_millis(config.kalshi_retry_max_ms),
+            },
+            banned_until: Mutex::default(),
         })
     }
+
+    /// `get_json` against the active spot root, falling through the rest in
+    /// order when it errs (geo-block, ban, exhausted retries). The root that
+    /// answers stays active, so a blocked one costs one failed call, not one
+    /// per request. Only the last root's error is returned.
+    async fn get_spot_json<T: DeserializeOwned>(&self, what: &str, path: &str) -> Result<Option<T>> {
+        let start = self.active_url.load(Ordering::Relaxed);
+        let mut tried = 0;
+        loop {
+            let idx = (start + tried) % self.base_urls.len();
+            let host = &self.base_urls[idx];
+            tried += 1;
+            match self.get_json(what, host, path).await {
+                Err(e) if tried < self.base_urls.len() => {
+                    tracing::warn!("Binance {} via {} failed: {} — trying next endpoint", what, host, e);
+                }
+                result => {
+                    if result.is_ok() && idx != start {
+                        tracing::info!("Binance REST switched to {}", host);
+                        self.active_url.store(idx, Ordering::Relaxed);
+                    }
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// GET and decode `host` + `path`, retrying 429s, 5xx, and connect/timeout errors
+    /// with backoff. A 418 (IP ban) or a 429 whose Retry-After we can't wait
+    /// out bans the host for that long, and a 451 or 403 (geo-block) for an
+    /// hour, failing with `PriceFeedError::Down`;
+    /// exhausted retries or an undecodable body fail with `Transient`. Any
+    /// other error status is "no data": `Ok(None)`.
+    async fn get_json<T: DeserializeOwned>(&self, what: &str, host: &str, path: &str) -> Result<Option<T>> {
+        let url = format!("{}{}", host, path);
+        if let Some(left) = self.ban_remaining(host) {
+            return Err(PriceFeedError::Down {
+                detail: format!("Binance {} skipped: {} banned", what, host),
+                retry_in_secs: left.as_secs().max(1),
+            }
+            .into());
+        }
+
+        let mut retries = 0;
+        loop {
+            // Failure worth retrying, with the minimum wait before the retry
+            let (reason, floor) = match self.client.get(&url).send().await {
+                Ok(resp) if resp.status().is_success() => {
+                    return match resp.json().await {
+                        Ok(v) => Ok(Some(v)),
+                        Err(e) => Err(PriceFeedError::Transient(format!("Binance {} parse error: {}", what, e)).into()),
+                    };
+                }
+                Ok(resp) if resp.status() == StatusCode::IM_A_TEAPOT || resp.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    let teapot = resp.status() == StatusCode::IM_A_TEAPOT;
+                    let retry_after = Duration::from_secs(
+                        resp.headers()
+                            .get(reqwest::header::RETRY_AFTER)
+                            .and_then(|v| v.to_str().ok())
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or(if teapot { 120 } else { 1 }),
+                    );
+                    if teapot || retries >= self.retry.max_retries || retry_after > self.retry.max_delay {
+                        self.ban(host, retry_after);
+                        return Err(PriceFeedError::Down {
+                            detail: format!("Binance {} -> {}", what, resp.status()),
+                            retry_in_secs: retry_after.as_secs().max(1),
+                        }
+                        .into());
+                    }
+                    (resp.status().to_string(), retry_after)
+                }
+                Ok(resp) if resp.status() == StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS || resp.status() == StatusCode::FORBIDDEN => {
+                    self.ban(host, GEO_BLOCK_PAUSE);
+                    return Err(PriceFeedError::Down {
+                        detail: format!("Binance {} -> {} (geo-blocked)", what, resp.status()),
+                        retry_in_secs: GEO_BLOCK_PAUSE.as_secs(),
+                    }
+                    .into());
+                }
+                Ok(resp) if retry::is_retryable_status(resp.status()) => (resp.status().to_string(), Duration::ZERO),
+                Ok(resp) => {
+                    tracing::warn!("Binance {} -> {}", what, resp.status());
+                    return Ok(None);
+                }
+                Err(e) if retry::is_transient(&e) => (e.to_string(), Duration::ZERO),
+                Err(e) => {
+                    return Err(PriceFeedError::Transient(format!("Binance {} request failed: {}", what, e)).into());
+                }
+            };
+
+            if retries >= self.retry.max_retries {
+                return Err(PriceFeedError::Transient(format!(
+                    "Binance {} failed ({}) after {} retries",
+                    what, reason, retries
+                ))
+                .into());
+            }
+            let delay = self.retry.delay(retries).max(floor);
+            retries += 1;
+            tracing::warn!(
+                "Binance {} failed ({}) — retry {}/{} in {}ms",
+                what, reason, retries, self.retry.max_retries, delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    fn ban(&self, host: &str, for_: Duration) {
+        tracing::warn!("Binance {} refusing requests — pausing it for {}s", host, for_.as_secs());
+        if let Ok(mut bans) = self.banned_until.lock() {
+            bans.insert(host.to_string(), Instant::now() + for_);
+        }
+    }
+
+    /// Time left on the host's ban, if any.
+    fn ban_remaining(&self, host: &str) -> Option<Duration> {
+        let until = *self.banned_until.lock().ok()?.get(host)?;
+        until.checked_duration_since(Instant::now()).filter(|d| !d.is_zero())
+    }
 }
 
 #[async_trait]
@@ -28,30 +185,9 @@ impl PriceFeed for BinanceClient {
         interval: &str,
         limit: u32,
     ) -> Result<Option<Vec<Candle>>> {
-        let url = format!(
-            "{}/api/v3/klines?symbol={}&interval={}&limit={}",
-            self.base_url, symbol, interval, limit
-        );
-
-        let resp = match self.client.get(&url).send().await {
-            Ok(r) => r,
-            Err(e) => {
-                tracing::warn!("Binance klines request failed: {}", e);
-                return Ok(None);
-            }
-        };
-
-        if !resp.status().is_success() {
-            tracing::warn!("Binance klines -> {}", resp.status());
+        let path = format!("/api/v3/klines?symbol={}&interval={}&limit={}", symbol, interval, limit);
+        let Some(raw) = self.get_spot_json::<Vec<Vec<serde_json::Value>>>("klines", &path).await? else {
             return Ok(None);
-        }
-
-        let raw: Vec<Vec<serde_json::Value>> = match resp.json().await {
-            Ok(v) => v,
-            Err(e) => {
-                tracing::warn!("Binance klines parse error: {}", e);
-                return Ok(None);
-            }
         };
 
         let candles = raw
@@ -76,37 +212,54 @@ impl PriceFeed for BinanceClient {
     }
 
     async fn spot_price(&self, symbol: &str) -> Result<Option<f64>> {
-        let url = format!(
-            "{}/api/v3/ticker/price?symbol={}",
-            self.base_url, symbol
-        );
-
-        let resp = match self.client.get(&url).send().await {
-            Ok(r) => r,
-            Err(e) => {
-                tracing::warn!("Binance ticker request failed: {}", e);
-                return Ok(None);
-            }
+        let path = format!("/api/v3/ticker/price?symbol={}", symbol);
+
+        #[derive(Deserialize)]
+        struct TickerPrice {
+            price: String,
+        }
+
+        let Some(ticker) = self.get_spot_json::<TickerPrice>("ticker", &path).await? else {
+            return Ok(None);
         };
+        Ok(ticker.price.parse().ok())
+    }
 
-        if !resp.status().is_success() {
-            tracing::warn!("Binance ticker -> {}", resp.status());
+    async fn perp(&self, symbol: &str) -> Result<Option<PerpQuote>> {
+        let Some(futures_url) = &self.futures_url else {
             return Ok(None);
-        }
+        };
+        let path = format!("/fapi/v1/premiumIndex?symbol={}", symbol);
 
         #[derive(Deserialize)]
-        struct TickerPrice {
-            price: String,
+        #[serde(rename_all = "camelCase")]
+        struct PremiumIndex {
+            mark_price: String,
+            last_funding_rate: String,
+            next_funding_time: i64,
         }
 
-        let ticker: TickerPrice = match resp.json().await {
-            Ok(v) => v,
-            Err(e) => {
-                tracing::warn!("Binance ticker parse error: {}", e);
-                return Ok(None);
-            }
+        let Some(index) = self.get_json::<PremiumIndex>("premiumIndex", futures_url, &path).await? else {
+            return Ok(None);
         };
 
-        Ok(ticker.price.parse().ok())
+        let (Ok(mark_price), Ok(funding_rate)) = (index.mark_price.parse::<f64>(), index.last_funding_rate.parse::<f64>()) else {
+            return Ok(None);
+        };
+        Ok(Some(PerpQuote {
+            mark_price,
+            funding_rate_pct: funding_rate * 100.0,
+            next_funding_ms: index.next_funding_time,
+        }))
+    }
+
+    fn record_price(&self, symbol: &str, price: f64, ts_ms: i64) {
+        if let Ok(mut rv) = self.realized_vol.lock() {
+            rv.record(symbol, price, ts_ms);
+        }
+    }
+
+    fn realized_vol(&self, symbol: &str) -> Option<f64> {
+        self.realized_vol.lock().ok()?.realized_vol_pct(symbol)
     }
 }
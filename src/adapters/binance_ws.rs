@@ -1,32 +1,129 @@
-use futures_util::StreamExt;
+use crate::adapters::kalshi::retry::RetryPolicy;
+use crate::adapters::ws_health::WsHealth;
+use crate::adapters::ws_tap::RawTap;
+use crate::core::types::Candle;
+use futures_util::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
 use tokio_tungstenite::connect_async;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Debug, Clone)]
 pub struct CryptoPriceUpdate {
     pub symbol: String,
     pub price: f64,
+    /// Binance event time (unix millis).
+    pub event_time_ms: i64,
+    /// The kline this update came from, in progress unless `closed`.
+    pub candle: Candle,
+    pub closed: bool,
 }
 
+/// Top of a spot order book from a partial depth stream, best levels first.
+#[derive(Debug, Clone)]
+pub struct DepthUpdate {
+    pub symbol: String,
+    /// `(price, quantity)` levels.
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+    /// Receive time (unix millis); partial depth frames carry no event time.
+    pub recv_ms: i64,
+}
+
+/// One aggregated spot trade.
+#[derive(Debug, Clone)]
+pub struct SpotTrade {
+    pub symbol: String,
+    pub qty: f64,
+    /// The taker bought (the buyer wasn't the maker).
+    pub taker_buy: bool,
+    /// Trade time (unix millis).
+    pub trade_time_ms: i64,
+}
+
+#[derive(Debug, Clone)]
+pub enum BinanceWsEvent {
+    Price(CryptoPriceUpdate),
+    Depth(DepthUpdate),
+    Trade(SpotTrade),
+    /// A reconnect attempt failed; `consecutive` counts failures since the
+    /// last successful connect.
+    ReconnectFailed { consecutive: u32 },
+}
+
+/// Stream from the first of `urls` that connects, moving to the next one
+/// after each failed attempt.
 pub async fn connect(
-    url: &str,
-    tx: mpsc::Sender<CryptoPriceUpdate>,
+    urls: &[String],
+    tx: mpsc::Sender<BinanceWsEvent>,
+    health: &WsHealth,
+    closing: CancellationToken,
+    tap: Option<RawTap>,
+    backoff: RetryPolicy,
+    symbols: &[String],
 ) -> anyhow::Result<()> {
+    // 1m klines, partial book depth (top 20, 1s) and aggregated trades per
+    // traded symbol, added to whatever the URL streams
+    let extra_streams: Vec<String> = symbols
+        .iter()
+        .flat_map(|s| {
+            let s = s.to_lowercase();
+            [format!("{}@kline_1m", s), format!("{}@depth20", s), format!("{}@aggTrade", s)]
+        })
+        .collect();
+    let mut failures: u32 = 0;
+    let mut url_idx = 0;
     loop {
+        let url = &urls[url_idx];
         tracing::info!("Binance WS connecting to {}", url);
-        match connect_async(url).await {
+        match connect_async(url.as_str()).await {
             Ok((ws, _)) => {
                 tracing::info!("Binance WS connected");
-                let (_, mut read) = ws.split();
+                failures = 0;
+                health.on_connect();
+                let (mut write, mut read) = ws.split();
+                if !extra_streams.is_empty() {
+                    let subscribe = serde_json::json!({"method": "SUBSCRIBE", "params": extra_streams, "id": 1});
+                    let msg = tokio_tungstenite::tungstenite::Message::Text(subscribe.to_string());
+                    if let Err(e) = write.send(msg).await {
+                        tracing::warn!("Binance WS depth/trade subscribe failed: {}", e);
+                    }
+                }
 
-                while let Some(msg) = read.next().await {
+                loop {
+                    let msg = tokio::select! {
+                        msg = read.next() => match msg {
+                            Some(msg) => msg,
+                            None => break,
+                        },
+                        _ = closing.cancelled() => {
+                            if let Err(e) = write.close().await {
+                                tracing::debug!("Binance WS close frame failed: {}", e);
+                            }
+                            health.on_disconnect();
+                            tracing::info!("Binance WS closed");
+                            return Ok(());
+                        }
+                        _ = health.reconnect_requested() => {
+                            tracing::warn!("Binance WS reconnect requested");
+                            break;
+                        }
+                    };
                     match msg {
                         Ok(tokio_tungstenite::tungstenite::Message::Text(text)) => {
-                            if let Some(update) = parse_kline(&text) {
-                                if tx.send(update).await.is_err() {
-                                    tracing::warn!("Binance WS receiver dropped");
-                                    return Ok(());
-                                }
+                            health.on_message();
+                            if let Some(tap) = &tap {
+                                tap.forward("binance", &text);
+                            }
+                            let Some(event) = parse_message(&text) else {
+                                health.on_parse_failure();
+                                continue;
+                            };
+                            let Some(event) = event else {
+                                continue;
+                            };
+                            if tx.send(event).await.is_err() {
+                                tracing::warn!("Binance WS receiver dropped");
+                                return Ok(());
                             }
                         }
                         Ok(tokio_tungstenite::tungstenite::Message::Close(_)) => {
@@ -40,29 +137,99 @@ pub async fn connect(
                         _ => {}
                     }
                 }
+                health.on_disconnect();
             }
             Err(e) => {
-                tracing::warn!("Binance WS connect failed: {}", e);
+                failures += 1;
+                tracing::warn!("Binance WS connect failed ({} in a row): {}", failures, e);
+                // A geo-blocked or unreachable host: try the next one
+                url_idx = (url_idx + 1) % urls.len();
+                if tx.send(BinanceWsEvent::ReconnectFailed { consecutive: failures }).await.is_err() {
+                    return Ok(());
+                }
             }
         }
-        tracing::info!("Binance WS reconnecting in 5s");
-        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        let delay = backoff.delay(failures);
+        tracing::info!("Binance WS reconnecting in {}ms", delay.as_millis());
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = closing.cancelled() => return Ok(()),
+        }
     }
 }
 
-fn parse_kline(text: &str) -> Option<CryptoPriceUpdate> {
+/// Parse a text frame: Some(None) for a frame that's fine but carries no
+/// event (a SUBSCRIBE reply), None for one that didn't parse.
+fn parse_message(text: &str) -> Option<Option<BinanceWsEvent>> {
     let v: serde_json::Value = serde_json::from_str(text).ok()?;
+    if v.get("result").is_some() && v.get("id").is_some() {
+        return Some(None);
+    }
 
     // Combined stream format: {"stream":"btcusdt@kline_1m","data":{...}}
     // Single stream format: {"e":"kline","k":{...}}
-    let k = if let Some(data) = v.get("data") {
-        data.get("k")?
-    } else {
-        v.get("k")?
+    let event = v.get("data").unwrap_or(&v);
+    if let Some(stream) = v.get("stream").and_then(|s| s.as_str()) {
+        if let Some(symbol) = stream.split_once("@depth").map(|(s, _)| s) {
+            return parse_depth(symbol, event).map(|d| Some(BinanceWsEvent::Depth(d)));
+        }
+    }
+    if event.get("e").and_then(|e| e.as_str()) == Some("aggTrade") {
+        return parse_agg_trade(event).map(|t| Some(BinanceWsEvent::Trade(t)));
+    }
+    parse_kline(event).map(|k| Some(BinanceWsEvent::Price(k)))
+}
+
+fn parse_agg_trade(event: &serde_json::Value) -> Option<SpotTrade> {
+    let num = |key: &str| event.get(key)?.as_str()?.parse::<f64>().ok();
+    Some(SpotTrade {
+        symbol: event.get("s")?.as_str()?.to_string(),
+        qty: num("q")?,
+        // "m": the buyer was the maker, so the taker sold
+        taker_buy: !event.get("m")?.as_bool()?,
+        trade_time_ms: event.get("T")?.as_i64()?,
+    })
+}
+
+/// Partial depth frames name the symbol only in the combined stream name.
+fn parse_depth(symbol: &str, data: &serde_json::Value) -> Option<DepthUpdate> {
+    let levels = |key: &str| -> Option<Vec<(f64, f64)>> {
+        data.get(key)?
+            .as_array()?
+            .iter()
+            .map(|level| {
+                let l = level.as_array()?;
+                Some((l.first()?.as_str()?.parse().ok()?, l.get(1)?.as_str()?.parse().ok()?))
+            })
+            .collect()
     };
+    Some(DepthUpdate {
+        symbol: symbol.to_uppercase(),
+        bids: levels("bids")?,
+        asks: levels("asks")?,
+        recv_ms: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+fn parse_kline(event: &serde_json::Value) -> Option<CryptoPriceUpdate> {
+    let k = event.get("k")?;
 
-    let close_str = k.get("c")?.as_str()?;
-    let price = close_str.parse::<f64>().ok()?;
+    let num = |key: &str| k.get(key)?.as_str()?.parse::<f64>().ok();
+    let price = num("c")?;
     let symbol = k.get("s")?.as_str()?.to_string();
-    Some(CryptoPriceUpdate { symbol, price })
+    let event_time_ms = event
+        .get("E")
+        .and_then(|e| e.as_i64())
+        .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    let candle = Candle {
+        open_time: k.get("t")?.as_i64()?,
+        open: num("o")?,
+        high: num("h")?,
+        low: num("l")?,
+        close: price,
+        volume: num("v")?,
+        close_time: k.get("T")?.as_i64()?,
+    };
+    let closed = k.get("x").and_then(|x| x.as_bool()).unwrap_or(false);
+    Some(CryptoPriceUpdate { symbol, price, event_time_ms, candle, closed })
 }
@@ -0,0 +1,182 @@
+use crate::core::types::{interval_minutes, Candle, PerpQuote};
+use crate::ports::price_feed::PriceFeed;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// `PriceFeed` decorator that keeps every closed candle it sees on disk, at
+/// `<dir>/<SYMBOL>_<interval>.jsonl`, keyed by open time. When the closed
+/// part of a request is already cached, only the in-progress candle is
+/// fetched, so restarts and many series on one symbol don't re-download
+/// history — and the files double as backtest data.
+///
+/// Wraps a single source adapter (its REST candles), never a failover or
+/// aggregating feed, so one source's history is never mixed with another's.
+/// Candles older than the retention are pruned from memory and disk.
+pub struct CandleCache {
+    inner: Box<dyn PriceFeed>,
+    dir: PathBuf,
+    retention_ms: i64,
+    /// Loaded lazily per (symbol, interval).
+    candles: Mutex<HashMap<(String, String), BTreeMap<i64, Candle>>>,
+}
+
+/// Interval length in millis for epoch-aligned intervals (minutes, hours,
+/// days); weekly candles open on Mondays, not at a multiple of the epoch.
+fn interval_ms(interval: &str) -> Option<i64> {
+    interval_minutes(interval)
+        .filter(|_| !interval.ends_with('w'))
+        .map(|m| m as i64 * 60_000)
+}
+
+impl CandleCache {
+    pub fn new(inner: Box<dyn PriceFeed>, dir: &str, retention_days: u64) -> Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        Ok(Self {
+            inner,
+            dir: PathBuf::from(dir),
+            retention_ms: retention_days as i64 * 86_400_000,
+            candles: Mutex::default(),
+        })
+    }
+
+    fn path(&self, symbol: &str, interval: &str) -> PathBuf {
+        self.dir.join(format!("{}_{}.jsonl", symbol, interval))
+    }
+
+    /// Read a series' file; unreadable lines are skipped, and the file is
+    /// rewritten without them if any candles have aged out.
+    fn load(&self, symbol: &str, interval: &str) -> BTreeMap<i64, Candle> {
+        let Ok(text) = std::fs::read_to_string(self.path(symbol, interval)) else {
+            return BTreeMap::new();
+        };
+        let mut series: BTreeMap<i64, Candle> = text
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Candle>(line).ok())
+            .map(|c| (c.open_time, c))
+            .collect();
+        if self.prune(&mut series, chrono::Utc::now().timestamp_millis()) {
+            self.rewrite(symbol, interval, &series);
+        }
+        series
+    }
+
+    /// Drop candles older than the retention; true if any were dropped.
+    fn prune(&self, series: &mut BTreeMap<i64, Candle>, now_ms: i64) -> bool {
+        let before = series.len();
+        *series = series.split_off(&(now_ms - self.retention_ms));
+        series.len() != before
+    }
+
+    /// Replace a series' file with `series`.
+    fn rewrite(&self, symbol: &str, interval: &str, series: &BTreeMap<i64, Candle>) {
+        let lines: String = series
+            .values()
+            .filter_map(|c| serde_json::to_string(c).ok())
+            .map(|line| line + "\n")
+            .collect();
+        let path = self.path(symbol, interval);
+        if let Err(e) = std::fs::write(&path, lines) {
+            tracing::warn!("Candle cache rewrite of {} failed: {}", path.display(), e);
+        }
+    }
+
+    /// Cached closed candles in `[from, to)`.
+    fn cached(&self, symbol: &str, interval: &str, from: i64, to: i64) -> Vec<Candle> {
+        let mut candles = self.candles.lock().expect("candle cache poisoned");
+        let series = candles
+            .entry((symbol.to_string(), interval.to_string()))
+            .or_insert_with(|| self.load(symbol, interval));
+        series.range(from..to).map(|(_, c)| c.clone()).collect()
+    }
+
+    /// Add closed candles not cached yet, appending them to the series' file.
+    /// Only candles opened before `current_open` are stored — never the one
+    /// still forming.
+    fn store(&self, symbol: &str, interval: &str, fetched: &[Candle], current_open: i64, now_ms: i64) {
+        let mut candles = self.candles.lock().expect("candle cache poisoned");
+        let Some(series) = candles.get_mut(&(symbol.to_string(), interval.to_string())) else {
+            return;
+        };
+        if self.prune(series, now_ms) {
+            self.rewrite(symbol, interval, series);
+        }
+        let mut lines = String::new();
+        for candle in fetched.iter().filter(|c| c.open_time < current_open && c.close_time < now_ms) {
+            if series.contains_key(&candle.open_time) {
+                continue;
+            }
+            if let Ok(line) = serde_json::to_string(candle) {
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+            series.insert(candle.open_time, candle.clone());
+        }
+        if lines.is_empty() {
+            return;
+        }
+        let path = self.path(symbol, interval);
+        let written = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut f| f.write_all(lines.as_bytes()));
+        if let Err(e) = written {
+            tracing::warn!("Candle cache write to {} failed: {}", path.display(), e);
+        }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for CandleCache {
+    async fn candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: u32,
+    ) -> Result<Option<Vec<Candle>>> {
+        let Some(span) = interval_ms(interval).filter(|_| limit > 1) else {
+            return self.inner.candles(symbol, interval, limit).await;
+        };
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let current_open = now_ms - now_ms.rem_euclid(span);
+        let first_open = current_open - (limit as i64 - 1) * span;
+
+        let cached = self.cached(symbol, interval, first_open, current_open);
+        let complete = cached.len() as u32 == limit - 1;
+        let fetch_limit = if complete { 1 } else { limit };
+        let Some(fetched) = self.inner.candles(symbol, interval, fetch_limit).await? else {
+            return Ok(None);
+        };
+        self.store(symbol, interval, &fetched, current_open, now_ms);
+        if !complete {
+            return Ok(Some(fetched));
+        }
+        let mut candles = cached;
+        candles.extend(fetched.into_iter().filter(|c| c.open_time >= current_open));
+        Ok(Some(candles))
+    }
+
+    async fn spot_price(&self, symbol: &str) -> Result<Option<f64>> {
+        self.inner.spot_price(symbol).await
+    }
+
+    async fn perp(&self, symbol: &str) -> Result<Option<PerpQuote>> {
+        self.inner.perp(symbol).await
+    }
+
+    fn record_price(&self, symbol: &str, price: f64, ts_ms: i64) {
+        self.inner.record_price(symbol, price, ts_ms);
+    }
+
+    fn record_kline(&self, symbol: &str, candle: &Candle, closed: bool) {
+        self.inner.record_kline(symbol, candle, closed);
+    }
+
+    fn realized_vol(&self, symbol: &str) -> Option<f64> {
+        self.inner.realized_vol(symbol)
+    }
+}
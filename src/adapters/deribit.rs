@@ -0,0 +1,79 @@
+use crate::ports::vol_feed::VolFeed;
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+/// Implements `VolFeed` with Deribit's DVOL index (30-day implied vol from
+/// BTC and ETH options), via the public REST API.
+pub struct DeribitClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl DeribitClient {
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(5))
+                .build()?,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+/// DVOL is only published for these currencies.
+fn dvol_currency(symbol: &str) -> Option<&'static str> {
+    match symbol {
+        "BTCUSDT" | "BTCUSD" => Some("BTC"),
+        "ETHUSDT" | "ETHUSD" => Some("ETH"),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl VolFeed for DeribitClient {
+    async fn implied_vol(&self, symbol: &str) -> Result<Option<f64>> {
+        let Some(currency) = dvol_currency(symbol) else {
+            return Ok(None);
+        };
+        // Minute candles over the last 10 minutes; the newest close is current DVOL
+        let end = chrono::Utc::now().timestamp_millis();
+        let url = format!(
+            "{}/api/v2/public/get_volatility_index_data?currency={}&start_timestamp={}&end_timestamp={}&resolution=60",
+            self.base_url, currency, end - 600_000, end
+        );
+
+        let resp = match self.client.get(&url).send().await {
+            Ok(r) => r,
+            Err(e) => {
+                tracing::warn!("Deribit DVOL request failed: {}", e);
+                return Ok(None);
+            }
+        };
+
+        if !resp.status().is_success() {
+            tracing::warn!("Deribit DVOL -> {}", resp.status());
+            return Ok(None);
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            result: VolData,
+        }
+        #[derive(Deserialize)]
+        struct VolData {
+            /// `[timestamp, open, high, low, close]`, oldest first.
+            data: Vec<(i64, f64, f64, f64, f64)>,
+        }
+
+        let body: Response = match resp.json().await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::warn!("Deribit DVOL parse error: {}", e);
+                return Ok(None);
+            }
+        };
+
+        Ok(body.result.data.iter().max_by_key(|row| row.0).map(|row| row.4))
+    }
+}
@@ -4,12 +4,49 @@ use rsa::pkcs1::DecodeRsaPrivateKey;
 use rsa::pkcs8::DecodePrivateKey;
 use rsa::pss::SigningKey;
 use rsa::signature::{RandomizedSigner, SignatureEncoding};
+use super::endpoint::Endpoint;
+use crate::core::types::{Config, KalshiAuthMode};
 use rsa::RsaPrivateKey;
+use serde::Deserialize;
 use sha2::Sha256;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
+/// Clones share the clock offset and login token, so REST and WS
+/// authenticate the same way.
+#[derive(Clone)]
 pub struct KalshiAuth {
-    signing_key: SigningKey<Sha256>,
-    pub key_id: String,
+    credentials: Credentials,
+    clock: Arc<ClockOffset>,
+}
+
+#[derive(Clone)]
+enum Credentials {
+    /// RSA-PSS signature per request with an API key.
+    Rsa {
+        signing_key: Box<SigningKey<Sha256>>,
+        key_id: String,
+    },
+    /// Bearer token: a fixed API token, or one obtained (and renewed when
+    /// rejected) by logging in with email and password.
+    Token {
+        login: Option<(String, String)>,
+        token: Arc<RwLock<String>>,
+    },
+}
+
+#[derive(Deserialize)]
+struct LoginResponse {
+    token: String,
+}
+
+#[derive(Default)]
+struct ClockOffset {
+    /// Server time minus local time.
+    offset_ms: AtomicI64,
+    /// Local unix millis of the last sync attempt; 0 = never.
+    attempted_ms: AtomicI64,
 }
 
 impl KalshiAuth {
@@ -20,19 +57,122 @@ impl KalshiAuth {
             RsaPrivateKey::from_pkcs8_pem(pem)?
         };
         Ok(Self {
-            signing_key: SigningKey::<Sha256>::new(private_key),
-            key_id,
+            credentials: Credentials::Rsa {
+                signing_key: Box::new(SigningKey::<Sha256>::new(private_key)),
+                key_id,
+            },
+            clock: Arc::new(ClockOffset::default()),
         })
     }
 
+    /// RSA signing or bearer-token auth, per `KALSHI_AUTH`.
+    pub fn from_config(config: &Config) -> anyhow::Result<Self> {
+        let login = match config.kalshi_auth_mode {
+            KalshiAuthMode::Rsa => return Self::new(config.kalshi_key_id.clone(), &config.kalshi_private_key_pem),
+            KalshiAuthMode::Token if !config.kalshi_api_token.is_empty() => None,
+            KalshiAuthMode::Token => Some((config.kalshi_email.clone(), config.kalshi_password.clone())),
+        };
+        Ok(Self {
+            credentials: Credentials::Token {
+                login,
+                token: Arc::new(RwLock::new(config.kalshi_api_token.clone())),
+            },
+            clock: Arc::new(ClockOffset::default()),
+        })
+    }
+
+    /// Whether requests need a fresh token from `login` first.
+    pub fn needs_login(&self) -> bool {
+        match &self.credentials {
+            Credentials::Token { login: Some(_), token } => token.read().expect("token lock poisoned").is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Exchange email and password for a session token.
+    pub async fn login(&self, client: &reqwest::Client, base_url: &str) -> anyhow::Result<()> {
+        let Credentials::Token { login: Some((email, password)), token } = &self.credentials else {
+            return Ok(());
+        };
+        let resp = client
+            .post(format!("{}{}", base_url, Endpoint::login()))
+            .json(&serde_json::json!({ "email": email, "password": password }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Kalshi login as {} failed: {}", email, resp.status());
+        }
+        let session: LoginResponse = resp.json().await?;
+        *token.write().expect("token lock poisoned") = session.token;
+        tracing::info!("Logged in to Kalshi as {}", email);
+        Ok(())
+    }
+
+    /// Drop a session token the exchange rejected, so the next request logs
+    /// in again. Fixed API tokens are kept.
+    pub fn expire_login(&self) {
+        if let Credentials::Token { login: Some(_), token } = &self.credentials {
+            token.write().expect("token lock poisoned").clear();
+        }
+    }
+
+    /// Whether a clock sync is due, claiming it if so — concurrent callers
+    /// see only one `true` per interval. Failed syncs also wait the interval.
+    pub fn claim_sync(&self, interval: Duration) -> bool {
+        let now = chrono::Utc::now().timestamp_millis();
+        let last = self.clock.attempted_ms.load(Ordering::Relaxed);
+        if last != 0 && now - last < interval.as_millis() as i64 {
+            return false;
+        }
+        self.clock
+            .attempted_ms
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    /// Measure the offset to Kalshi's clock from the `Date` header of an
+    /// unsigned request, and apply it to every later signature. The header
+    /// has 1s resolution, so offsets under a second are treated as zero.
+    pub async fn sync_clock(&self, client: &reqwest::Client, base_url: &str) -> anyhow::Result<i64> {
+        let sent = chrono::Utc::now().timestamp_millis();
+        // Public endpoint, so no signature (and no skew) is involved
+        let resp = client.get(format!("{}{}", base_url, Endpoint::exchange_status())).send().await?;
+        let received = chrono::Utc::now().timestamp_millis();
+        let date = resp
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| anyhow::anyhow!("Kalshi response has no Date header"))?;
+        // Mid-second, since the header truncates
+        let server = chrono::DateTime::parse_from_rfc2822(date)?.timestamp_millis() + 500;
+        let mut offset = server - (sent + received) / 2;
+        if offset.abs() < 1000 {
+            offset = 0;
+        }
+        let previous = self.clock.offset_ms.swap(offset, Ordering::Relaxed);
+        if offset != previous {
+            tracing::warn!("Kalshi clock offset {}ms (was {}ms) — correcting signatures", offset, previous);
+        }
+        Ok(offset)
+    }
+
     pub fn headers(&self, method: &str, path: &str) -> Vec<(&'static str, String)> {
-        let ts = chrono::Utc::now().timestamp_millis().to_string();
+        let (signing_key, key_id) = match &self.credentials {
+            Credentials::Rsa { signing_key, key_id } => (signing_key, key_id),
+            Credentials::Token { token, .. } => {
+                return vec![
+                    ("Authorization", format!("Bearer {}", token.read().expect("token lock poisoned"))),
+                    ("Content-Type", "application/json".into()),
+                ];
+            }
+        };
+        let ts = (chrono::Utc::now().timestamp_millis() + self.clock.offset_ms.load(Ordering::Relaxed)).to_string();
         let sign_path = path.split('?').next().unwrap_or(path);
         let msg = format!("{}{}{}", ts, method, sign_path);
         let mut rng = rand::thread_rng();
-        let sig = self.signing_key.sign_with_rng(&mut rng, msg.as_bytes());
+        let sig = signing_key.sign_with_rng(&mut rng, msg.as_bytes());
         vec![
-            ("KALSHI-ACCESS-KEY", self.key_id.clone()),
+            ("KALSHI-ACCESS-KEY", key_id.clone()),
             ("KALSHI-ACCESS-TIMESTAMP", ts),
             ("KALSHI-ACCESS-SIGNATURE", STANDARD.encode(sig.to_bytes())),
             ("Content-Type", "application/json".into()),
@@ -1,41 +1,174 @@
 use super::auth::KalshiAuth;
+use super::endpoint::Endpoint;
+use super::metrics::ApiMetrics;
+use super::rate_limit::RateLimiter;
+use super::retry::{self, RetryPolicy};
 use super::types::*;
 use crate::core::types::*;
 use crate::ports::exchange::Exchange;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 pub struct KalshiClient {
     client: reqwest::Client,
     auth: KalshiAuth,
     base_url: String,
+    /// Shared by every request so concurrent cycles stay under Kalshi's limits.
+    limiter: Arc<RateLimiter>,
+    retry: RetryPolicy,
+    /// Open-market listings per series, with when they were fetched.
+    market_cache: Mutex<HashMap<String, (Instant, Vec<MarketState>)>>,
+    market_cache_ttl: Duration,
+    clock_sync_interval: Duration,
+    metrics: ApiMetrics,
+    /// Set by `sever`: requests fail without being sent until then.
+    severed_until: Mutex<Option<Instant>>,
 }
 
 impl KalshiClient {
     pub fn new(config: &Config) -> Result<Self> {
-        let auth = KalshiAuth::new(
-            config.kalshi_key_id.clone(),
-            &config.kalshi_private_key_pem,
-        )?;
+        let auth = KalshiAuth::from_config(config)?;
         Ok(Self {
-            client: reqwest::Client::new(),
+            client: http_client(config)?,
             auth,
             base_url: config.kalshi_base_url.clone(),
+            limiter: Arc::new(RateLimiter::new(
+                config.kalshi_reads_per_sec,
+                config.kalshi_writes_per_sec,
+            )),
+            retry: RetryPolicy {
+                max_retries: config.kalshi_max_retries,
+                base_delay: std::time::Duration::from_millis(config.kalshi_retry_base_ms),
+                max_delay: std::time::Duration::from_millis(config.kalshi_retry_max_ms),
+            },
+            market_cache: Mutex::new(HashMap::new()),
+            market_cache_ttl: Duration::from_secs(config.kalshi_market_cache_secs),
+            clock_sync_interval: Duration::from_secs(config.kalshi_clock_sync_secs),
+            metrics: ApiMetrics::default(),
+            severed_until: Mutex::new(None),
         })
     }
 
+    /// Fail every request for `offline`, as if Kalshi REST were unreachable
+    /// (outage drills).
+    pub fn sever(&self, offline: Duration) {
+        tracing::warn!("Kalshi REST severed for {}s", offline.as_secs());
+        *self.severed_until.lock().expect("sever lock poisoned") = Some(Instant::now() + offline);
+    }
+
+    /// Per-endpoint latency, status, and retry counters for the metrics push.
+    pub fn api_metrics(&self) -> Vec<LabelledGauge> {
+        self.metrics.gauges()
+    }
+
+    /// Signer shared with this client, clock offset included — hand clones
+    /// to the WS so both sign with the corrected time.
+    pub fn auth(&self) -> &KalshiAuth {
+        &self.auth
+    }
+
+    /// A series' open markets from the cache, if listed within the TTL.
+    fn cached_markets(&self, series_ticker: &str) -> Option<Vec<MarketState>> {
+        let cache = self.market_cache.lock().expect("market cache poisoned");
+        cache
+            .get(series_ticker)
+            .filter(|(at, _)| at.elapsed() < self.market_cache_ttl)
+            .map(|(_, markets)| markets.clone())
+    }
+
+    /// Fresh quotes for cached markets in one `tickers=` call. Markets that
+    /// expired or no longer come back open are dropped.
+    async fn requote(&self, cached: Vec<MarketState>) -> Result<Vec<MarketState>> {
+        let now = chrono::Utc::now();
+        let tickers: Vec<&str> = cached.iter().map(|m| m.ticker.as_str()).collect();
+        let endpoint = Endpoint::markets().query("tickers", tickers.join(",")).query("status", "open");
+        let resp: MarketsResponse = self.get(&endpoint).await?;
+        let mut quotes: HashMap<String, MarketState> = resp
+            .markets
+            .into_iter()
+            .filter_map(|m| market_state(m, now))
+            .map(|m| (m.ticker.clone(), m))
+            .collect();
+        Ok(cached
+            .into_iter()
+            .filter_map(|mut m| {
+                let q = quotes.remove(&m.ticker)?;
+                m.yes_bid = q.yes_bid;
+                m.yes_ask = q.yes_ask;
+                m.no_bid = q.no_bid;
+                m.no_ask = q.no_ask;
+                m.last_price = q.last_price;
+                m.volume = q.volume;
+                m.volume_24h = q.volume_24h;
+                m.open_interest = q.open_interest;
+                m.minutes_to_expiry = q.minutes_to_expiry;
+                Some(m)
+            })
+            .filter(|m| m.minutes_to_expiry > 0.0)
+            .collect())
+    }
+
     async fn request<T: DeserializeOwned>(
         &self,
         method: reqwest::Method,
-        path: &str,
+        endpoint: &Endpoint,
         body: Option<&serde_json::Value>,
     ) -> Result<T> {
-        let mut attempts = 0;
+        let path = endpoint.path();
+        let resp = self.send(method.clone(), &path, body).await?;
+        let status = resp.status();
+
+        if !status.is_success() {
+            let err_body = resp.text().await.unwrap_or_default();
+            return Err(api_error(&method, &path, status, &err_body).into());
+        }
+
+        let text = resp.text().await?;
+        serde_json::from_str::<T>(&text).map_err(|e| {
+            tracing::error!("Deserialize error on {}: {} (body: {}...)", path, e, &text[..text.len().min(300)]);
+            e.into()
+        })
+    }
+
+    /// Sign and send a request, taking a rate-limit token per attempt and
+    /// retrying 429s, 5xx, and connect/timeout errors per the retry policy —
+    /// for a POST, only 429s and connect errors, since a 5xx or timeout may
+    /// have placed the order.
+    /// A 429 also pauses the method's bucket for Retry-After (default 1s), so
+    /// the requests queued behind it slow down instead of piling on. The
+    /// final response is returned whatever its status.
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<reqwest::Response> {
+        if let Some(until) = *self.severed_until.lock().expect("sever lock poisoned") {
+            if Instant::now() < until {
+                anyhow::bail!("Kalshi {} {} failed: REST severed", method, path);
+            }
+        }
+        if self.auth.claim_sync(self.clock_sync_interval) {
+            if let Err(e) = self.auth.sync_clock(&self.client, &self.base_url).await {
+                tracing::warn!("Kalshi clock sync failed: {}", e);
+            }
+        }
+        if self.auth.needs_login() {
+            self.auth.login(&self.client, &self.base_url).await?;
+        }
+        let bucket = self.limiter.bucket(&method);
+        let idempotent = method != reqwest::Method::POST;
+        let mut retries = 0;
         loop {
+            bucket.acquire().await;
+
+            // Re-signed every attempt: the timestamp is part of the signature
             let headers = self.auth.headers(method.as_str(), path);
             let url = format!("{}{}", self.base_url, path);
-
             let mut req = self.client.request(method.clone(), &url);
             for (k, v) in &headers {
                 req = req.header(*k, v);
@@ -44,103 +177,364 @@ impl KalshiClient {
                 req = req.json(b);
             }
 
-            let resp = req.send().await?;
-            let status = resp.status();
-
-            if status == 429 && attempts < 1 {
-                attempts += 1;
-                tracing::warn!("Kalshi 429 — retrying in 2s");
-                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-                continue;
+            let started = Instant::now();
+            let outcome = req.send().await;
+            let status = match &outcome {
+                Ok(resp) => resp.status().as_u16().to_string(),
+                Err(_) => "error".to_string(),
+            };
+            self.metrics.record(method.as_str(), path, &status, started.elapsed());
+            if matches!(&outcome, Ok(resp) if resp.status() == reqwest::StatusCode::UNAUTHORIZED) {
+                self.auth.expire_login();
             }
-
-            if !status.is_success() {
-                let err_body = resp.text().await.unwrap_or_default();
-                anyhow::bail!("Kalshi {} {} -> {} : {}", method, path, status, err_body);
+            // Failure worth retrying, with the minimum wait before the retry
+            let failure = match &outcome {
+                Ok(resp) if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                    let retry_after = std::time::Duration::from_secs(
+                        resp.headers()
+                            .get(reqwest::header::RETRY_AFTER)
+                            .and_then(|v| v.to_str().ok())
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or(1),
+                    );
+                    tracing::warn!("Kalshi 429 on {} — pausing requests for {}s", path, retry_after.as_secs());
+                    bucket.pause(retry_after).await;
+                    Some((resp.status().to_string(), retry_after))
+                }
+                Ok(resp) if idempotent && retry::is_retryable_status(resp.status()) => {
+                    Some((resp.status().to_string(), std::time::Duration::ZERO))
+                }
+                Err(e) if e.is_connect() || (idempotent && retry::is_transient(e)) => {
+                    Some((e.to_string(), std::time::Duration::ZERO))
+                }
+                _ => None,
+            };
+
+            match failure {
+                Some((reason, floor)) if retries < self.retry.max_retries => {
+                    let delay = self.retry.delay(retries).max(floor);
+                    retries += 1;
+                    self.metrics.record_retry(method.as_str(), path);
+                    tracing::warn!(
+                        "Kalshi {} {} failed ({}) — retry {}/{} in {}ms",
+                        method, path, reason, retries, self.retry.max_retries, delay.as_millis()
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                _ => return Ok(outcome?),
             }
-
-            let text = resp.text().await?;
-            return serde_json::from_str::<T>(&text).map_err(|e| {
-                tracing::error!("Deserialize error on {}: {} (body: {}...)", path, e, &text[..text.len().min(300)]);
-                e.into()
-            });
         }
     }
 
-    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
-        self.request(reqwest::Method::GET, path, None).await
+    async fn get<T: DeserializeOwned>(&self, endpoint: &Endpoint) -> Result<T> {
+        self.request(reqwest::Method::GET, endpoint, None).await
     }
 
-    async fn post<T: DeserializeOwned>(&self, path: &str, body: &serde_json::Value) -> Result<T> {
-        self.request(reqwest::Method::POST, path, Some(body)).await
+    /// GET a list endpoint, following `cursor` until the last page so large
+    /// accounts don't silently get only the first page.
+    async fn get_all<T: DeserializeOwned + Paginated>(&self, endpoint: &Endpoint) -> Result<T> {
+        let mut resp: T = self.get(endpoint).await?;
+        let mut pages = 1;
+        while let Some(cursor) = resp.cursor().map(str::to_string) {
+            if pages >= MAX_PAGES {
+                anyhow::bail!("Kalshi {} still paginating after {} pages", endpoint, MAX_PAGES);
+            }
+            let next: T = self.get(&endpoint.clone().query("cursor", cursor)).await?;
+            resp.extend(next);
+            pages += 1;
+        }
+        Ok(resp)
     }
 
-    async fn delete_request(&self, path: &str) -> Result<()> {
-        let headers = self.auth.headers("DELETE", path);
-        let url = format!("{}{}", self.base_url, path);
+    async fn post<T: DeserializeOwned>(&self, endpoint: &Endpoint, body: &serde_json::Value) -> Result<T> {
+        self.request(reqwest::Method::POST, endpoint, Some(body)).await
+    }
 
-        let mut req = self.client.delete(&url);
-        for (k, v) in &headers {
-            req = req.header(*k, v);
+    /// Create an order, deduping on its `client_order_id`: if the request
+    /// fails — possibly after it landed but its response was lost — look the
+    /// id up before giving up.
+    async fn submit_order(&self, order: &OrderRequest, body: &serde_json::Value) -> Result<OrderResult> {
+        match self.post::<CreateOrderResponse>(&Endpoint::orders(), body).await {
+            Ok(resp) => Ok(OrderResult {
+                created_ms: resp.order.created_ms(),
+                order_id: resp.order.order_id,
+                status: OrderStatus::parse(&resp.order.status),
+            }),
+            Err(e) if e.downcast_ref::<KalshiError>().is_some_and(KalshiError::is_order_refusal) => Err(e),
+            Err(e) => match self.order_by_client_id(&order.ticker, &order.client_order_id).await {
+                Ok(Some(found)) => {
+                    tracing::warn!(
+                        "Order {} on {} failed ({}) but exists on the exchange as {} — using it",
+                        order.client_order_id, order.ticker, e, found.order_id
+                    );
+                    Ok(found)
+                }
+                Ok(None) => Err(e),
+                Err(lookup) => Err(e.context(format!(
+                    "order {} state unknown: lookup failed: {}",
+                    order.client_order_id, lookup
+                ))),
+            },
         }
+    }
 
-        let resp = req.send().await?;
-        if !resp.status().is_success() {
+    async fn delete_request(&self, endpoint: &Endpoint) -> Result<()> {
+        let path = endpoint.path();
+        let resp = self.send(reqwest::Method::DELETE, &path, None).await?;
+        let status = resp.status();
+        if !status.is_success() {
             let err_body = resp.text().await.unwrap_or_default();
-            anyhow::bail!("Kalshi DELETE {} -> {}", path, err_body);
+            return Err(api_error(&reqwest::Method::DELETE, &path, status, &err_body).into());
         }
         Ok(())
     }
 }
 
+/// Pooled client tuned for bursts: concurrent cycles across series reuse
+/// warm connections (multiplexed over HTTP/2 when offered) instead of paying
+/// a TLS handshake per request.
+fn http_client(config: &Config) -> Result<reqwest::Client> {
+    let keepalive = (config.kalshi_keepalive_secs > 0).then(|| Duration::from_secs(config.kalshi_keepalive_secs));
+    let mut builder = reqwest::Client::builder()
+        .pool_max_idle_per_host(config.kalshi_pool_max_idle)
+        .pool_idle_timeout(Duration::from_secs(config.kalshi_pool_idle_secs))
+        .tcp_keepalive(keepalive)
+        .tcp_nodelay(true);
+    builder = if config.kalshi_http2 {
+        builder
+            .http2_keep_alive_interval(keepalive)
+            .http2_keep_alive_while_idle(true)
+            .http2_adaptive_window(true)
+    } else {
+        builder.http1_only()
+    };
+    Ok(builder.build()?)
+}
+
+/// Classify a failed call by status, then by Kalshi's error code.
+fn api_error(method: &reqwest::Method, path: &str, status: reqwest::StatusCode, body: &str) -> KalshiError {
+    let detail = format!("Kalshi {} {} -> {} : {}", method, path, status, body);
+    let code = serde_json::from_str::<ErrorResponse>(body)
+        .map(|r| format!("{} {}", r.error.code, r.error.message).to_lowercase())
+        .unwrap_or_default();
+    match status.as_u16() {
+        429 => KalshiError::RateLimited(detail),
+        401 | 403 => KalshiError::Unauthorized(detail),
+        500..=599 => KalshiError::Server(detail),
+        _ if code.contains("insufficient_balance") || code.contains("insufficient balance") => {
+            KalshiError::InsufficientBalance(detail)
+        }
+        _ if ["market_closed", "market_not_active", "market closed", "trading_is_paused", "exchange_closed"]
+            .iter()
+            .any(|c| code.contains(c)) =>
+        {
+            KalshiError::MarketClosed(detail)
+        }
+        _ if code.contains("already_exists") || code.contains("duplicate") => KalshiError::DuplicateOrder(detail),
+        _ if code.contains("post_only") || code.contains("post only") => KalshiError::PostOnlyCross(detail),
+        404 => KalshiError::NotFound(detail),
+        400 | 409 | 422 if path.contains("/orders") => KalshiError::InvalidOrder(detail),
+        _ => KalshiError::Other(detail),
+    }
+}
+
+/// A held position from its signed contract count (`market_exposure` is a
+/// cost in cents, not a count); None when flat.
+fn position(p: KalshiPosition) -> Option<Position> {
+    (p.position != 0).then(|| Position {
+        ticker: p.ticker,
+        side: if p.position > 0 { Side::Yes } else { Side::No },
+        count: p.position.unsigned_abs() as u32,
+    })
+}
+
+/// Contracts held on the order's ticker and side.
+fn held_on_side(positions: &[Position], order: &OrderRequest) -> u32 {
+    positions
+        .iter()
+        .find(|p| p.ticker == order.ticker && p.side == order.side)
+        .map_or(0, |p| p.count)
+}
+
+/// Order body for the create/batched-create endpoints. Market orders keep
+/// `yes_price` as their worst acceptable price; GTC sends no TIF fields.
+fn order_body(order: &OrderRequest, action: &str) -> serde_json::Value {
+    let side_str = match order.side {
+        Side::Yes => "yes",
+        Side::No => "no",
+    };
+    let mut body = serde_json::json!({
+        "ticker": order.ticker,
+        "action": action,
+        "side": side_str,
+        "count": order.shares,
+        "type": order.order_type.to_string(),
+        "yes_price": if order.side == Side::Yes { order.price_cents } else { 100 - order.price_cents },
+        "client_order_id": order.client_order_id,
+    });
+    if order.post_only {
+        body["post_only"] = true.into();
+    }
+    match order.time_in_force {
+        TimeInForce::Gtc => {}
+        TimeInForce::Ioc => body["time_in_force"] = "immediate_or_cancel".into(),
+        TimeInForce::Fok => body["time_in_force"] = "fill_or_kill".into(),
+        TimeInForce::GoodTill(ts) => body["expiration_ts"] = ts.into(),
+    }
+    body
+}
+
+/// Domain view of a Kalshi market, or None without a usable expiration.
+fn market_state(m: KalshiMarket, now: chrono::DateTime<chrono::Utc>) -> Option<MarketState> {
+    let exp_str = m.expected_expiration_time.as_deref().or(m.expiration_time.as_deref())?;
+    let exp = chrono::DateTime::parse_from_rfc3339(exp_str).ok()?.with_timezone(&chrono::Utc);
+    Some(MarketState {
+        strike: m.floor_strike.or(m.cap_strike),
+        strike_kind: StrikeKind::from_strikes(m.floor_strike, m.cap_strike),
+        ticker: m.ticker,
+        event_ticker: m.event_ticker,
+        title: m.title,
+        yes_bid: m.yes_bid,
+        yes_ask: m.yes_ask,
+        no_bid: m.no_bid,
+        no_ask: m.no_ask,
+        last_price: m.last_price,
+        volume: m.volume.unwrap_or(0),
+        volume_24h: m.volume_24h.unwrap_or(0),
+        open_interest: m.open_interest.unwrap_or(0),
+        open_time: m.open_time.unwrap_or_default(),
+        expiration_time: m.expected_expiration_time.or(m.expiration_time).unwrap_or_default(),
+        minutes_to_expiry: (exp - now).num_seconds() as f64 / 60.0,
+    })
+}
+
+/// Cents from a decimal dollar string like "0.0700", rounded; 0 if unparseable.
+fn dollars_to_cents(dollars: &str) -> i64 {
+    dollars.trim().parse::<f64>().map(|d| (d * 100.0).round() as i64).unwrap_or(0)
+}
+
+/// Most orders Kalshi accepts in one batched cancel.
+const MAX_BATCH_CANCEL: usize = 20;
+
+/// Guard against a cursor that never runs out.
+const MAX_PAGES: usize = 100;
+
 #[async_trait]
 impl Exchange for KalshiClient {
-    async fn active_market(&self, series_ticker: &str) -> Result<Option<MarketState>> {
-        let path = format!(
-            "/trade-api/v2/markets?series_ticker={}&status=open",
-            series_ticker
-        );
-        let resp: MarketsResponse = self.get(&path).await?;
+    async fn open_markets(&self, series_ticker: &str) -> Result<Vec<MarketState>> {
+        // Within the TTL only quotes are refreshed; re-list once nothing cached is still open
+        if let Some(cached) = self.cached_markets(series_ticker).filter(|c| !c.is_empty()) {
+            let live = self.requote(cached).await?;
+            if !live.is_empty() {
+                return Ok(live);
+            }
+        }
+
+        let endpoint = Endpoint::markets().query("series_ticker", series_ticker).query("status", "open");
+        let resp: MarketsResponse = self.get_all(&endpoint).await?;
 
         let now = chrono::Utc::now();
         let mut candidates: Vec<_> = resp
             .markets
             .into_iter()
-            .filter_map(|m| {
-                let exp_str = m.expected_expiration_time.as_deref()
-                    .or(m.expiration_time.as_deref())?;
-                let exp =
-                    chrono::DateTime::parse_from_rfc3339(exp_str)
-                        .ok()?
-                        .with_timezone(&chrono::Utc);
-                let mins = (exp - now).num_seconds() as f64 / 60.0;
-                Some((m, mins))
+            .filter_map(|m| market_state(m, now))
+            .filter(|m| m.minutes_to_expiry > 0.0)
+            .collect();
+
+        candidates.sort_by(|a, b| a.minutes_to_expiry.partial_cmp(&b.minutes_to_expiry).unwrap());
+        if !self.market_cache_ttl.is_zero() {
+            self.market_cache
+                .lock()
+                .expect("market cache poisoned")
+                .insert(series_ticker.to_string(), (Instant::now(), candidates.clone()));
+        }
+        Ok(candidates)
+    }
+
+    async fn upcoming_markets(&self, series_ticker: &str) -> Result<Vec<MarketState>> {
+        let endpoint = Endpoint::markets().query("series_ticker", series_ticker).query("status", "unopened");
+        let resp: MarketsResponse = self.get_all(&endpoint).await?;
+
+        let now = chrono::Utc::now();
+        let mut upcoming: Vec<_> = resp
+            .markets
+            .into_iter()
+            .filter_map(|m| market_state(m, now))
+            .collect();
+        // RFC 3339 UTC timestamps sort chronologically as strings
+        upcoming.sort_by(|a, b| a.open_time.cmp(&b.open_time));
+        Ok(upcoming)
+    }
+
+    async fn market(&self, ticker: &str) -> Result<MarketState> {
+        let resp: MarketResponse = self.get(&Endpoint::market(ticker)).await?;
+        market_state(resp.market, chrono::Utc::now())
+            .ok_or_else(|| anyhow::anyhow!("Kalshi market {} has no expiration time", ticker))
+    }
+
+    async fn series(&self, category: &str) -> Result<Vec<SeriesInfo>> {
+        let resp: SeriesListResponse = self.get(&Endpoint::series().query("category", category)).await?;
+        Ok(resp
+            .series
+            .unwrap_or_default()
+            .into_iter()
+            .map(|s| SeriesInfo {
+                ticker: s.ticker,
+                title: s.title,
+                category: s.category,
+                frequency: s.frequency,
             })
-            .filter(|(_, mins)| *mins > 0.0)
+            .collect())
+    }
+
+    async fn event(&self, event_ticker: &str) -> Result<Event> {
+        let endpoint = Endpoint::event(event_ticker).query("with_nested_markets", true);
+        let resp: EventResponse = self.get(&endpoint).await?;
+        let now = chrono::Utc::now();
+        let mut markets: Vec<MarketState> = resp
+            .event
+            .markets
+            .into_iter()
+            .filter_map(|m| market_state(m, now))
             .collect();
+        markets.sort_by(|a, b| a.strike.partial_cmp(&b.strike).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(Event {
+            event_ticker: resp.event.event_ticker,
+            series_ticker: resp.event.series_ticker,
+            title: resp.event.title,
+            markets,
+        })
+    }
 
-        candidates.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-
-        Ok(candidates.into_iter().next().map(|(m, mins)| MarketState {
-            ticker: m.ticker,
-            event_ticker: m.event_ticker,
-            title: m.title,
-            yes_bid: m.yes_bid,
-            yes_ask: m.yes_ask,
-            no_bid: m.no_bid,
-            no_ask: m.no_ask,
-            last_price: m.last_price,
-            volume: m.volume.unwrap_or(0),
-            volume_24h: m.volume_24h.unwrap_or(0),
-            open_interest: m.open_interest.unwrap_or(0),
-            expiration_time: m.expected_expiration_time.or(m.expiration_time).unwrap_or_default(),
-            minutes_to_expiry: mins,
-        }))
+    async fn market_candles(
+        &self,
+        ticker: &str,
+        since: chrono::DateTime<chrono::Utc>,
+        period_minutes: u32,
+    ) -> Result<Vec<ContractCandle>> {
+        let endpoint = Endpoint::candlesticks(ticker_series(ticker), ticker)
+            .query("start_ts", since.timestamp())
+            .query("end_ts", chrono::Utc::now().timestamp())
+            .query("period_interval", period_minutes);
+        let resp: CandlesticksResponse = self.get(&endpoint).await?;
+        let close = |c: Option<CandleClose>| c.and_then(|c| c.close);
+        let mut candles: Vec<ContractCandle> = resp
+            .candlesticks
+            .into_iter()
+            .map(|c| ContractCandle {
+                end_ts: c.end_period_ts,
+                yes_bid: close(c.yes_bid),
+                yes_ask: close(c.yes_ask),
+                last_price: close(c.price),
+                volume: c.volume.unwrap_or(0),
+            })
+            .collect();
+        candles.sort_by_key(|c| c.end_ts);
+        Ok(candles)
     }
 
     async fn orderbook(&self, ticker: &str) -> Result<Orderbook> {
-        let path = format!("/trade-api/v2/markets/{}/orderbook", ticker);
-        let resp: OrderbookResponse = self.get(&path).await?;
+        let resp: OrderbookResponse = self.get(&Endpoint::orderbook(ticker)).await?;
 
         let parse_side = |levels: Vec<Vec<u64>>| -> Vec<(u32, u32)> {
             levels
@@ -162,109 +556,234 @@ impl Exchange for KalshiClient {
     }
 
     async fn resting_orders(&self) -> Result<Vec<RestingOrder>> {
-        let path = "/trade-api/v2/portfolio/orders?status=resting";
-        let resp: OrdersResponse = self.get(path).await?;
+        let resp: OrdersResponse = self.get_all(&Endpoint::orders().query("status", "resting")).await?;
 
         Ok(resp
             .orders
             .into_iter()
             .map(|o| RestingOrder {
+                expires_ms: o.expires_ms(),
+                side: if o.side.as_deref() == Some("no") { Side::No } else { Side::Yes },
+                action: o.action.unwrap_or_else(|| "buy".into()),
                 order_id: o.order_id,
                 ticker: o.ticker,
             })
             .collect())
     }
 
+    async fn order_by_client_id(
+        &self,
+        ticker: &str,
+        client_order_id: &str,
+    ) -> Result<Option<OrderResult>> {
+        let resp: OrdersResponse = self.get_all(&Endpoint::orders().query("ticker", ticker)).await?;
+
+        Ok(resp
+            .orders
+            .into_iter()
+            .find(|o| o.client_order_id.as_deref() == Some(client_order_id))
+            .map(|o| OrderResult {
+                created_ms: o.created_ms(),
+                order_id: o.order_id,
+                status: OrderStatus::parse(&o.status),
+            }))
+    }
+
+    async fn get_order(&self, order_id: &str) -> Result<OrderState> {
+        let resp: OrderResponse = self.get(&Endpoint::order(order_id)).await?;
+        let o = resp.order;
+        let side = match o.side.as_deref() {
+            Some("no") => Side::No,
+            _ => Side::Yes,
+        };
+        let price_cents = match side {
+            Side::Yes => o.yes_price.or(o.no_price.map(|p| 100 - p)),
+            Side::No => o.no_price.or(o.yes_price.map(|p| 100 - p)),
+        }
+        .unwrap_or(0);
+        Ok(OrderState {
+            status: OrderStatus::parse(&o.status),
+            filled: o.fill_count.unwrap_or(0),
+            remaining: o.remaining_count.unwrap_or(0),
+            order_id: o.order_id,
+            ticker: o.ticker,
+            side,
+            price_cents,
+        })
+    }
+
     async fn cancel_order(&self, order_id: &str) -> Result<()> {
-        let path = format!("/trade-api/v2/portfolio/orders/{}", order_id);
-        self.delete_request(&path).await
+        self.delete_request(&Endpoint::order(order_id)).await
     }
 
-    async fn place_order(&self, order: &OrderRequest) -> Result<OrderResult> {
-        let path = "/trade-api/v2/portfolio/orders";
-        let side_str = match order.side {
-            Side::Yes => "yes",
-            Side::No => "no",
+    async fn cancel_all_orders(&self, scope: Option<&str>) -> Result<Vec<String>> {
+        let ids: Vec<String> = self
+            .resting_orders()
+            .await?
+            .into_iter()
+            .filter(|o| scope.is_none_or(|s| o.ticker == s || ticker_series(&o.ticker) == s))
+            .map(|o| o.order_id)
+            .collect();
+
+        let mut canceled = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(MAX_BATCH_CANCEL) {
+            let body = serde_json::json!({ "ids": chunk });
+            let resp: BatchCancelOrdersResponse = self
+                .request(reqwest::Method::DELETE, &Endpoint::batched_orders(), Some(&body))
+                .await?;
+            for entry in resp.orders {
+                match entry.error {
+                    None => canceled.push(entry.order_id),
+                    Some(e) => tracing::warn!("Kalshi batch cancel failed for {}: {}", entry.order_id, e),
+                }
+            }
+        }
+        Ok(canceled)
+    }
+
+    async fn amend_order(
+        &self,
+        order_id: &str,
+        new_price_cents: u32,
+        new_count: u32,
+    ) -> Result<OrderResult> {
+        if !(1..=99).contains(&new_price_cents) {
+            anyhow::bail!("Cannot amend order {} to {}¢ — price must be 1-99", order_id, new_price_cents);
+        }
+        // The amend endpoint wants the order's ticker/side/action echoed back
+        let current: OrderResponse = self.get(&Endpoint::order(order_id)).await?;
+        let o = current.order;
+        let (Some(side), Some(action)) = (o.side, o.action) else {
+            anyhow::bail!("Kalshi order {} has no side/action — cannot amend", order_id);
         };
-        let body = serde_json::json!({
-            "ticker": order.ticker,
-            "action": "buy",
-            "side": side_str,
-            "count": order.shares,
-            "type": "limit",
-            "yes_price": if order.side == Side::Yes { order.price_cents } else { 100 - order.price_cents },
-            "client_order_id": uuid::Uuid::new_v4().to_string(),
+        let yes_price = if side == "yes" { new_price_cents } else { 100 - new_price_cents };
+
+        let mut body = serde_json::json!({
+            "ticker": o.ticker,
+            "side": side,
+            "action": action,
+            "count": new_count,
+            "yes_price": yes_price,
         });
+        // Each amend needs its own id; reusing the old one would dedupe it away
+        if let Some(client_order_id) = o.client_order_id {
+            body["client_order_id"] = client_order_id.into();
+            body["updated_client_order_id"] = uuid::Uuid::new_v4().to_string().into();
+        }
 
-        let resp: CreateOrderResponse = self.post(path, &body).await?;
+        let resp: AmendOrderResponse = self.post(&Endpoint::amend_order(order_id), &body).await?;
         Ok(OrderResult {
+            created_ms: resp.order.created_ms(),
             order_id: resp.order.order_id,
-            status: resp.order.status,
+            status: OrderStatus::parse(&resp.order.status),
         })
     }
 
-    async fn sell_order(&self, order: &OrderRequest) -> Result<OrderResult> {
-        let path = "/trade-api/v2/portfolio/orders";
-        let side_str = match order.side {
-            Side::Yes => "yes",
-            Side::No => "no",
-        };
-        let body = serde_json::json!({
-            "ticker": order.ticker,
-            "action": "sell",
-            "side": side_str,
-            "count": order.shares,
-            "type": "limit",
-            "yes_price": if order.side == Side::Yes { order.price_cents } else { 100 - order.price_cents },
-            "client_order_id": uuid::Uuid::new_v4().to_string(),
-        });
+    async fn place_order(&self, order: &OrderRequest) -> Result<OrderResult> {
+        self.submit_order(order, &order_body(order, "buy")).await
+    }
 
-        let resp: CreateOrderResponse = self.post(path, &body).await?;
-        Ok(OrderResult {
-            order_id: resp.order.order_id,
-            status: resp.order.status,
+    async fn sell_order(&self, order: &OrderRequest) -> Result<SellResult> {
+        let held = held_on_side(&self.positions().await?, order);
+        if held == 0 {
+            return Err(SellExceedsPosition {
+                ticker: order.ticker.clone(),
+                side: order.side.clone(),
+                requested: order.shares,
+                held,
+            }
+            .into());
+        }
+        let mut order = order.clone();
+        if order.shares > held {
+            tracing::warn!(
+                "Sell of {} {} on {} exceeds the {} held — selling {}",
+                order.shares, order.side, order.ticker, held, held
+            );
+            order.shares = held;
+        }
+
+        let mut body = order_body(&order, "sell");
+        body["reduce_only"] = serde_json::Value::Bool(true);
+        // Should the sell still meet one of our own bids, cancel the resting bid, not the exit
+        body["self_trade_prevention_type"] = "maker".into();
+
+        Ok(SellResult {
+            order: self.submit_order(&order, &body).await?,
+            held,
+            remaining: held - order.shares,
         })
     }
 
     async fn positions(&self) -> Result<Vec<Position>> {
-        let path = "/trade-api/v2/portfolio/positions";
-        let resp: PositionsResponse = self.get(path).await?;
+        let resp: PositionsResponse = self.get_all(&Endpoint::positions()).await?;
 
-        Ok(resp
-            .market_positions
+        Ok(resp.market_positions.into_iter().filter_map(position).collect())
+    }
+
+    async fn fills(
+        &self,
+        ticker: Option<&str>,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<FillEvent>> {
+        let endpoint = Endpoint::fills().query("min_ts", since.timestamp()).query_opt("ticker", ticker);
+        let resp: FillsResponse = self.get_all(&endpoint).await?;
+
+        let mut fills: Vec<FillEvent> = resp
+            .fills
             .into_iter()
-            .filter(|p| p.market_exposure.unwrap_or(0) != 0)
-            .map(|p| {
-                let exposure = p.market_exposure.unwrap_or(0);
-                Position {
-                    ticker: p.ticker,
-                    side: if exposure > 0 { Side::Yes } else { Side::No },
-                    count: exposure.unsigned_abs() as u32,
-                }
+            .filter(|f| f.action == "buy")
+            .filter_map(|f| {
+                let (side, price) = match f.side.as_str() {
+                    "yes" => (Side::Yes, f.yes_price),
+                    "no" => (Side::No, f.no_price),
+                    _ => return None,
+                };
+                Some(FillEvent {
+                    order_id: f.order_id,
+                    ticker: f.ticker,
+                    side,
+                    shares: f.count,
+                    price_cents: price.unwrap_or(0),
+                    exchange_ts_ms: f
+                        .created_time
+                        .and_then(|t| chrono::DateTime::parse_from_rfc3339(&t).ok())
+                        .map(|t| t.timestamp_millis()),
+                })
             })
-            .collect())
+            .collect();
+        // Listed newest first
+        fills.sort_by_key(|f| f.exchange_ts_ms);
+        Ok(fills)
     }
 
     async fn settlements(&self, ticker: &str) -> Result<Vec<Settlement>> {
-        let path = format!("/trade-api/v2/portfolio/settlements?ticker={}", ticker);
-        let resp: SettlementsResponse = self.get(&path).await?;
+        let resp: SettlementsResponse = self.get_all(&Endpoint::settlements().query("ticker", ticker)).await?;
 
         Ok(resp
             .settlements
             .into_iter()
             .map(|s| {
                 let pnl = s.revenue.unwrap_or(0);
+                let (yes, no) = (s.yes_count.unwrap_or(0), s.no_count.unwrap_or(0));
+                // The side held is whichever has contracts; both only if hedged
+                let (side, count, cost) = if no > yes {
+                    (Side::No, no, s.no_total_cost.unwrap_or(0))
+                } else {
+                    (Side::Yes, yes, s.yes_total_cost.unwrap_or(0))
+                };
                 Settlement {
                     ticker: s.ticker,
-                    side: Side::Yes,
-                    count: 0,
-                    price_cents: 0,
+                    side,
+                    count,
+                    price_cents: if count > 0 { (cost / count as i64) as u32 } else { 0 },
                     result: if pnl > 0 {
                         "win".into()
                     } else {
                         "loss".into()
                     },
                     pnl_cents: pnl,
+                    fee_cents: s.fee_cost.as_deref().map(dollars_to_cents).unwrap_or(0),
                     settled_time: s.settled_time.unwrap_or_default(),
                     market_result: s.market_result,
                 }
@@ -272,9 +791,62 @@ impl Exchange for KalshiClient {
             .collect())
     }
 
+    async fn market_result(&self, ticker: &str) -> Result<Option<String>> {
+        let resp: MarketResponse = self.get(&Endpoint::market(ticker)).await?;
+        Ok(resp.market.result.filter(|r| !r.is_empty()))
+    }
+
     async fn balance(&self) -> Result<u64> {
-        let path = "/trade-api/v2/portfolio/balance";
-        let resp: BalanceResponse = self.get(path).await?;
+        let resp: BalanceResponse = self.get(&Endpoint::balance()).await?;
         Ok(resp.balance)
     }
+
+    async fn exchange_status(&self) -> Result<ExchangeStatus> {
+        let (status_endpoint, schedule_endpoint) = (Endpoint::exchange_status(), Endpoint::exchange_schedule());
+        let (status, schedule) = tokio::join!(
+            self.get::<ExchangeStatusResponse>(&status_endpoint),
+            self.get::<ExchangeScheduleResponse>(&schedule_endpoint),
+        );
+        let status = status?;
+        Ok(ExchangeStatus {
+            exchange_active: status.exchange_active,
+            trading_active: status.trading_active,
+            resume_time: status.exchange_estimated_resume_time.filter(|t| !t.is_empty()),
+            maintenance_windows: schedule?
+                .schedule
+                .maintenance_windows
+                .into_iter()
+                .map(|w| (w.start_datetime, w.end_datetime))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(json: &str) -> Vec<Position> {
+        let resp: PositionsResponse = serde_json::from_str(json).expect("positions parse");
+        resp.market_positions.into_iter().filter_map(position).collect()
+    }
+
+    #[test]
+    fn no_position_exit_sells_what_is_held() {
+        let held = positions(
+            r#"{"market_positions":[
+                {"ticker":"KXBTC15M-25OCT171415-15","position":-7,"market_exposure":266,"resting_orders_count":0},
+                {"ticker":"KXETH15M-25OCT171415-15","position":3,"market_exposure":141,"resting_orders_count":1},
+                {"ticker":"KXSOL15M-25OCT171415-15","position":0,"market_exposure":0,"resting_orders_count":0}
+            ],"cursor":null}"#,
+        );
+        assert_eq!(held.len(), 2);
+        assert_eq!((held[0].side.clone(), held[0].count), (Side::No, 7));
+        assert_eq!((held[1].side.clone(), held[1].count), (Side::Yes, 3));
+
+        let no_exit = OrderRequest::new("KXBTC15M-25OCT171415-15".into(), Side::No, 10, 40);
+        assert_eq!(held_on_side(&held, &no_exit), 7);
+        let yes_exit = OrderRequest::new("KXBTC15M-25OCT171415-15".into(), Side::Yes, 7, 60);
+        assert_eq!(held_on_side(&held, &yes_exit), 0);
+    }
 }
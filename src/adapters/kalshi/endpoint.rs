@@ -0,0 +1,114 @@
+/// Root of every versioned Kalshi REST path; bump here to move API versions.
+pub const API_ROOT: &str = "/trade-api/v2";
+
+/// A REST path under [`API_ROOT`]. Segments and query values are
+/// percent-encoded as they're added, so tickers, ids, and cursors with
+/// reserved characters can't break the URL or the signed path.
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    url: reqwest::Url,
+}
+
+impl Endpoint {
+    fn new(segments: &[&str]) -> Self {
+        let mut url = reqwest::Url::parse(&format!("http://kalshi{}", API_ROOT)).expect("static url");
+        url.path_segments_mut().expect("http url has a path").extend(segments);
+        Self { url }
+    }
+
+    pub fn markets() -> Self {
+        Self::new(&["markets"])
+    }
+
+    pub fn market(ticker: &str) -> Self {
+        Self::new(&["markets", ticker])
+    }
+
+    pub fn orderbook(ticker: &str) -> Self {
+        Self::new(&["markets", ticker, "orderbook"])
+    }
+
+    pub fn series() -> Self {
+        Self::new(&["series"])
+    }
+
+    pub fn event(event_ticker: &str) -> Self {
+        Self::new(&["events", event_ticker])
+    }
+
+    pub fn candlesticks(series_ticker: &str, ticker: &str) -> Self {
+        Self::new(&["series", series_ticker, "markets", ticker, "candlesticks"])
+    }
+
+    pub fn orders() -> Self {
+        Self::new(&["portfolio", "orders"])
+    }
+
+    pub fn order(order_id: &str) -> Self {
+        Self::new(&["portfolio", "orders", order_id])
+    }
+
+    pub fn amend_order(order_id: &str) -> Self {
+        Self::new(&["portfolio", "orders", order_id, "amend"])
+    }
+
+    pub fn batched_orders() -> Self {
+        Self::new(&["portfolio", "orders", "batched"])
+    }
+
+    pub fn positions() -> Self {
+        Self::new(&["portfolio", "positions"])
+    }
+
+    pub fn fills() -> Self {
+        Self::new(&["portfolio", "fills"])
+    }
+
+    pub fn settlements() -> Self {
+        Self::new(&["portfolio", "settlements"])
+    }
+
+    pub fn balance() -> Self {
+        Self::new(&["portfolio", "balance"])
+    }
+
+    pub fn exchange_status() -> Self {
+        Self::new(&["exchange", "status"])
+    }
+
+    pub fn exchange_schedule() -> Self {
+        Self::new(&["exchange", "schedule"])
+    }
+
+    pub fn login() -> Self {
+        Self::new(&["login"])
+    }
+
+    /// Append `key=value` to the query string.
+    pub fn query(mut self, key: &str, value: impl std::fmt::Display) -> Self {
+        self.url.query_pairs_mut().append_pair(key, &value.to_string());
+        self
+    }
+
+    /// Append `key=value` only when there's a value.
+    pub fn query_opt(self, key: &str, value: Option<impl std::fmt::Display>) -> Self {
+        match value {
+            Some(v) => self.query(key, v),
+            None => self,
+        }
+    }
+
+    /// Encoded path and query, as appended to the base URL and signed.
+    pub fn path(&self) -> String {
+        match self.url.query().filter(|q| !q.is_empty()) {
+            Some(q) => format!("{}?{}", self.url.path(), q),
+            None => self.url.path().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.path())
+    }
+}
@@ -0,0 +1,315 @@
+use super::websocket::KalshiWsEvent;
+use crate::core::types::{LabelledGauge, MarketLifecycleEvent, OrderbookUpdate};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+/// Queue fill, as a share of capacity, at which a consumer counts as lagging.
+const LAG_THRESHOLD: f64 = 0.75;
+
+/// Fans Kalshi WS events out to any number of consumers, each on its own
+/// channel: the full stream in order, or one typed stream (books,
+/// lifecycle). A consumer that drops its receiver is pruned on the next publish.
+///
+/// A consumer that falls behind never loses fills or other events — the WS
+/// waits for it — but books queue up outside its channel with only the
+/// newest kept per ticker, since each one is a full book. A drain task hands
+/// them over as room frees, so the newest book arrives even if the market
+/// then goes quiet.
+#[derive(Clone, Default)]
+pub struct KalshiEventBus {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+}
+
+#[derive(Clone)]
+struct Subscriber {
+    name: &'static str,
+    sink: Sink,
+    capacity: usize,
+    lag: Arc<Mutex<Lag>>,
+}
+
+#[derive(Clone)]
+enum Sink {
+    All(mpsc::Sender<KalshiWsEvent>),
+    Orderbooks(mpsc::Sender<OrderbookUpdate>),
+    Lifecycle(mpsc::Sender<MarketLifecycleEvent>),
+}
+
+#[derive(Default)]
+struct Lag {
+    /// Books waiting for channel room, oldest first, at most one per ticker.
+    backlog: Vec<OrderbookUpdate>,
+    /// Books replaced in the backlog by a newer one before delivery.
+    coalesced: u64,
+    lagging: bool,
+    /// A drain task is waiting for channel room.
+    draining: bool,
+}
+
+impl Sink {
+    fn free(&self) -> usize {
+        match self {
+            Sink::All(tx) => tx.capacity(),
+            Sink::Orderbooks(tx) => tx.capacity(),
+            Sink::Lifecycle(tx) => tx.capacity(),
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        match self {
+            Sink::All(tx) => tx.is_closed(),
+            Sink::Orderbooks(tx) => tx.is_closed(),
+            Sink::Lifecycle(tx) => tx.is_closed(),
+        }
+    }
+
+    fn takes_books(&self) -> bool {
+        matches!(self, Sink::All(_) | Sink::Orderbooks(_))
+    }
+
+    /// Hand over a book if the channel has room, or give it back.
+    fn try_send_book(&self, book: OrderbookUpdate) -> Result<(), OrderbookUpdate> {
+        // A closed receiver drops the book; it's pruned after the publish
+        match self {
+            Sink::All(tx) => match tx.try_send(KalshiWsEvent::Orderbook(book)) {
+                Err(TrySendError::Full(KalshiWsEvent::Orderbook(book))) => Err(book),
+                _ => Ok(()),
+            },
+            Sink::Orderbooks(tx) => match tx.try_send(book) {
+                Err(TrySendError::Full(book)) => Err(book),
+                _ => Ok(()),
+            },
+            Sink::Lifecycle(_) => Ok(()),
+        }
+    }
+
+    /// Wait until the channel has room; false once the receiver is gone.
+    async fn room(&self) -> bool {
+        match self {
+            Sink::All(tx) => tx.reserve().await.is_ok(),
+            Sink::Orderbooks(tx) => tx.reserve().await.is_ok(),
+            Sink::Lifecycle(tx) => tx.reserve().await.is_ok(),
+        }
+    }
+
+    async fn send_book(&self, book: OrderbookUpdate) {
+        // A closed receiver fails the send; it's pruned after the publish
+        match self {
+            Sink::All(tx) => {
+                let _ = tx.send(KalshiWsEvent::Orderbook(book)).await;
+            }
+            Sink::Orderbooks(tx) => {
+                let _ = tx.send(book).await;
+            }
+            _ => {}
+        }
+    }
+
+    /// Deliver anything but a book, waiting for room.
+    async fn send(&self, event: &KalshiWsEvent) {
+        match (self, event) {
+            (Sink::All(tx), _) => {
+                let _ = tx.send(event.clone()).await;
+            }
+            (Sink::Lifecycle(tx), KalshiWsEvent::MarketLifecycle(lifecycle)) => {
+                let _ = tx.send(lifecycle.clone()).await;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Subscriber {
+    /// Queue a book behind any already waiting, replacing an older one for
+    /// the same ticker, then hand over as many as fit without waiting. What
+    /// doesn't fit is left to the drain task.
+    fn offer_book(&self, book: &OrderbookUpdate) {
+        let mut lag = self.lag.lock().expect("event bus poisoned");
+        match lag.backlog.iter_mut().find(|b| b.ticker == book.ticker) {
+            Some(older) => {
+                *older = book.clone();
+                lag.coalesced += 1;
+            }
+            None => lag.backlog.push(book.clone()),
+        }
+        self.drain_locked(&mut lag);
+        if !lag.backlog.is_empty() && !lag.draining {
+            lag.draining = true;
+            self.spawn_drain();
+        }
+    }
+
+    /// Hand over waiting books while the channel has room.
+    fn drain_books(&self) {
+        self.drain_locked(&mut self.lag.lock().expect("event bus poisoned"));
+    }
+
+    /// Sent under the lag lock, so books leave the backlog in order.
+    fn drain_locked(&self, lag: &mut Lag) {
+        while !lag.backlog.is_empty() {
+            let book = lag.backlog.remove(0);
+            if let Err(book) = self.sink.try_send_book(book) {
+                lag.backlog.insert(0, book);
+                break;
+            }
+        }
+    }
+
+    /// Hand over the backlog as the consumer frees room, until it's empty or
+    /// the receiver is gone.
+    fn spawn_drain(&self) {
+        let subscriber = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let open = subscriber.sink.room().await;
+                let mut lag = subscriber.lag.lock().expect("event bus poisoned");
+                if open {
+                    subscriber.drain_locked(&mut lag);
+                } else {
+                    lag.backlog.clear();
+                }
+                if lag.backlog.is_empty() {
+                    lag.draining = false;
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Deliver every waiting book, so later events stay in order behind them.
+    async fn flush_books(&self) {
+        let backlog = std::mem::take(&mut self.lag.lock().expect("event bus poisoned").backlog);
+        for book in backlog {
+            self.sink.send_book(book).await;
+        }
+    }
+
+    /// Warn when the consumer crosses the lag threshold, and when it recovers.
+    fn check_lag(&self) {
+        let depth = self.capacity - self.sink.free();
+        let mut lag = self.lag.lock().expect("event bus poisoned");
+        let lagging = !lag.backlog.is_empty() || depth as f64 >= self.capacity as f64 * LAG_THRESHOLD;
+        if lagging && !lag.lagging {
+            tracing::warn!(
+                "Kalshi event consumer '{}' falling behind: {}/{} queued, {} books waiting",
+                self.name, depth, self.capacity, lag.backlog.len()
+            );
+        } else if !lagging && lag.lagging {
+            tracing::info!("Kalshi event consumer '{}' caught up ({} books coalesced so far)", self.name, lag.coalesced);
+        }
+        lag.lagging = lagging;
+    }
+}
+
+impl KalshiEventBus {
+    /// Every event, in the order the WS produced them.
+    pub fn subscribe_all(&self, name: &'static str, capacity: usize) -> mpsc::Receiver<KalshiWsEvent> {
+        let (tx, rx) = mpsc::channel(capacity);
+        self.add(name, Sink::All(tx), capacity);
+        rx
+    }
+
+    /// Full books (deltas already applied).
+    pub fn orderbooks(&self, name: &'static str, capacity: usize) -> mpsc::Receiver<OrderbookUpdate> {
+        let (tx, rx) = mpsc::channel(capacity);
+        self.add(name, Sink::Orderbooks(tx), capacity);
+        rx
+    }
+
+    pub fn lifecycle(&self, name: &'static str, capacity: usize) -> mpsc::Receiver<MarketLifecycleEvent> {
+        let (tx, rx) = mpsc::channel(capacity);
+        self.add(name, Sink::Lifecycle(tx), capacity);
+        rx
+    }
+
+    fn add(&self, name: &'static str, sink: Sink, capacity: usize) {
+        let subscriber = Subscriber { name, sink, capacity, lag: Arc::default() };
+        self.subscribers.lock().expect("event bus poisoned").push(subscriber);
+    }
+
+    /// Deliver an event to every interested consumer. Returns false once no
+    /// consumer is left.
+    pub async fn publish(&self, event: KalshiWsEvent) -> bool {
+        let subscribers = self.subscribers.lock().expect("event bus poisoned").clone();
+        for subscriber in &subscribers {
+            match &event {
+                KalshiWsEvent::Orderbook(book) if subscriber.sink.takes_books() => {
+                    subscriber.offer_book(book);
+                }
+                KalshiWsEvent::Orderbook(_) => {}
+                _ => {
+                    match subscriber.sink {
+                        Sink::All(_) => subscriber.flush_books().await,
+                        Sink::Orderbooks(_) => subscriber.drain_books(),
+                        _ => {}
+                    }
+                    subscriber.sink.send(&event).await;
+                }
+            }
+            subscriber.check_lag();
+        }
+
+        let mut subscribers = self.subscribers.lock().expect("event bus poisoned");
+        subscribers.retain(|s| !s.sink.is_closed());
+        !subscribers.is_empty()
+    }
+
+    /// Queue depth, waiting books, and coalesced books per consumer.
+    pub fn gauges(&self) -> Vec<LabelledGauge> {
+        let subscribers = self.subscribers.lock().expect("event bus poisoned");
+        let mut gauges = Vec::new();
+        for subscriber in subscribers.iter() {
+            let lag = subscriber.lag.lock().expect("event bus poisoned");
+            let labels = vec![("consumer", subscriber.name.to_string())];
+            gauges.push(LabelledGauge {
+                name: "kalshi_ws_consumer_queue_depth",
+                labels: labels.clone(),
+                value: (subscriber.capacity - subscriber.sink.free()) as f64,
+            });
+            gauges.push(LabelledGauge {
+                name: "kalshi_ws_consumer_books_waiting",
+                labels: labels.clone(),
+                value: lag.backlog.len() as f64,
+            });
+            gauges.push(LabelledGauge {
+                name: "kalshi_ws_consumer_books_coalesced_total",
+                labels,
+                value: lag.coalesced as f64,
+            });
+        }
+        gauges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn book(ticker: &str, ts: i64) -> KalshiWsEvent {
+        KalshiWsEvent::Orderbook(OrderbookUpdate {
+            ticker: ticker.into(),
+            yes: vec![(40, 10)],
+            no: vec![(55, 10)],
+            exchange_ts_ms: Some(ts),
+        })
+    }
+
+    #[tokio::test]
+    async fn lagging_subscriber_gets_newest_book_without_another_publish() {
+        let bus = KalshiEventBus::default();
+        let mut rx = bus.orderbooks("test", 1);
+        bus.publish(book("KXBTC15M-A", 1)).await;
+        // Channel full: these wait outside it, the second replacing the first
+        bus.publish(book("KXBTC15M-B", 1)).await;
+        bus.publish(book("KXBTC15M-B", 2)).await;
+
+        assert_eq!(rx.recv().await.expect("first book").ticker, "KXBTC15M-A");
+        let newest = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("drained without a publish")
+            .expect("channel open");
+        assert_eq!((newest.ticker.as_str(), newest.exchange_ts_ms), ("KXBTC15M-B", Some(2)));
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::core::types::LabelledGauge;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the request latency histogram buckets.
+const LATENCY_BUCKETS: [f64; 8] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Per-endpoint request counters since startup: latency histogram, responses
+/// by status, and retries. Endpoints are path templates, so every ticker or
+/// order id lands in the same series.
+#[derive(Default)]
+pub struct ApiMetrics {
+    endpoints: Mutex<HashMap<(String, String), EndpointStats>>,
+}
+
+#[derive(Default)]
+struct EndpointStats {
+    /// Count per bucket of `LATENCY_BUCKETS`, non-cumulative.
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    latency_sum_secs: f64,
+    count: u64,
+    /// Responses by status code, or "error" when no response arrived.
+    statuses: HashMap<String, u64>,
+    retries: u64,
+}
+
+impl ApiMetrics {
+    /// Record one attempt: its outcome and how long it took.
+    pub fn record(&self, method: &str, path: &str, status: &str, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        let mut endpoints = self.endpoints.lock().expect("api metrics poisoned");
+        let stats = endpoints.entry((method.to_string(), endpoint(path))).or_default();
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|le| secs <= *le) {
+            stats.buckets[i] += 1;
+        }
+        stats.latency_sum_secs += secs;
+        stats.count += 1;
+        *stats.statuses.entry(status.to_string()).or_default() += 1;
+    }
+
+    pub fn record_retry(&self, method: &str, path: &str) {
+        let mut endpoints = self.endpoints.lock().expect("api metrics poisoned");
+        endpoints.entry((method.to_string(), endpoint(path))).or_default().retries += 1;
+    }
+
+    /// Everything recorded so far, in Prometheus histogram/counter naming.
+    pub fn gauges(&self) -> Vec<LabelledGauge> {
+        let endpoints = self.endpoints.lock().expect("api metrics poisoned");
+        let mut gauges = Vec::new();
+        for ((method, path), stats) in endpoints.iter() {
+            let labels = vec![("method", method.clone()), ("endpoint", path.clone())];
+            let with = |extra: (&'static str, String)| {
+                let mut l = labels.clone();
+                l.push(extra);
+                l
+            };
+            let mut cumulative = 0;
+            for (le, n) in LATENCY_BUCKETS.iter().zip(stats.buckets) {
+                cumulative += n;
+                gauges.push(LabelledGauge {
+                    name: "kalshi_api_latency_seconds_bucket",
+                    labels: with(("le", le.to_string())),
+                    value: cumulative as f64,
+                });
+            }
+            gauges.push(LabelledGauge {
+                name: "kalshi_api_latency_seconds_bucket",
+                labels: with(("le", "+Inf".into())),
+                value: stats.count as f64,
+            });
+            gauges.push(LabelledGauge {
+                name: "kalshi_api_latency_seconds_sum",
+                labels: labels.clone(),
+                value: stats.latency_sum_secs,
+            });
+            gauges.push(LabelledGauge {
+                name: "kalshi_api_latency_seconds_count",
+                labels: labels.clone(),
+                value: stats.count as f64,
+            });
+            for (status, n) in &stats.statuses {
+                gauges.push(LabelledGauge {
+                    name: "kalshi_api_responses_total",
+                    labels: with(("status", status.clone())),
+                    value: *n as f64,
+                });
+            }
+            gauges.push(LabelledGauge {
+                name: "kalshi_api_retries_total",
+                labels,
+                value: stats.retries as f64,
+            });
+        }
+        gauges
+    }
+}
+
+/// Path template: query dropped, tickers and ids (segments with uppercase
+/// letters or digits, other than the API version) replaced by `{id}`.
+fn endpoint(path: &str) -> String {
+    path.split('?')
+        .next()
+        .unwrap_or(path)
+        .split('/')
+        .map(|seg| {
+            let variable = seg != "v2" && seg.chars().any(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+            if variable { "{id}" } else { seg }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -1,4 +1,10 @@
 pub mod auth;
 pub mod client;
+pub mod endpoint;
+pub mod event_bus;
+pub mod metrics;
+pub mod rate_limit;
+pub mod retry;
 pub mod types;
 pub mod websocket;
+pub mod ws_messages;
@@ -0,0 +1,76 @@
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Token bucket: `rate` tokens per second, holding at most one second's
+/// worth so a quiet spell can't build up a burst Kalshi would reject.
+pub struct TokenBucket {
+    rate: f64,
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    last: Instant,
+    /// No tokens are handed out before this (set after a 429).
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    pub fn new(rate: f64) -> Self {
+        let rate = rate.max(0.1);
+        Self {
+            rate,
+            state: Mutex::new(BucketState { tokens: rate, last: Instant::now(), paused_until: None }),
+        }
+    }
+
+    /// Wait for a token. Waiters queue on the mutex, so tokens go out in
+    /// arrival order.
+    pub async fn acquire(&self) {
+        let mut state = self.state.lock().await;
+        if let Some(until) = state.paused_until.take() {
+            tokio::time::sleep_until(until).await;
+            state.tokens = 0.0;
+            state.last = Instant::now();
+        }
+        let now = Instant::now();
+        state.tokens = (state.tokens + now.duration_since(state.last).as_secs_f64() * self.rate).min(self.rate);
+        state.last = now;
+        if state.tokens < 1.0 {
+            let wait = Duration::from_secs_f64((1.0 - state.tokens) / self.rate);
+            tokio::time::sleep(wait).await;
+            state.tokens = 1.0;
+            state.last = Instant::now();
+        }
+        state.tokens -= 1.0;
+    }
+
+    /// Hand out nothing for `delay` — the exchange says we're over its limit.
+    pub async fn pause(&self, delay: Duration) {
+        let mut state = self.state.lock().await;
+        let until = Instant::now() + delay;
+        state.paused_until = Some(state.paused_until.map_or(until, |p| p.max(until)));
+    }
+}
+
+/// Separate read (GET) and write (POST/DELETE) buckets, matching Kalshi's
+/// per-tier read and write limits.
+pub struct RateLimiter {
+    reads: TokenBucket,
+    writes: TokenBucket,
+}
+
+impl RateLimiter {
+    pub fn new(reads_per_sec: f64, writes_per_sec: f64) -> Self {
+        Self { reads: TokenBucket::new(reads_per_sec), writes: TokenBucket::new(writes_per_sec) }
+    }
+
+    pub fn bucket(&self, method: &reqwest::Method) -> &TokenBucket {
+        if method == reqwest::Method::GET {
+            &self.reads
+        } else {
+            &self.writes
+        }
+    }
+}
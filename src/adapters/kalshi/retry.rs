@@ -0,0 +1,52 @@
+use crate::core::types::Config;
+use rand::Rng;
+use std::time::Duration;
+
+/// Retry policy for Kalshi REST calls: exponential backoff with full jitter,
+/// on 429, 5xx, and connect/timeout errors. POSTs only retry failures that
+/// never reached the exchange (429, connect errors): after a 5xx or timeout
+/// the order may exist, so the caller reconciles by `client_order_id`
+/// instead. The WS loops use the same backoff between reconnect attempts
+/// (`RetryPolicy::ws_reconnect`).
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 = never retry).
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Random delay in `[0, min(max_delay, base_delay * 2^retry)]`, so clients
+    /// that failed together don't retry in lockstep.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let ceiling = self
+            .base_delay
+            .saturating_mul(1u32.checked_shl(retry).unwrap_or(u32::MAX))
+            .min(self.max_delay);
+        let ms = ceiling.as_millis() as u64;
+        Duration::from_millis(rand::thread_rng().gen_range(0..=ms))
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff between WS reconnect attempts, from `WS_RECONNECT_*`.
+    /// `max_retries` is unused — the loops reconnect until closed; the
+    /// daemon halts entries after `ws_reconnect_halt_after` failures instead.
+    pub fn ws_reconnect(config: &Config) -> Self {
+        Self {
+            max_retries: u32::MAX,
+            base_delay: Duration::from_millis(config.ws_reconnect_initial_ms.max(1)),
+            max_delay: Duration::from_secs(config.ws_reconnect_max_secs.max(1)),
+        }
+    }
+}
+
+pub fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Errors that never produced a response and are worth another try.
+pub fn is_transient(err: &reqwest::Error) -> bool {
+    err.is_connect() || err.is_timeout()
+}
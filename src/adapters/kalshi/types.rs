@@ -1,5 +1,68 @@
 use serde::Deserialize;
 
+/// A list response that may continue on another page. Kalshi returns an
+/// empty or missing `cursor` on the last page.
+pub trait Paginated {
+    fn cursor(&self) -> Option<&str>;
+    /// Append the items of the next page.
+    fn extend(&mut self, next: Self);
+}
+
+impl Paginated for MarketsResponse {
+    fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref().filter(|c| !c.is_empty())
+    }
+
+    fn extend(&mut self, next: Self) {
+        self.markets.extend(next.markets);
+        self.cursor = next.cursor;
+    }
+}
+
+impl Paginated for PositionsResponse {
+    fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref().filter(|c| !c.is_empty())
+    }
+
+    fn extend(&mut self, next: Self) {
+        self.market_positions.extend(next.market_positions);
+        self.cursor = next.cursor;
+    }
+}
+
+impl Paginated for OrdersResponse {
+    fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref().filter(|c| !c.is_empty())
+    }
+
+    fn extend(&mut self, next: Self) {
+        self.orders.extend(next.orders);
+        self.cursor = next.cursor;
+    }
+}
+
+impl Paginated for FillsResponse {
+    fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref().filter(|c| !c.is_empty())
+    }
+
+    fn extend(&mut self, next: Self) {
+        self.fills.extend(next.fills);
+        self.cursor = next.cursor;
+    }
+}
+
+impl Paginated for SettlementsResponse {
+    fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref().filter(|c| !c.is_empty())
+    }
+
+    fn extend(&mut self, next: Self) {
+        self.settlements.extend(next.settlements);
+        self.cursor = next.cursor;
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct MarketsResponse {
     #[serde(default)]
@@ -31,6 +94,13 @@ pub struct KalshiMarket {
     pub open_interest: Option<u64>,
     pub result: Option<String>,
     pub series_ticker: Option<String>,
+    pub floor_strike: Option<f64>,
+    pub cap_strike: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarketResponse {
+    pub market: KalshiMarket,
 }
 
 #[derive(Debug, Deserialize)]
@@ -49,10 +119,128 @@ pub struct CreateOrderResponse {
     pub order: OrderInfo,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BatchCancelOrdersResponse {
+    #[serde(default)]
+    pub orders: Vec<BatchCancelEntry>,
+}
+
+/// One slot of a batched cancel: the order id, and why it failed if it did.
+#[derive(Debug, Deserialize)]
+pub struct BatchCancelEntry {
+    #[serde(default)]
+    pub order_id: String,
+    pub error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AmendOrderResponse {
+    pub order: OrderInfo,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct OrderInfo {
     pub order_id: String,
     pub status: String,
+    #[serde(default)]
+    pub created_time: Option<String>,
+}
+
+impl OrderInfo {
+    pub fn created_ms(&self) -> Option<i64> {
+        rfc3339_ms(self.created_time.as_deref())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CandlesticksResponse {
+    #[serde(default)]
+    pub candlesticks: Vec<KalshiCandlestick>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KalshiCandlestick {
+    pub end_period_ts: i64,
+    pub yes_bid: Option<CandleClose>,
+    pub yes_ask: Option<CandleClose>,
+    pub price: Option<CandleClose>,
+    pub volume: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CandleClose {
+    pub close: Option<u32>,
+}
+
+/// Error body of a failed REST call: `{"error": {"code", "message"}}`.
+#[derive(Debug, Deserialize)]
+pub struct ErrorResponse {
+    pub error: KalshiErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KalshiErrorDetail {
+    #[serde(default)]
+    pub code: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SeriesListResponse {
+    #[serde(default)]
+    pub series: Option<Vec<KalshiSeries>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KalshiSeries {
+    pub ticker: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub frequency: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventResponse {
+    pub event: KalshiEvent,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KalshiEvent {
+    pub event_ticker: String,
+    #[serde(default)]
+    pub series_ticker: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub markets: Vec<KalshiMarket>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExchangeStatusResponse {
+    pub exchange_active: bool,
+    pub trading_active: bool,
+    pub exchange_estimated_resume_time: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExchangeScheduleResponse {
+    pub schedule: ExchangeSchedule,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExchangeSchedule {
+    #[serde(default)]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceWindow {
+    pub start_datetime: String,
+    pub end_datetime: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -64,12 +252,15 @@ pub struct BalanceResponse {
 pub struct PositionsResponse {
     #[serde(default)]
     pub market_positions: Vec<KalshiPosition>,
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct KalshiPosition {
     pub ticker: String,
-    pub market_exposure: Option<i64>,
+    /// Contracts held: positive YES, negative NO, 0 flat.
+    #[serde(default)]
+    pub position: i64,
     pub resting_orders_count: Option<u32>,
 }
 
@@ -77,6 +268,12 @@ pub struct KalshiPosition {
 pub struct OrdersResponse {
     #[serde(default)]
     pub orders: Vec<KalshiOrder>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OrderResponse {
+    pub order: KalshiOrder,
 }
 
 #[derive(Debug, Deserialize)]
@@ -84,12 +281,66 @@ pub struct KalshiOrder {
     pub order_id: String,
     pub ticker: String,
     pub status: String,
+    #[serde(default)]
+    pub side: Option<String>,
+    #[serde(default)]
+    pub action: Option<String>,
+    #[serde(default)]
+    pub yes_price: Option<u32>,
+    #[serde(default)]
+    pub no_price: Option<u32>,
+    #[serde(default)]
+    pub fill_count: Option<u32>,
+    #[serde(default)]
+    pub remaining_count: Option<u32>,
+    #[serde(default)]
+    pub client_order_id: Option<String>,
+    #[serde(default)]
+    pub created_time: Option<String>,
+    /// Set for good-till orders: when the exchange expires what's unfilled.
+    #[serde(default)]
+    pub expiration_time: Option<String>,
+}
+
+impl KalshiOrder {
+    pub fn created_ms(&self) -> Option<i64> {
+        rfc3339_ms(self.created_time.as_deref())
+    }
+
+    pub fn expires_ms(&self) -> Option<i64> {
+        rfc3339_ms(self.expiration_time.as_deref())
+    }
+}
+
+/// An RFC 3339 API timestamp as unix millis.
+fn rfc3339_ms(t: Option<&str>) -> Option<i64> {
+    Some(chrono::DateTime::parse_from_rfc3339(t?).ok()?.timestamp_millis())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FillsResponse {
+    #[serde(default)]
+    pub fills: Vec<KalshiFill>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KalshiFill {
+    pub order_id: String,
+    pub ticker: String,
+    pub side: String,
+    pub action: String,
+    pub count: u32,
+    pub yes_price: Option<u32>,
+    pub no_price: Option<u32>,
+    pub created_time: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SettlementsResponse {
     #[serde(default)]
     pub settlements: Vec<KalshiSettlement>,
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -98,4 +349,11 @@ pub struct KalshiSettlement {
     pub market_result: String,
     pub revenue: Option<i64>,
     pub settled_time: Option<String>,
+    pub yes_count: Option<u32>,
+    pub no_count: Option<u32>,
+    /// Cents paid for the contracts on each side.
+    pub yes_total_cost: Option<i64>,
+    pub no_total_cost: Option<i64>,
+    /// Dollars, as a decimal string (e.g. "0.0700").
+    pub fee_cost: Option<String>,
 }
@@ -1,33 +1,108 @@
 use crate::adapters::kalshi::auth::KalshiAuth;
+use crate::adapters::kalshi::event_bus::KalshiEventBus;
+use crate::adapters::kalshi::retry::RetryPolicy;
+use crate::adapters::kalshi::ws_messages::*;
+use crate::adapters::ws_health::WsHealth;
+use crate::adapters::ws_tap::RawTap;
 use crate::core::types::*;
 use futures_util::{SinkExt, StreamExt};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio_tungstenite::connect_async_with_config;
 use tokio_tungstenite::tungstenite;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Debug, Clone)]
 pub enum KalshiWsEvent {
     Orderbook(OrderbookUpdate),
     Fill(FillEvent),
     MarketLifecycle(MarketLifecycleEvent),
+    Ticker(TickerUpdate),
+    Trade(ContractTrade),
+    Position(PositionUpdate),
+    Order(OrderState),
+    /// An orderbook subscription skipped a `seq`: these tickers' books are
+    /// dropped until the resubscription snapshot arrives.
+    BookGap { tickers: Vec<String> },
+    /// The server refused a subscribe or unsubscribe command.
+    CommandFailed {
+        cmd: String,
+        channels: Vec<String>,
+        tickers: Vec<String>,
+        code: Option<i64>,
+        message: String,
+    },
+    /// A server `error` message not tied to one of our commands.
+    Error {
+        kind: WsErrorKind,
+        code: Option<i64>,
+        message: String,
+    },
     Disconnected,
+    /// Reconnected, and every subscription still wanted (on `tickers` markets)
+    /// was sent again; books arrive with the new snapshots.
+    Resubscribed { tickers: usize },
+    /// A reconnect attempt failed; `consecutive` counts failures since the
+    /// last successful connect.
+    ReconnectFailed { consecutive: u32 },
+}
+
+/// What a server `error` message is about, by code and text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WsErrorKind {
+    /// Authentication missing or expired; a reconnect signs fresh headers.
+    Auth,
+    UnknownChannel,
+    TooManySubscriptions,
+    Other,
+}
+
+impl WsErrorKind {
+    fn classify(code: Option<i64>, message: &str) -> Self {
+        let message = message.to_ascii_lowercase();
+        match code {
+            Some(9) => Self::Auth,
+            Some(8) => Self::UnknownChannel,
+            _ if message.contains("auth") => Self::Auth,
+            _ if message.contains("too many") || message.contains("limit") => Self::TooManySubscriptions,
+            _ => Self::Other,
+        }
+    }
 }
 
 pub struct KalshiWsSender {
     cmd_tx: mpsc::Sender<WsCommand>,
+    health: Arc<WsHealth>,
+    closing: CancellationToken,
+    task: tokio::task::JoinHandle<()>,
 }
 
 enum WsCommand {
-    Subscribe { channels: Vec<String>, ticker: String },
+    Subscribe { channels: Vec<String>, tickers: Vec<String> },
+    /// Account-wide channels that take no market tickers.
+    SubscribeAll { channels: Vec<String> },
     Unsubscribe { channels: Vec<String>, ticker: String },
+    Sever { offline: std::time::Duration },
 }
 
 impl KalshiWsSender {
     pub async fn subscribe(&self, channels: Vec<String>, ticker: &str) {
-        let _ = self.cmd_tx.send(WsCommand::Subscribe {
-            channels,
-            ticker: ticker.to_string(),
-        }).await;
+        self.subscribe_markets(channels, vec![ticker.to_string()]).await;
+    }
+
+    /// Subscribe a batch of markets to the same channels in one message.
+    pub async fn subscribe_markets(&self, channels: Vec<String>, tickers: Vec<String>) {
+        if tickers.is_empty() {
+            return;
+        }
+        let _ = self.cmd_tx.send(WsCommand::Subscribe { channels, tickers }).await;
+    }
+
+    /// Subscribe to channels across all markets, e.g. `market_positions`.
+    pub async fn subscribe_all(&self, channels: Vec<String>) {
+        let _ = self.cmd_tx.send(WsCommand::SubscribeAll { channels }).await;
     }
 
     pub async fn unsubscribe(&self, channels: Vec<String>, ticker: &str) {
@@ -36,34 +111,225 @@ impl KalshiWsSender {
             ticker: ticker.to_string(),
         }).await;
     }
+
+    /// Connection health gauges for the metrics push.
+    pub fn health_gauges(&self) -> Vec<LabelledGauge> {
+        self.health.gauges()
+    }
+
+    /// Unsubscribe everything, send a Close frame, and wait for the WS task
+    /// to finish, giving up after `timeout`.
+    pub async fn close(self, timeout: Duration) {
+        self.closing.cancel();
+        let abort = self.task.abort_handle();
+        if tokio::time::timeout(timeout, self.task).await.is_err() {
+            tracing::warn!("Kalshi WS didn't close within {}s — dropping it", timeout.as_secs());
+            abort.abort();
+        }
+    }
+
+    /// Drop the connection and stay offline for `offline` before the usual
+    /// reconnect — for outage drills.
+    pub async fn sever(&self, offline: std::time::Duration) {
+        let _ = self.cmd_tx.send(WsCommand::Sever { offline }).await;
+    }
+}
+
+/// Orderbook state for one connection: books rebuilt from each
+/// subscription's snapshot, and the last `seq` seen per subscription id.
+#[derive(Default)]
+struct BookState {
+    books: HashMap<String, OrderbookUpdate>,
+    /// Orderbook subscription id per ticker.
+    sids: HashMap<String, u64>,
+    seqs: HashMap<u64, u64>,
+    /// Gapped subscriptions being replaced; their late deltas are ignored.
+    stale: HashSet<u64>,
+}
+
+impl BookState {
+    /// Replace a ticker's book with a subscription snapshot.
+    fn insert_snapshot(&mut self, book: OrderbookUpdate, sid: Option<u64>, seq: Option<u64>) {
+        if let (Some(sid), Some(seq)) = (sid, seq) {
+            self.sids.insert(book.ticker.clone(), sid);
+            self.seqs.insert(sid, seq);
+        }
+        self.books.insert(book.ticker.clone(), book);
+    }
+
+    /// Drop an unsubscribed ticker's book, so a long-running connection
+    /// doesn't keep every expired market's book.
+    fn forget(&mut self, ticker: &str) {
+        self.books.remove(ticker);
+        if let Some(sid) = self.sids.remove(ticker) {
+            if !self.sids.values().any(|s| *s == sid) {
+                self.seqs.remove(&sid);
+                self.stale.remove(&sid);
+            }
+        }
+    }
+}
+
+/// Subscriptions the caller asked for and hasn't dropped, kept across
+/// connections and replayed after each reconnect.
+#[derive(Default)]
+struct Desired {
+    /// Channels per market ticker.
+    markets: HashMap<String, HashSet<String>>,
+    /// Account-wide channels.
+    global: HashSet<String>,
+}
+
+impl Desired {
+    fn subscribe(&mut self, channels: &[String], tickers: &[String]) {
+        for ticker in tickers {
+            self.markets.entry(ticker.clone()).or_default().extend(channels.iter().cloned());
+        }
+    }
+
+    fn unsubscribe(&mut self, channels: &[String], ticker: &str) {
+        if let Some(wanted) = self.markets.get_mut(ticker) {
+            for channel in channels {
+                wanted.remove(channel);
+            }
+            if wanted.is_empty() {
+                self.markets.remove(ticker);
+            }
+        }
+    }
+}
+
+/// Kalshi's error code for a subscribe that's already in place.
+const ALREADY_SUBSCRIBED: i64 = 6;
+
+/// A command awaiting its `subscribed`/`unsubscribed`/`error` reply.
+struct PendingCommand {
+    cmd: &'static str,
+    channels: Vec<String>,
+    tickers: Vec<String>,
+    /// Subscribes get one `subscribed` reply per channel.
+    awaiting: usize,
+}
+
+/// Command ids for one connection, and the commands not yet answered.
+#[derive(Default)]
+struct Commands {
+    next_id: u64,
+    pending: HashMap<u64, PendingCommand>,
+    /// Subscription ids the server has confirmed.
+    sids: HashSet<u64>,
+}
+
+impl Commands {
+    /// Build a command message under a fresh id and remember it until the reply.
+    fn message(
+        &mut self,
+        cmd: &'static str,
+        channels: Vec<String>,
+        tickers: Vec<String>,
+        params: serde_json::Value,
+    ) -> tungstenite::Message {
+        self.next_id += 1;
+        let id = self.next_id;
+        let awaiting = if cmd == "subscribe" { channels.len().max(1) } else { 1 };
+        self.pending.insert(id, PendingCommand { cmd, channels, tickers, awaiting });
+        let msg = serde_json::json!({ "id": id, "cmd": cmd, "params": params });
+        tungstenite::Message::Text(msg.to_string())
+    }
+
+    /// Count one acknowledgement against a pending command.
+    fn ack(&mut self, id: u64, sid: Option<u64>) {
+        let Some(pending) = self.pending.get_mut(&id) else { return };
+        if let Some(sid) = sid {
+            if pending.cmd == "subscribe" {
+                self.sids.insert(sid);
+            } else {
+                self.sids.remove(&sid);
+            }
+        }
+        tracing::debug!(
+            "Kalshi WS {} #{} acknowledged (sid {:?}) for {}",
+            pending.cmd, id, sid, pending.channels.join(",")
+        );
+        pending.awaiting = pending.awaiting.saturating_sub(1);
+        if pending.awaiting == 0 {
+            self.pending.remove(&id);
+        }
+    }
+
+    /// Resolve a command's `error` reply; an already-active subscription isn't a failure.
+    /// Anything not matching a pending command is reported as a plain `Error`.
+    fn fail(&mut self, id: Option<u64>, code: Option<i64>, message: String) -> Option<KalshiWsEvent> {
+        let Some(pending) = id.and_then(|id| self.pending.remove(&id)) else {
+            let kind = WsErrorKind::classify(code, &message);
+            return Some(KalshiWsEvent::Error { kind, code, message });
+        };
+        if pending.cmd == "subscribe" && code == Some(ALREADY_SUBSCRIBED) {
+            return None;
+        }
+        Some(KalshiWsEvent::CommandFailed {
+            cmd: pending.cmd.to_string(),
+            channels: pending.channels,
+            tickers: pending.tickers,
+            code,
+            message,
+        })
+    }
+}
+
+/// Connection settings taken from config when the WS starts.
+struct WsOptions {
+    url: String,
+    ping_interval: Duration,
+    idle_timeout: Duration,
+    tap: Option<RawTap>,
+    backoff: RetryPolicy,
 }
 
 pub async fn connect(
-    ws_url: &str,
+    config: &Config,
     auth: &KalshiAuth,
-    event_tx: mpsc::Sender<KalshiWsEvent>,
+    bus: KalshiEventBus,
+    tap: Option<RawTap>,
 ) -> anyhow::Result<KalshiWsSender> {
     let (cmd_tx, cmd_rx) = mpsc::channel::<WsCommand>(32);
 
-    let url = ws_url.to_string();
-    let auth_headers = auth.headers("GET", "/trade-api/ws/v2");
+    let options = WsOptions {
+        url: config.kalshi_ws_url.clone(),
+        ping_interval: Duration::from_secs(config.kalshi_ws_ping_secs.max(1)),
+        idle_timeout: Duration::from_secs(config.kalshi_ws_idle_secs.max(1)),
+        tap,
+        backoff: RetryPolicy::ws_reconnect(config),
+    };
+    let auth = auth.clone();
+    let health = Arc::new(WsHealth::new("kalshi"));
+
+    let closing = CancellationToken::new();
 
-    let event_tx_clone = event_tx.clone();
-    tokio::spawn(async move {
-        ws_loop(&url, auth_headers, event_tx_clone, cmd_rx).await;
+    let loop_health = health.clone();
+    let loop_closing = closing.clone();
+    let task = tokio::spawn(async move {
+        ws_loop(&options, &auth, &loop_health, &loop_closing, &bus, cmd_rx).await;
     });
 
-    Ok(KalshiWsSender { cmd_tx })
+    Ok(KalshiWsSender { cmd_tx, health, closing, task })
 }
 
 async fn ws_loop(
-    url: &str,
-    auth_headers: Vec<(&'static str, String)>,
-    event_tx: mpsc::Sender<KalshiWsEvent>,
+    options: &WsOptions,
+    auth: &KalshiAuth,
+    health: &WsHealth,
+    closing: &CancellationToken,
+    bus: &KalshiEventBus,
     mut cmd_rx: mpsc::Receiver<WsCommand>,
 ) {
+    let url = options.url.as_str();
+    let mut failures: u32 = 0;
+    let mut desired = Desired::default();
+    let mut connected_before = false;
     loop {
         tracing::info!("Kalshi WS connecting to {}", url);
+        let mut offline: Option<Duration> = None;
 
         let mut request = match url.parse::<http::Uri>() {
             Ok(uri) => {
@@ -80,18 +346,20 @@ async fn ws_loop(
                     Ok(r) => r,
                     Err(e) => {
                         tracing::error!("Failed to build WS request: {}", e);
-                        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                        tokio::time::sleep(options.backoff.max_delay).await;
                         continue;
                     }
                 }
             }
             Err(e) => {
                 tracing::error!("Invalid WS URL: {}", e);
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                tokio::time::sleep(options.backoff.max_delay).await;
                 continue;
             }
         };
 
+        // Signed per attempt: a reconnect after a long outage needs a fresh timestamp
+        let auth_headers = auth.headers("GET", "/trade-api/ws/v2");
         for (k, v) in &auth_headers {
             request.headers_mut().insert(
                 http::header::HeaderName::from_static(
@@ -100,6 +368,7 @@ async fn ws_loop(
                         "KALSHI-ACCESS-TIMESTAMP" => "kalshi-access-timestamp",
                         "KALSHI-ACCESS-SIGNATURE" => "kalshi-access-signature",
                         "Content-Type" => "content-type",
+                        "Authorization" => "authorization",
                         _ => continue,
                     }
                 ),
@@ -110,16 +379,63 @@ async fn ws_loop(
         match connect_async_with_config(request, None, false).await {
             Ok((ws, _)) => {
                 tracing::info!("Kalshi WS connected");
+                failures = 0;
+                health.on_connect();
                 let (mut write, mut read) = ws.split();
+                let mut state = BookState::default();
+                let mut commands = Commands::default();
+                let mut ping = tokio::time::interval_at(
+                    tokio::time::Instant::now() + options.ping_interval,
+                    options.ping_interval,
+                );
+                // Any frame counts — Kalshi pings every 10s even with no subscriptions
+                let mut last_seen = tokio::time::Instant::now();
+
+                if connected_before {
+                    if let Err(e) = replay_subscriptions(&mut write, &desired, &mut commands).await {
+                        tracing::warn!("Kalshi WS send error: {}", e);
+                        health.on_disconnect();
+                        bus.publish(KalshiWsEvent::Disconnected).await;
+                        tokio::time::sleep(options.backoff.delay(1)).await;
+                        continue;
+                    }
+                    let tickers = desired.markets.len();
+                    if !bus.publish(KalshiWsEvent::Resubscribed { tickers }).await {
+                        return;
+                    }
+                }
+                connected_before = true;
 
                 loop {
                     tokio::select! {
                         msg = read.next() => {
+                            if matches!(msg, Some(Ok(_))) {
+                                last_seen = tokio::time::Instant::now();
+                            }
                             match msg {
                                 Some(Ok(tungstenite::Message::Text(text))) => {
-                                    if let Some(event) = parse_kalshi_message(&text) {
-                                        if event_tx.send(event).await.is_err() {
-                                            tracing::warn!("Kalshi WS receiver dropped");
+                                    health.on_message();
+                                    if let Some(tap) = &options.tap {
+                                        tap.forward("kalshi", &text);
+                                    }
+                                    if let Some(event) = parse_kalshi_message(&text, &mut state, &mut commands, health) {
+                                        if let KalshiWsEvent::CommandFailed { cmd, channels, tickers, code, message } = &event {
+                                            tracing::warn!(
+                                                "Kalshi WS {} {} on {} refused (code {:?}): {}",
+                                                cmd, channels.join(","), tickers.join(","), code, message
+                                            );
+                                        }
+                                        if let KalshiWsEvent::Error { kind, code, message } = &event {
+                                            tracing::warn!("Kalshi WS error {:?} (code {:?}): {}", kind, code, message);
+                                        }
+                                        if let KalshiWsEvent::BookGap { tickers } = &event {
+                                            if let Err(e) = resubscribe_books(&mut write, &state, tickers, &mut commands).await {
+                                                tracing::warn!("Kalshi WS send error: {}", e);
+                                                break;
+                                            }
+                                        }
+                                        if !bus.publish(event).await {
+                                            tracing::warn!("Kalshi WS has no consumers left");
                                             return;
                                         }
                                     }
@@ -139,37 +455,75 @@ async fn ws_loop(
                                 _ => {}
                             }
                         }
+                        _ = ping.tick() => {
+                            if let Err(e) = write.send(tungstenite::Message::Ping(Vec::new())).await {
+                                tracing::warn!("Kalshi WS ping failed: {}", e);
+                                break;
+                            }
+                        }
+                        _ = closing.cancelled() => {
+                            close_gracefully(&mut write, &mut commands).await;
+                            health.on_disconnect();
+                            tracing::info!("Kalshi WS closed");
+                            return;
+                        }
+                        _ = tokio::time::sleep_until(last_seen + options.idle_timeout) => {
+                            tracing::warn!(
+                                "Kalshi WS silent for {}s — assuming a dead connection",
+                                options.idle_timeout.as_secs()
+                            );
+                            break;
+                        }
                         cmd = cmd_rx.recv() => {
                             match cmd {
-                                Some(WsCommand::Subscribe { channels, ticker }) => {
-                                    let msg = serde_json::json!({
-                                        "id": 1,
-                                        "cmd": "subscribe",
-                                        "params": {
-                                            "channels": channels,
-                                            "market_tickers": [ticker]
-                                        }
+                                Some(WsCommand::Subscribe { channels, tickers }) => {
+                                    desired.subscribe(&channels, &tickers);
+                                    let params = serde_json::json!({
+                                        "channels": channels,
+                                        "market_tickers": tickers
                                     });
-                                    if let Err(e) = write.send(tungstenite::Message::Text(msg.to_string().into())).await {
+                                    let summary = match tickers.as_slice() {
+                                        [ticker] => ticker.clone(),
+                                        _ => format!("{} markets", tickers.len()),
+                                    };
+                                    let msg = commands.message("subscribe", channels.clone(), tickers, params);
+                                    if let Err(e) = write.send(msg).await {
+                                        tracing::warn!("Kalshi WS send error: {}", e);
+                                        break;
+                                    }
+                                    tracing::info!("Kalshi WS subscribed to {} on {}", channels.join(","), summary);
+                                }
+                                Some(WsCommand::SubscribeAll { channels }) => {
+                                    desired.global.extend(channels.iter().cloned());
+                                    let params = serde_json::json!({ "channels": channels });
+                                    let msg = commands.message("subscribe", channels.clone(), Vec::new(), params);
+                                    if let Err(e) = write.send(msg).await {
                                         tracing::warn!("Kalshi WS send error: {}", e);
                                         break;
                                     }
-                                    tracing::info!("Kalshi WS subscribed to {} on {}", channels.join(","), ticker);
+                                    tracing::info!("Kalshi WS subscribed to {}", channels.join(","));
                                 }
                                 Some(WsCommand::Unsubscribe { channels, ticker }) => {
-                                    let msg = serde_json::json!({
-                                        "id": 2,
-                                        "cmd": "unsubscribe",
-                                        "params": {
-                                            "channels": channels,
-                                            "market_tickers": [ticker]
-                                        }
+                                    if channels.iter().any(|c| c == "orderbook_delta") {
+                                        state.forget(&ticker);
+                                    }
+                                    desired.unsubscribe(&channels, &ticker);
+                                    let params = serde_json::json!({
+                                        "channels": channels,
+                                        "market_tickers": [ticker]
                                     });
-                                    if let Err(e) = write.send(tungstenite::Message::Text(msg.to_string().into())).await {
+                                    let msg = commands.message("unsubscribe", channels, vec![ticker], params);
+                                    if let Err(e) = write.send(msg).await {
                                         tracing::warn!("Kalshi WS send error: {}", e);
                                         break;
                                     }
                                 }
+                                Some(WsCommand::Sever { offline: duration }) => {
+                                    tracing::warn!("Kalshi WS severed for {}s", duration.as_secs());
+                                    let _ = write.close().await;
+                                    offline = Some(duration);
+                                    break;
+                                }
                                 None => {
                                     tracing::warn!("Kalshi WS command channel closed");
                                     return;
@@ -179,87 +533,343 @@ async fn ws_loop(
                     }
                 }
 
-                let _ = event_tx.send(KalshiWsEvent::Disconnected).await;
+                health.on_disconnect();
+                bus.publish(KalshiWsEvent::Disconnected).await;
             }
             Err(e) => {
-                tracing::warn!("Kalshi WS connect failed: {}", e);
+                failures += 1;
+                tracing::warn!("Kalshi WS connect failed ({} in a row): {}", failures, e);
+                if !bus.publish(KalshiWsEvent::ReconnectFailed { consecutive: failures }).await {
+                    return;
+                }
             }
         }
 
-        tracing::info!("Kalshi WS reconnecting in 5s");
-        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        let delay = offline.unwrap_or_else(|| options.backoff.delay(failures));
+        tracing::info!("Kalshi WS reconnecting in {}ms", delay.as_millis());
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = closing.cancelled() => return,
+        }
     }
 }
 
-fn parse_kalshi_message(text: &str) -> Option<KalshiWsEvent> {
-    let v: serde_json::Value = serde_json::from_str(text).ok()?;
-    let msg_type = v.get("type")?.as_str()?;
-
-    match msg_type {
-        "orderbook_snapshot" | "orderbook_delta" => {
-            let ticker = v.get("msg")?.get("market_ticker")?.as_str()?.to_string();
-
-            let parse_levels = |key: &str| -> Vec<(u32, u32)> {
-                v.get("msg")
-                    .and_then(|m| m.get(key))
-                    .and_then(|s| s.as_array())
-                    .map(|arr| {
-                        arr.iter()
-                            .filter_map(|level| {
-                                let l = level.as_array()?;
-                                if l.len() >= 2 {
-                                    Some((l[0].as_u64()? as u32, l[1].as_u64()? as u32))
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect()
-                    })
-                    .unwrap_or_default()
-            };
+/// Unsubscribe every acknowledged subscription, then send a Close frame.
+/// Best effort: the process is exiting either way.
+async fn close_gracefully<S>(write: &mut S, commands: &mut Commands)
+where
+    S: futures_util::Sink<tungstenite::Message, Error = tungstenite::Error> + Unpin,
+{
+    let sids: Vec<u64> = commands.sids.iter().copied().collect();
+    if !sids.is_empty() {
+        let params = serde_json::json!({ "sids": sids });
+        let unsubscribe = commands.message("unsubscribe", Vec::new(), Vec::new(), params);
+        if let Err(e) = write.send(unsubscribe).await {
+            tracing::debug!("Kalshi WS unsubscribe on close failed: {}", e);
+        }
+    }
+    if let Err(e) = write.close().await {
+        tracing::debug!("Kalshi WS close frame failed: {}", e);
+    }
+}
 
-            Some(KalshiWsEvent::Orderbook(OrderbookUpdate {
-                ticker,
-                yes: parse_levels("yes"),
-                no: parse_levels("no"),
-            }))
+/// Send every wanted subscription on a fresh connection.
+async fn replay_subscriptions<S>(
+    write: &mut S,
+    desired: &Desired,
+    commands: &mut Commands,
+) -> Result<(), tungstenite::Error>
+where
+    S: futures_util::Sink<tungstenite::Message, Error = tungstenite::Error> + Unpin,
+{
+    if !desired.global.is_empty() {
+        let channels: Vec<String> = desired.global.iter().cloned().collect();
+        let params = serde_json::json!({ "channels": channels });
+        write.send(commands.message("subscribe", channels, Vec::new(), params)).await?;
+    }
+    // One message per distinct channel set
+    let mut batches: BTreeMap<Vec<String>, Vec<String>> = BTreeMap::new();
+    for (ticker, channels) in &desired.markets {
+        let mut channels: Vec<String> = channels.iter().cloned().collect();
+        channels.sort();
+        batches.entry(channels).or_default().push(ticker.clone());
+    }
+    for (channels, tickers) in batches {
+        let params = serde_json::json!({ "channels": channels, "market_tickers": tickers });
+        write.send(commands.message("subscribe", channels, tickers, params)).await?;
+    }
+    tracing::info!(
+        "Kalshi WS resubscribed {} market(s) and {} account channel(s)",
+        desired.markets.len(), desired.global.len()
+    );
+    Ok(())
+}
+
+/// Drop the gapped orderbook subscription and subscribe its tickers again;
+/// Kalshi opens every new subscription with a fresh snapshot.
+async fn resubscribe_books<S>(
+    write: &mut S,
+    state: &BookState,
+    tickers: &[String],
+    commands: &mut Commands,
+) -> Result<(), tungstenite::Error>
+where
+    S: futures_util::Sink<tungstenite::Message, Error = tungstenite::Error> + Unpin,
+{
+    let mut sids: Vec<u64> = tickers.iter().filter_map(|t| state.sids.get(t).copied()).collect();
+    sids.dedup();
+    let channels = vec!["orderbook_delta".to_string()];
+    let unsubscribe = commands.message(
+        "unsubscribe",
+        channels.clone(),
+        tickers.to_vec(),
+        serde_json::json!({ "sids": sids }),
+    );
+    let subscribe = commands.message(
+        "subscribe",
+        channels.clone(),
+        tickers.to_vec(),
+        serde_json::json!({ "channels": channels, "market_tickers": tickers }),
+    );
+    write.send(unsubscribe).await?;
+    write.send(subscribe).await?;
+    tracing::warn!("Kalshi WS book gap on {} — resubscribing for a snapshot", tickers.join(","));
+    Ok(())
+}
+
+/// Parse one WS message. Orderbook deltas are applied to the ticker's last
+/// snapshot in `state`, so every `Orderbook` event carries the full book; a
+/// skipped `seq` drops the subscription's books and reports a `BookGap`.
+/// Command replies resolve against `commands`; refusals become `CommandFailed`.
+fn parse_kalshi_message(
+    text: &str,
+    state: &mut BookState,
+    commands: &mut Commands,
+    health: &WsHealth,
+) -> Option<KalshiWsEvent> {
+    let frame: Frame = match serde_json::from_str(text) {
+        Ok(frame) => frame,
+        Err(e) => {
+            health.on_parse_failure();
+            tracing::debug!("Kalshi WS unparseable frame ({}): {}", e, text);
+            return None;
         }
-        "fill" => {
-            let msg = v.get("msg")?;
-            let order_id = msg.get("order_id")?.as_str()?.to_string();
-            let ticker = msg.get("market_ticker")?.as_str()?.to_string();
-            let side_str = msg.get("side")?.as_str()?;
-            let side = match side_str {
-                "yes" => Side::Yes,
-                "no" => Side::No,
-                _ => return None,
+    };
+    match decode_frame(&frame, state, commands) {
+        Ok(event) => event,
+        Err(e) => {
+            health.on_parse_failure();
+            tracing::warn!("Kalshi WS {} message doesn't match its schema: {} — {}", frame.kind, e, text);
+            None
+        }
+    }
+}
+
+/// Decode `frame.msg` as its channel's struct and turn it into an event.
+fn decode_frame(
+    frame: &Frame,
+    state: &mut BookState,
+    commands: &mut Commands,
+) -> Result<Option<KalshiWsEvent>, serde_json::Error> {
+    fn msg<T: serde::de::DeserializeOwned>(frame: &Frame) -> Result<T, serde_json::Error> {
+        T::deserialize(&frame.msg)
+    }
+    fn msg_or_default<T: serde::de::DeserializeOwned + Default>(frame: &Frame) -> Result<T, serde_json::Error> {
+        if frame.msg.is_null() { Ok(T::default()) } else { msg(frame) }
+    }
+    let (sid, seq) = (frame.sid, frame.seq);
+
+    let event = match frame.kind.as_str() {
+        "subscribed" | "unsubscribed" | "ok" => {
+            let reply: SubscribedMsg = msg_or_default(frame)?;
+            if let Some(id) = frame.id {
+                commands.ack(id, reply.sid.or(sid));
+            }
+            None
+        }
+        "error" => {
+            let error: ErrorMsg = msg_or_default(frame)?;
+            let message = error.msg.unwrap_or_else(|| "unknown error".into());
+            commands.fail(frame.id, error.code, message)
+        }
+        "orderbook_snapshot" => {
+            let snapshot: OrderbookSnapshotMsg = msg(frame)?;
+            let mut book = OrderbookUpdate {
+                ticker: snapshot.market_ticker,
+                yes: snapshot.yes,
+                no: snapshot.no,
+                exchange_ts_ms: first_ts(&[&snapshot.ts]),
+            };
+            book.yes.sort_unstable_by_key(|&(p, _)| p);
+            book.no.sort_unstable_by_key(|&(p, _)| p);
+            state.insert_snapshot(book.clone(), sid, seq);
+            Some(KalshiWsEvent::Orderbook(book))
+        }
+        "orderbook_delta" => {
+            let delta: OrderbookDeltaMsg = msg(frame)?;
+            if let (Some(sid), Some(seq)) = (sid, seq) {
+                if state.stale.contains(&sid) {
+                    return Ok(None);
+                }
+                if let Some(last) = state.seqs.insert(sid, seq).filter(|last| seq != last + 1) {
+                    tracing::warn!("Kalshi WS sid {} jumped from seq {} to {}", sid, last, seq);
+                    state.seqs.remove(&sid);
+                    state.stale.insert(sid);
+                    let tickers: Vec<String> =
+                        state.sids.iter().filter(|(_, s)| **s == sid).map(|(t, _)| t.clone()).collect();
+                    for t in &tickers {
+                        state.books.remove(t);
+                    }
+                    return Ok((!tickers.is_empty()).then_some(KalshiWsEvent::BookGap { tickers }));
+                }
+            }
+            // A delta before its snapshot has nothing to apply to
+            let Some(book) = state.books.get_mut(&delta.market_ticker) else {
+                return Ok(None);
+            };
+            let levels = match delta.side {
+                Side::Yes => &mut book.yes,
+                Side::No => &mut book.no,
             };
-            let shares = msg.get("count")?.as_u64()? as u32;
-            let price_cents = msg.get("yes_price")
-                .and_then(|p| p.as_u64())
-                .map(|p| if side == Side::Yes { p as u32 } else { 100 - p as u32 })
+            apply_delta(levels, delta.price, delta.delta);
+            if let Some(ts) = first_ts(&[&delta.ts]) {
+                book.exchange_ts_ms = Some(ts);
+            }
+            Some(KalshiWsEvent::Orderbook(book.clone()))
+        }
+        "fill" => {
+            let fill: FillMsg = msg(frame)?;
+            let price_cents = fill
+                .yes_price
+                .map(|p| if fill.side == Side::Yes { p } else { 100 - p })
                 .unwrap_or(0);
-
             Some(KalshiWsEvent::Fill(FillEvent {
-                order_id,
-                ticker,
-                side,
-                shares,
+                order_id: fill.order_id,
+                ticker: fill.market_ticker,
+                shares: fill.count,
                 price_cents,
+                exchange_ts_ms: first_ts(&[&fill.ts, &fill.created_time]),
+                side: fill.side,
+            }))
+        }
+        "user_order" => {
+            let order: UserOrderMsg = msg(frame)?;
+            let price_cents = order
+                .yes_price_cents()
+                .map(|p| if order.side == Side::Yes { p } else { 100 - p })
+                .unwrap_or(0);
+            Some(KalshiWsEvent::Order(OrderState {
+                status: OrderStatus::parse(&order.status),
+                filled: order.fill_count.as_ref().map_or(0, Count::contracts),
+                remaining: order.remaining_count.as_ref().map_or(0, Count::contracts),
+                order_id: order.order_id,
+                ticker: order.ticker,
+                side: order.side,
+                price_cents,
+            }))
+        }
+        "ticker" | "ticker_v2" => {
+            let quote: TickerMsg = msg(frame)?;
+            Some(KalshiWsEvent::Ticker(TickerUpdate {
+                ticker: quote.market_ticker,
+                yes_bid: quote.yes_bid,
+                yes_ask: quote.yes_ask,
+                last_price: quote.price,
+                volume: quote.volume,
+                volume_delta: quote.volume_delta,
+                exchange_ts_ms: first_ts(&[&quote.ts]),
+            }))
+        }
+        "trade" => {
+            let trade: TradeMsg = msg(frame)?;
+            Some(KalshiWsEvent::Trade(ContractTrade {
+                ticker: trade.market_ticker,
+                yes_price: trade.yes_price,
+                count: trade.count,
+                taker_side: trade.taker_side,
+                exchange_ts_ms: first_ts(&[&trade.ts]),
+            }))
+        }
+        "market_position" => {
+            let position: MarketPositionMsg = msg(frame)?;
+            let avg_price_cents = position
+                .position_cost
+                .filter(|_| position.position != 0)
+                .map(|cost| (cost.unsigned_abs() / 100 / position.position.unsigned_abs()) as u32);
+            Some(KalshiWsEvent::Position(PositionUpdate {
+                ticker: position.market_ticker,
+                position: position.position,
+                avg_price_cents,
+                exchange_ts_ms: first_ts(&[&position.ts]),
             }))
         }
         "market_lifecycle" => {
-            let msg = v.get("msg")?;
-            let ticker = msg.get("market_ticker")?.as_str()?.to_string();
-            let status = msg.get("status")?.as_str()?.to_string();
-            let result = msg.get("result").and_then(|r| r.as_str()).map(|s| s.to_string());
-
+            let lifecycle: MarketLifecycleMsg = msg(frame)?;
+            let exchange_ts_ms =
+                first_ts(&[&lifecycle.ts, &lifecycle.settled_ts, &lifecycle.determination_ts, &lifecycle.close_ts]);
             Some(KalshiWsEvent::MarketLifecycle(MarketLifecycleEvent {
-                ticker,
-                status,
-                result,
+                ticker: lifecycle.market_ticker,
+                status: lifecycle.status,
+                result: lifecycle.result,
+                exchange_ts_ms,
             }))
         }
         _ => None,
+    };
+    Ok(event)
+}
+
+/// Add `delta` contracts at `price`, keeping levels sorted by price and
+/// dropping any that empty out.
+fn apply_delta(levels: &mut Vec<(u32, u32)>, price: u32, delta: i64) {
+    match levels.binary_search_by_key(&price, |&(p, _)| p) {
+        Ok(i) => {
+            let qty = levels[i].1 as i64 + delta;
+            if qty > 0 {
+                levels[i].1 = qty as u32;
+            } else {
+                levels.remove(i);
+            }
+        }
+        Err(i) if delta > 0 => levels.insert(i, (price, delta as u32)),
+        Err(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(text: &str) -> Option<KalshiWsEvent> {
+        let frame: Frame = serde_json::from_str(text).expect("frame parses");
+        decode_frame(&frame, &mut BookState::default(), &mut Commands::default()).expect("msg decodes")
+    }
+
+    #[test]
+    fn position_cost_is_centi_cents() {
+        let event = decode(r#"{"type":"market_position","sid":14,"msg":{"market_ticker":"FED-23DEC-T3.00","position":-20,"position_cost":70000,"realized_pnl":0,"fees_paid":1400,"volume":20}}"#);
+        let Some(KalshiWsEvent::Position(update)) = event else { panic!("expected a position, got {:?}", event) };
+        assert_eq!((update.position, update.avg_price_cents), (-20, Some(35)));
+    }
+
+    #[test]
+    fn flat_position_has_no_cost_basis() {
+        let event = decode(r#"{"type":"market_position","sid":14,"msg":{"market_ticker":"FED-23DEC-T3.00","position":0,"position_cost":0}}"#);
+        let Some(KalshiWsEvent::Position(update)) = event else { panic!("expected a position, got {:?}", event) };
+        assert_eq!((update.position, update.avg_price_cents), (0, None));
+    }
+
+    #[test]
+    fn schema_mismatch_counts_as_a_parse_failure() {
+        let health = WsHealth::new("Kalshi");
+        let event = parse_kalshi_message(
+            r#"{"type":"market_position","sid":14,"msg":{"market_ticker":"FED-23DEC-T3.00","position":"-20"}}"#,
+            &mut BookState::default(),
+            &mut Commands::default(),
+            &health,
+        );
+        assert!(event.is_none());
+        let failures = health.gauges().into_iter().find(|g| g.name == "ws_parse_failures_total").expect("gauge");
+        assert_eq!(failures.value, 1.0);
     }
 }
@@ -0,0 +1,291 @@
+use crate::core::types::Side;
+use serde::Deserialize;
+
+/// Envelope shared by every Kalshi WS frame. `msg` is decoded into the
+/// channel's struct once `kind` is known, so a schema change surfaces as a
+/// deserialization error naming the field.
+#[derive(Debug, Deserialize)]
+pub struct Frame {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: Option<u64>,
+    pub sid: Option<u64>,
+    pub seq: Option<u64>,
+    #[serde(default)]
+    pub msg: serde_json::Value,
+}
+
+/// A unix timestamp in seconds or millis, or an RFC 3339 string — Kalshi
+/// uses each on different channels.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Timestamp {
+    Number(i64),
+    Text(String),
+}
+
+impl Timestamp {
+    pub fn millis(&self) -> Option<i64> {
+        match self {
+            // Anything below ~2286-11-20 in seconds is a seconds value
+            Timestamp::Number(t) => Some(if *t < 10_000_000_000 { t * 1000 } else { *t }),
+            Timestamp::Text(s) => chrono::DateTime::parse_from_rfc3339(s).ok().map(|t| t.timestamp_millis()),
+        }
+    }
+}
+
+/// The first timestamp present, in unix millis.
+pub fn first_ts(candidates: &[&Option<Timestamp>]) -> Option<i64> {
+    candidates.iter().find_map(|ts| ts.as_ref()?.millis())
+}
+
+/// A contract count, sent as an integer or (on `_fp` fields) a decimal string.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Count {
+    Whole(u64),
+    Text(String),
+}
+
+impl Count {
+    pub fn contracts(&self) -> u32 {
+        match self {
+            Count::Whole(n) => *n as u32,
+            Count::Text(s) => s.parse::<f64>().map(|c| c as u32).unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SubscribedMsg {
+    pub sid: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ErrorMsg {
+    pub code: Option<i64>,
+    pub msg: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OrderbookSnapshotMsg {
+    pub market_ticker: String,
+    /// `[price, quantity]` levels.
+    #[serde(default)]
+    pub yes: Vec<(u32, u32)>,
+    #[serde(default)]
+    pub no: Vec<(u32, u32)>,
+    pub ts: Option<Timestamp>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OrderbookDeltaMsg {
+    pub market_ticker: String,
+    pub price: u32,
+    pub delta: i64,
+    pub side: Side,
+    pub ts: Option<Timestamp>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FillMsg {
+    pub order_id: String,
+    pub market_ticker: String,
+    pub side: Side,
+    pub count: u32,
+    pub yes_price: Option<u32>,
+    pub ts: Option<Timestamp>,
+    pub created_time: Option<Timestamp>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserOrderMsg {
+    pub order_id: String,
+    #[serde(alias = "market_ticker")]
+    pub ticker: String,
+    pub status: String,
+    pub side: Side,
+    #[serde(alias = "fill_count_fp")]
+    pub fill_count: Option<Count>,
+    #[serde(alias = "remaining_count_fp")]
+    pub remaining_count: Option<Count>,
+    pub yes_price: Option<u32>,
+    pub yes_price_dollars: Option<String>,
+}
+
+impl UserOrderMsg {
+    pub fn yes_price_cents(&self) -> Option<u32> {
+        self.yes_price.or_else(|| {
+            let dollars = self.yes_price_dollars.as_deref()?.parse::<f64>().ok()?;
+            Some((dollars * 100.0).round() as u32)
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TickerMsg {
+    pub market_ticker: String,
+    pub yes_bid: Option<u32>,
+    pub yes_ask: Option<u32>,
+    pub price: Option<u32>,
+    pub volume: Option<u64>,
+    pub volume_delta: Option<i64>,
+    pub ts: Option<Timestamp>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TradeMsg {
+    pub market_ticker: String,
+    pub yes_price: u32,
+    pub count: u32,
+    pub taker_side: Side,
+    pub ts: Option<Timestamp>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarketPositionMsg {
+    pub market_ticker: String,
+    pub position: i64,
+    /// Cost basis in centi-cents.
+    pub position_cost: Option<i64>,
+    pub ts: Option<Timestamp>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarketLifecycleMsg {
+    pub market_ticker: String,
+    pub status: String,
+    pub result: Option<String>,
+    pub ts: Option<Timestamp>,
+    pub settled_ts: Option<Timestamp>,
+    pub determination_ts: Option<Timestamp>,
+    pub close_ts: Option<Timestamp>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(text: &str) -> Frame {
+        serde_json::from_str(text).expect("frame parses")
+    }
+
+    fn msg<T: serde::de::DeserializeOwned>(frame: &Frame) -> T {
+        T::deserialize(&frame.msg).expect("msg parses")
+    }
+
+    #[test]
+    fn orderbook_snapshot() {
+        let f = frame(r#"{"type":"orderbook_snapshot","sid":2,"seq":2,"msg":{"market_ticker":"KXBTC15M-25OCT171415-15","yes":[[8,300],[22,333]],"no":[[54,20],[56,146]]}}"#);
+        assert_eq!((f.kind.as_str(), f.sid, f.seq), ("orderbook_snapshot", Some(2), Some(2)));
+        let snapshot: OrderbookSnapshotMsg = msg(&f);
+        assert_eq!(snapshot.market_ticker, "KXBTC15M-25OCT171415-15");
+        assert_eq!(snapshot.yes, vec![(8, 300), (22, 333)]);
+        assert_eq!(snapshot.no, vec![(54, 20), (56, 146)]);
+        assert!(snapshot.ts.is_none());
+    }
+
+    #[test]
+    fn orderbook_delta() {
+        let f = frame(r#"{"type":"orderbook_delta","sid":2,"seq":3,"msg":{"market_ticker":"KXBTC15M-25OCT171415-15","price":96,"delta":-54,"side":"yes","ts":"2025-10-17T18:05:12.345Z"}}"#);
+        assert_eq!(f.seq, Some(3));
+        let delta: OrderbookDeltaMsg = msg(&f);
+        assert_eq!((delta.price, delta.delta, delta.side), (96, -54, Side::Yes));
+        assert_eq!(first_ts(&[&delta.ts]), Some(1_760_724_312_345));
+    }
+
+    #[test]
+    fn fill() {
+        let f = frame(r#"{"type":"fill","sid":13,"msg":{"trade_id":"d91bc706-ee49-470d-82d8-11418bda6fed","order_id":"ee587a1c-8b87-4dcf-b721-9f6f790619fa","market_ticker":"HIGHNY-22DEC23-B53.5","is_taker":true,"side":"yes","yes_price":75,"count":278,"action":"buy","ts":1671899397,"post_position":500}}"#);
+        let fill: FillMsg = msg(&f);
+        assert_eq!(fill.order_id, "ee587a1c-8b87-4dcf-b721-9f6f790619fa");
+        assert_eq!((fill.side, fill.count, fill.yes_price), (Side::Yes, 278, Some(75)));
+        assert_eq!(first_ts(&[&fill.ts, &fill.created_time]), Some(1_671_899_397_000));
+    }
+
+    #[test]
+    fn ticker() {
+        let f = frame(r#"{"type":"ticker","sid":11,"msg":{"market_ticker":"FED-23DEC-T3.00","price":48,"yes_bid":45,"yes_ask":53,"volume":33896,"open_interest":20422,"dollar_volume":16948,"dollar_open_interest":10211,"ts":1669149841}}"#);
+        let ticker: TickerMsg = msg(&f);
+        assert_eq!((ticker.yes_bid, ticker.yes_ask, ticker.price), (Some(45), Some(53), Some(48)));
+        assert_eq!(ticker.volume, Some(33896));
+        assert!(ticker.volume_delta.is_none());
+    }
+
+    #[test]
+    fn error() {
+        let f = frame(r#"{"id":123,"type":"error","msg":{"code":6,"msg":"Already subscribed"}}"#);
+        assert_eq!(f.id, Some(123));
+        let error: ErrorMsg = msg(&f);
+        assert_eq!(error.code, Some(6));
+        assert_eq!(error.msg.as_deref(), Some("Already subscribed"));
+    }
+
+    #[test]
+    fn subscribed() {
+        let f = frame(r#"{"id":1,"type":"subscribed","msg":{"channel":"orderbook_delta","sid":1}}"#);
+        assert_eq!((f.kind.as_str(), f.id), ("subscribed", Some(1)));
+        let reply: SubscribedMsg = msg(&f);
+        assert_eq!(reply.sid, Some(1));
+    }
+
+    #[test]
+    fn unknown_type_keeps_its_msg() {
+        let f = frame(r#"{"type":"multivariate_lookup","sid":7,"msg":{"collection_ticker":"KXOSCARWINNERS-25","event_ticker":"KXOSCARWINNERS-25C0CE5"}}"#);
+        assert_eq!(f.kind, "multivariate_lookup");
+        assert_eq!(f.msg["collection_ticker"], "KXOSCARWINNERS-25");
+    }
+
+    #[test]
+    fn user_order() {
+        let f = frame(r#"{"type":"user_order","sid":13,"msg":{"order_id":"ee587a1c-8b87-4dcf-b721-9f6f790619fa","user_id":"f5bd9df6-6e64-4b94-a7cf-3a1b8fa0a91b","ticker":"FED-23DEC-T3.00","status":"resting","side":"no","is_yes":false,"yes_price_dollars":"0.3500","fill_count_fp":"2.00","remaining_count_fp":"8.00","initial_count_fp":"10.00","client_order_id":"3c1e2a44-1d5c-4f4e-9b47-07c9c6e2d1aa","created_time":"2023-12-01T12:00:00Z"}}"#);
+        let order: UserOrderMsg = msg(&f);
+        assert_eq!((order.ticker.as_str(), order.status.as_str(), order.side.clone()), ("FED-23DEC-T3.00", "resting", Side::No));
+        assert_eq!(order.yes_price_cents(), Some(35));
+        let counts = (order.fill_count.as_ref().map(Count::contracts), order.remaining_count.as_ref().map(Count::contracts));
+        assert_eq!(counts, (Some(2), Some(8)));
+    }
+
+    #[test]
+    fn trade() {
+        let f = frame(r#"{"type":"trade","sid":11,"msg":{"trade_id":"d91bc706-ee49-470d-82d8-11418bda6fed","market_ticker":"HIGHNY-22DEC23-B53.5","yes_price":36,"no_price":64,"count":136,"taker_side":"no","ts":1669149841}}"#);
+        let trade: TradeMsg = msg(&f);
+        assert_eq!((trade.yes_price, trade.count, trade.taker_side), (36, 136, Side::No));
+        assert_eq!(first_ts(&[&trade.ts]), Some(1_669_149_841_000));
+    }
+
+    #[test]
+    fn market_position() {
+        let f = frame(r#"{"type":"market_position","sid":14,"msg":{"user_id":"f5bd9df6-6e64-4b94-a7cf-3a1b8fa0a91b","market_ticker":"FED-23DEC-T3.00","position":-20,"position_cost":70000,"realized_pnl":0,"fees_paid":1400,"volume":20}}"#);
+        let position: MarketPositionMsg = msg(&f);
+        assert_eq!(position.market_ticker, "FED-23DEC-T3.00");
+        // Centi-cents: 70000 is $7.00 for 20 NO contracts
+        assert_eq!((position.position, position.position_cost), (-20, Some(70000)));
+        assert!(position.ts.is_none());
+    }
+
+    #[test]
+    fn market_lifecycle() {
+        let f = frame(r#"{"type":"market_lifecycle","sid":15,"msg":{"market_ticker":"INXD-23SEP14-B4487","status":"settled","result":"yes","open_ts":1694635200,"close_ts":1694721600,"determination_ts":1694721900,"settled_ts":1694722000}}"#);
+        let lifecycle: MarketLifecycleMsg = msg(&f);
+        assert_eq!((lifecycle.status.as_str(), lifecycle.result.as_deref()), ("settled", Some("yes")));
+        assert_eq!(
+            first_ts(&[&lifecycle.ts, &lifecycle.settled_ts, &lifecycle.determination_ts, &lifecycle.close_ts]),
+            Some(1_694_722_000_000)
+        );
+    }
+
+    #[test]
+    fn schema_drift_is_an_error() {
+        // `count` renamed: the decode fails naming the field rather than defaulting
+        let f = frame(r#"{"type":"trade","sid":11,"msg":{"market_ticker":"HIGHNY-22DEC23-B53.5","yes_price":36,"count_fp":"136.00","taker_side":"no"}}"#);
+        let err = TradeMsg::deserialize(&f.msg).expect_err("missing count");
+        assert!(err.to_string().contains("count"), "{}", err);
+    }
+
+    #[test]
+    fn ok_without_msg() {
+        let f = frame(r#"{"id":4,"type":"ok"}"#);
+        assert!(f.msg.is_null());
+    }
+}
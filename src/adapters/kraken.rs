@@ -0,0 +1,179 @@
+use crate::core::realized_vol::RealizedVol;
+use crate::core::types::{Candle, Config};
+use crate::ports::price_feed::PriceFeed;
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 5-second returns over the last 10 minutes.
+const REALIZED_VOL_BUCKET_SECS: u64 = 5;
+const REALIZED_VOL_WINDOW_SECS: u64 = 600;
+
+/// `PriceFeed` backed by Kraken's public REST API. Callers keep using Binance
+/// symbols (`BTCUSDT`); they're mapped to Kraken pairs (`XBTUSD`) here.
+pub struct KrakenClient {
+    client: reqwest::Client,
+    base_url: String,
+    realized_vol: Mutex<RealizedVol>,
+}
+
+/// Kraken wraps every public response as `{"error": [...], "result": {...}}`,
+/// with `result` keyed by its own pair name (`XXBTZUSD` for `XBTUSD`).
+#[derive(Deserialize)]
+struct KrakenResponse<T> {
+    #[serde(default)]
+    error: Vec<String>,
+    result: Option<HashMap<String, T>>,
+}
+
+impl<T> KrakenResponse<T> {
+    fn into_pair(self) -> Option<T> {
+        if !self.error.is_empty() {
+            tracing::warn!("Kraken error: {}", self.error.join(", "));
+            return None;
+        }
+        // OHLC results also carry a "last" cursor next to the pair
+        self.result?.into_iter().find(|(k, _)| k != "last").map(|(_, v)| v)
+    }
+}
+
+#[derive(Deserialize)]
+struct TickerInfo {
+    /// Last trade: `[price, lot volume]`.
+    c: Vec<String>,
+}
+
+/// Binance symbol → Kraken pair: `BTCUSDT` → `XBTUSD`, `ETHUSDT` → `ETHUSD`.
+fn kraken_pair(symbol: &str) -> String {
+    let base = symbol
+        .strip_suffix("USDT")
+        .or_else(|| symbol.strip_suffix("USD"))
+        .unwrap_or(symbol);
+    let base = if base == "BTC" { "XBT" } else { base };
+    format!("{}USD", base)
+}
+
+/// Binance interval → Kraken OHLC interval in minutes.
+fn kraken_interval_mins(interval: &str) -> Option<u32> {
+    Some(match interval {
+        "1m" => 1,
+        "5m" => 5,
+        "15m" => 15,
+        "30m" => 30,
+        "1h" => 60,
+        "4h" => 240,
+        "1d" => 1440,
+        "1w" => 10080,
+        _ => return None,
+    })
+}
+
+impl KrakenClient {
+    pub fn new(_config: &Config) -> Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(5))
+                .build()?,
+            base_url: "https://api.kraken.com".into(),
+            realized_vol: Mutex::new(RealizedVol::new(
+                REALIZED_VOL_BUCKET_SECS,
+                REALIZED_VOL_WINDOW_SECS,
+            )),
+        })
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, what: &str, url: &str) -> Option<T> {
+        let resp = match self.client.get(url).send().await {
+            Ok(r) => r,
+            Err(e) => {
+                tracing::warn!("Kraken {} request failed: {}", what, e);
+                return None;
+            }
+        };
+
+        if !resp.status().is_success() {
+            tracing::warn!("Kraken {} -> {}", what, resp.status());
+            return None;
+        }
+
+        match resp.json::<KrakenResponse<T>>().await {
+            Ok(v) => v.into_pair(),
+            Err(e) => {
+                tracing::warn!("Kraken {} parse error: {}", what, e);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for KrakenClient {
+    async fn candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: u32,
+    ) -> Result<Option<Vec<Candle>>> {
+        let Some(mins) = kraken_interval_mins(interval) else {
+            tracing::warn!("Kraken has no {} candles", interval);
+            return Ok(None);
+        };
+        // OHLC has no limit parameter; ask for just enough history instead
+        let since = chrono::Utc::now().timestamp() - (limit as i64 + 1) * mins as i64 * 60;
+        let url = format!(
+            "{}/0/public/OHLC?pair={}&interval={}&since={}",
+            self.base_url, kraken_pair(symbol), mins, since
+        );
+
+        let Some(serde_json::Value::Array(rows)) = self.get::<serde_json::Value>("OHLC", &url).await else {
+            return Ok(None);
+        };
+
+        let interval_ms = mins as i64 * 60_000;
+        let num = |v: &serde_json::Value| v.as_str()?.parse::<f64>().ok();
+        let mut candles: Vec<Candle> = rows
+            .into_iter()
+            .filter_map(|row| {
+                // [time, open, high, low, close, vwap, volume, count]
+                let row = row.as_array()?;
+                if row.len() < 7 {
+                    return None;
+                }
+                let open_time = row[0].as_i64()? * 1000;
+                Some(Candle {
+                    open_time,
+                    open: num(&row[1])?,
+                    high: num(&row[2])?,
+                    low: num(&row[3])?,
+                    close: num(&row[4])?,
+                    volume: num(&row[6])?,
+                    close_time: open_time + interval_ms - 1,
+                })
+            })
+            .collect();
+        let excess = candles.len().saturating_sub(limit as usize);
+        candles.drain(..excess);
+
+        Ok(Some(candles))
+    }
+
+    async fn spot_price(&self, symbol: &str) -> Result<Option<f64>> {
+        let url = format!("{}/0/public/Ticker?pair={}", self.base_url, kraken_pair(symbol));
+        let Some(ticker) = self.get::<TickerInfo>("ticker", &url).await else {
+            return Ok(None);
+        };
+        Ok(ticker.c.first().and_then(|p| p.parse().ok()))
+    }
+
+    fn record_price(&self, symbol: &str, price: f64, ts_ms: i64) {
+        if let Ok(mut rv) = self.realized_vol.lock() {
+            rv.record(symbol, price, ts_ms);
+        }
+    }
+
+    fn realized_vol(&self, symbol: &str) -> Option<f64> {
+        self.realized_vol.lock().ok()?.realized_vol_pct(symbol)
+    }
+}
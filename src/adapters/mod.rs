@@ -1,4 +1,16 @@
 pub mod binance;
 pub mod binance_ws;
+pub mod candle_cache;
+pub mod deribit;
 pub mod kalshi;
+pub mod kraken;
 pub mod openrouter;
+pub mod otlp;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod pushgateway;
+pub mod s3;
+pub mod telegram;
+pub mod webdav;
+pub mod ws_health;
+pub mod ws_tap;
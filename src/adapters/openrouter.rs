@@ -1,3 +1,4 @@
+use crate::core::indicators;
 use crate::core::types::*;
 use crate::ports::brain::Brain;
 use anyhow::Result;
@@ -6,13 +7,20 @@ use async_trait::async_trait;
 pub struct OpenRouterClient {
     client: reqwest::Client,
     api_key: String,
+    model: String,
 }
 
 impl OpenRouterClient {
     pub fn new(config: &Config) -> Result<Self> {
+        Self::with_model(config, &config.openrouter_model)
+    }
+
+    /// Client for a model other than `openrouter_model` (e.g. the cheap tier).
+    pub fn with_model(config: &Config, model: &str) -> Result<Self> {
         Ok(Self {
             client: reqwest::Client::new(),
             api_key: config.openrouter_api_key.clone(),
+            model: model.to_string(),
         })
     }
 }
@@ -28,19 +36,43 @@ impl Brain for OpenRouterClient {
             ),
             None => format!("\n\n---\n## {} PRICE\nUnavailable this cycle.", ctx.crypto_label),
         };
+        let price_section = match (ctx.implied_vol, &ctx.crypto_price) {
+            (Some(iv), Some(snap)) => {
+                // 1-sigma move to expiry at this vol
+                let minutes = ctx.market.minutes_to_expiry.max(0.0);
+                let move_pct = iv * (minutes / (365.0 * 24.0 * 60.0)).sqrt();
+                format!(
+                    "{}\nImplied vol (DVOL): {:.1}% annualized → 1σ move to expiry ±{:.3}% (±${:.2})",
+                    price_section, iv, move_pct, snap.spot_price * move_pct / 100.0
+                )
+            }
+            _ => price_section,
+        };
 
         let signal_section = match &ctx.signal_summary {
             Some(summary) => format!("\n\n---\n## SIGNAL SUMMARY\n{}", format_signal_summary(summary)),
             None => "\n\n---\n## SIGNAL SUMMARY\nUnavailable this cycle.".to_string(),
         };
 
+        let ladder_section = if ctx.strikes.len() > 1 {
+            format!("\n\n---\n## STRIKE LADDER\n{}", format_strikes(&ctx.strikes, &ctx.market.ticker))
+        } else {
+            String::new()
+        };
+
+        let history_section = format_contract_history(&ctx.contract_candles)
+            .map(|h| format!("\n\n---\n## CONTRACT PRICE HISTORY\n{}", h))
+            .unwrap_or_default();
+
         let prompt = format!(
-            "{prompt}\n\n---\n## STATS\n{stats}\n\n---\n## LAST {n} TRADES\n{ledger}\n\n---\n## MARKET\n{market}\n\n---\n## ORDERBOOK\nYes bids: {yes_ob}\nNo bids: {no_ob}{price}{signal}",
+            "{prompt}\n\n---\n## STATS\n{stats}\n\n---\n## LAST {n} TRADES\n{ledger}\n\n---\n## MARKET\n{market}{ladder}{history}\n\n---\n## ORDERBOOK\nYes bids: {yes_ob}\nNo bids: {no_ob}{price}{signal}",
             prompt = ctx.prompt_md,
             stats = format_stats(&ctx.stats),
             n = ctx.last_n_trades.len(),
             ledger = format_ledger(&ctx.last_n_trades),
             market = format_market(&ctx.market),
+            ladder = ladder_section,
+            history = history_section,
             yes_ob = format_ob_side(&ctx.orderbook.yes),
             no_ob = format_ob_side(&ctx.orderbook.no),
             price = price_section,
@@ -48,7 +80,7 @@ impl Brain for OpenRouterClient {
         );
 
         let body = serde_json::json!({
-            "model": "anthropic/claude-opus-4-6",
+            "model": self.model,
             "max_tokens": 1200,
             "temperature": 0.2,
             "messages": [{"role": "user", "content": prompt}]
@@ -70,7 +102,10 @@ impl Brain for OpenRouterClient {
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("No content in OpenRouter response"))?;
 
-        parse_decision(content)
+        let mut decision = parse_decision(content)?;
+        decision.prompt = prompt;
+        decision.raw_response = content.to_string();
+        Ok(decision)
     }
 }
 
@@ -82,6 +117,40 @@ fn format_stats(s: &Stats) -> String {
     )
 }
 
+/// Implied-probability trend from the contract's own candles, or None
+/// when there's no priced history yet.
+fn format_contract_history(candles: &[ContractCandle]) -> Option<String> {
+    let now = candles.iter().rev().find_map(|c| c.implied_probability())?;
+    let change = |m| {
+        indicators::implied_probability_change(candles, m)
+            .map(|d| format!("{:+.1}pt", d))
+            .unwrap_or_else(|| "n/a".into())
+    };
+    // History reaches further back than 15m; count only the last 15m's volume
+    let cutoff = candles.last().map_or(0, |c| c.end_ts - 15 * 60);
+    let volume: u64 = candles.iter().filter(|c| c.end_ts > cutoff).map(|c| c.volume).sum();
+    Some(format!(
+        "Implied P(YES): {:.1}% | 5m change: {} | 15m change: {} | 15m volume: {}",
+        now, change(5), change(15), volume
+    ))
+}
+
+fn format_strikes(strikes: &[MarketState], current: &str) -> String {
+    strikes
+        .iter()
+        .map(|m| {
+            let strike = m.strike.map(|k| format!("{:.2}", k)).unwrap_or_else(|| "?".into());
+            let fmt = |p: Option<u32>| p.map(|c| format!("{}¢", c)).unwrap_or_else(|| "-".into());
+            format!(
+                "{}{} | strike {} | yes {}/{} | vol {}",
+                if m.ticker == current { "* " } else { "" },
+                m.ticker, strike, fmt(m.yes_bid), fmt(m.yes_ask), m.volume
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn format_ledger(trades: &[LedgerRow]) -> String {
     if trades.is_empty() {
         return "No trades yet.".into();
@@ -127,25 +196,55 @@ fn format_crypto_price(snap: &PriceSnapshot) -> String {
         MomentumDirection::Flat => "FLAT",
     };
 
+    // Indicators are over the series' candle windows; the labels follow them
+    let w = &snap.candle_windows;
     let mut s = format!(
-        "Spot: ${:.2} | 5m change: {:+.3}% | 15m change: {:+.3}% | 1h change: {:+.3}% | Momentum: {}\n\
-         SMA(15x1m): ${:.2} | Price vs SMA: {} | 1m volatility: {:.4}%\n\
+        "Spot: ${:.2} | {} change: {:+.3}% | {} change: {:+.3}% | {} change: {:+.3}% | Momentum: {}\n\
+         SMA({}x{}): ${:.2} | Price vs SMA: {} | {} volatility: {:.4}%\n\
          RSI(9): {:.1} | EMA(9): ${:.2} | Price vs EMA: {}",
         ind.spot_price,
+        w.long_interval,
         ind.pct_change_5m,
+        w.short_span(),
         ind.pct_change_15m,
+        w.long_span(),
         ind.pct_change_1h,
         momentum_str,
+        w.short_limit,
+        w.short_interval,
         ind.sma_15m,
         ind.price_vs_sma,
+        w.short_interval,
         ind.volatility_1m,
         ind.rsi_9,
         ind.ema_9,
         ind.price_vs_ema,
     );
 
+    if let Some(rv) = ind.realized_vol_5s {
+        s.push_str(&format!("\nRealized vol (5s returns, 10m window): {:.4}%", rv));
+    }
+    if let Some(depth) = ind.depth_imbalance {
+        s.push_str(&format!("\nSpot book imbalance (top 10 levels, >1 bid-heavy): {:.2}", depth));
+    }
+    if let Some(flow) = &ind.order_flow {
+        s.push_str(&format!(
+            "\nSpot taker flow (last {}s): buy {:.4} / sell {:.4}, delta {:+.4} ({:+.2} of volume)",
+            flow.window_secs, flow.buy_volume, flow.sell_volume, flow.delta(), flow.imbalance()
+        ));
+    }
+
+    if let Some(perp) = &snap.perp {
+        let basis = snap.basis_pct.map(|b| format!("{:+.4}%", b)).unwrap_or_else(|| "n/a".into());
+        let mins_to_funding = (perp.next_funding_ms - chrono::Utc::now().timestamp_millis()).max(0) / 60_000;
+        s.push_str(&format!(
+            "\nPerp: mark ${:.2} | basis vs spot {} | funding {:+.4}% (next in {}m, positive = longs pay)",
+            perp.mark_price, basis, perp.funding_rate_pct, mins_to_funding
+        ));
+    }
+
     if !ind.last_3_candles.is_empty() {
-        s.push_str("\nLast 3 candles (1m): ");
+        s.push_str(&format!("\nLast 3 candles ({}): ", w.short_interval));
         let candle_strs: Vec<String> = ind
             .last_3_candles
             .iter()
@@ -211,6 +310,9 @@ fn parse_decision(raw: &str) -> Result<TradeDecision> {
             reasoning: "Failed to parse AI response".into(),
             estimated_probability: None,
             estimated_edge: None,
+            prompt: String::new(),
+            raw_response: String::new(),
+            route: None,
         });
     };
 
@@ -0,0 +1,78 @@
+use crate::core::types::MetricsSample;
+use crate::ports::metrics::MetricsSink;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// OpenTelemetry collector over OTLP/HTTP with JSON encoding. Instance
+/// labels become resource attributes; each gauge is one data point.
+pub struct OtlpSink {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+impl OtlpSink {
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()?,
+            endpoint: format!("{}/v1/metrics", base_url.trim_end_matches('/')),
+        })
+    }
+}
+
+#[async_trait]
+impl MetricsSink for OtlpSink {
+    async fn push(&self, sample: &MetricsSample) -> Result<()> {
+        let now_nanos = chrono::Utc::now()
+            .timestamp_nanos_opt()
+            .unwrap_or_default()
+            .to_string();
+        let attributes: Vec<serde_json::Value> = std::iter::once(("service.name", "kalshi-bot"))
+            .chain(sample.labels.iter().map(|(k, v)| (*k, v.as_str())))
+            .map(|(k, v)| serde_json::json!({"key": k, "value": {"stringValue": v}}))
+            .collect();
+        let mut metrics: Vec<serde_json::Value> = sample
+            .gauges
+            .iter()
+            .map(|(name, value)| {
+                serde_json::json!({
+                    "name": name,
+                    "gauge": {"dataPoints": [{"asDouble": value, "timeUnixNano": now_nanos}]},
+                })
+            })
+            .collect();
+        // Labelled gauges: one metric per name, one data point per label set
+        let mut points: Vec<(&str, Vec<serde_json::Value>)> = Vec::new();
+        for g in &sample.labelled {
+            let point = serde_json::json!({
+                "asDouble": g.value,
+                "timeUnixNano": now_nanos,
+                "attributes": g.labels.iter()
+                    .map(|(k, v)| serde_json::json!({"key": k, "value": {"stringValue": v}}))
+                    .collect::<Vec<_>>(),
+            });
+            match points.iter_mut().find(|(name, _)| *name == g.name) {
+                Some((_, p)) => p.push(point),
+                None => points.push((g.name, vec![point])),
+            }
+        }
+        metrics.extend(points.into_iter().map(|(name, data_points)| {
+            serde_json::json!({"name": name, "gauge": {"dataPoints": data_points}})
+        }));
+        let body = serde_json::json!({
+            "resourceMetrics": [{
+                "resource": {"attributes": attributes},
+                "scopeMetrics": [{"scope": {"name": "kalshi-bot"}, "metrics": metrics}],
+            }]
+        });
+
+        let resp = self.client.post(&self.endpoint).json(&body).send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err_body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("OTLP POST {} -> {} : {}", self.endpoint, status, err_body);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,467 @@
+use crate::core::stats::{CounterSnapshot, StatCounters};
+use crate::core::types::*;
+use crate::ports::storage::Storage;
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Client, NoTls, Row};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS ledger (
+    id               BIGSERIAL PRIMARY KEY,
+    timestamp        TEXT   NOT NULL,
+    ticker           TEXT   NOT NULL,
+    side             TEXT   NOT NULL,
+    shares           BIGINT NOT NULL,
+    price            BIGINT NOT NULL,
+    result           TEXT   NOT NULL,
+    pnl_cents        BIGINT NOT NULL,
+    cumulative_cents BIGINT NOT NULL,
+    order_id         TEXT   NOT NULL,
+    signal           TEXT   NOT NULL DEFAULT ''
+);
+ALTER TABLE ledger ADD COLUMN IF NOT EXISTS signal TEXT NOT NULL DEFAULT '';
+CREATE INDEX IF NOT EXISTS ledger_pending ON ledger (ticker) WHERE result = 'pending';
+CREATE TABLE IF NOT EXISTS stats (
+    name       TEXT PRIMARY KEY,
+    body       JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS stats_history (
+    id          BIGSERIAL PRIMARY KEY,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ticker      TEXT  NOT NULL,
+    body        JSONB NOT NULL
+);
+CREATE TABLE IF NOT EXISTS book_samples (
+    id           BIGSERIAL PRIMARY KEY,
+    timestamp    TEXT   NOT NULL,
+    ticker       TEXT   NOT NULL,
+    spread_cents BIGINT,
+    bid_depth    BIGINT NOT NULL,
+    ask_depth    BIGINT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS equity_samples (
+    id                 BIGSERIAL PRIMARY KEY,
+    timestamp          TEXT   NOT NULL,
+    balance_cents      BIGINT NOT NULL,
+    open_positions     BIGINT NOT NULL,
+    cost_basis_cents   BIGINT NOT NULL,
+    marked_value_cents BIGINT NOT NULL,
+    equity_cents       BIGINT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS decisions (
+    order_id TEXT PRIMARY KEY,
+    record   JSONB NOT NULL
+);
+";
+
+const LEDGER_COLUMNS: &str =
+    "timestamp, ticker, side, shares, price, result, pnl_cents, cumulative_cents, order_id, signal";
+
+/// Shared ledger/stats database so several instances (different series or
+/// accounts) can trade against one history. Pending rows are updated under
+/// `SELECT … FOR UPDATE` so concurrent settlements never race.
+pub struct PostgresStorage {
+    client: Mutex<Client>,
+    counters: StatCounters,
+}
+
+impl PostgresStorage {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                tracing::error!("Postgres connection error: {}", e);
+            }
+        });
+        client.batch_execute(SCHEMA).await?;
+
+        let rows = client
+            .query(&format!("SELECT {} FROM ledger ORDER BY id", LEDGER_COLUMNS), &[])
+            .await?;
+        let ledger: Vec<LedgerRow> = rows.iter().map(ledger_row).collect();
+        tracing::info!("Postgres storage connected ({} ledger rows)", ledger.len());
+
+        Ok(Self {
+            client: Mutex::new(client),
+            counters: StatCounters::from_ledger(&ledger),
+        })
+    }
+
+    /// One-shot import of an existing ledger, in order and in a single
+    /// transaction. Rows are copied verbatim — order ids and cumulative P&L
+    /// are not recomputed. Refuses to run against a non-empty ledger table.
+    pub async fn import_ledger(&self, rows: &[LedgerRow]) -> Result<usize> {
+        let mut client = self.client.lock().await;
+        let tx = client.transaction().await?;
+        let existing: i64 = tx.query_one("SELECT count(*) FROM ledger", &[]).await?.get(0);
+        if existing > 0 {
+            anyhow::bail!("Postgres ledger already has {} rows — refusing to import", existing);
+        }
+
+        let insert = tx
+            .prepare(&format!(
+                "INSERT INTO ledger ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+                LEDGER_COLUMNS
+            ))
+            .await?;
+        for row in rows {
+            tx.execute(
+                &insert,
+                &[
+                    &row.timestamp,
+                    &row.ticker,
+                    &row.side,
+                    &(row.shares as i64),
+                    &(row.price as i64),
+                    &row.result,
+                    &row.pnl_cents,
+                    &row.cumulative_cents,
+                    &row.order_id,
+                    &row.signal,
+                ],
+            )
+            .await?;
+        }
+        tx.commit().await?;
+        drop(client);
+
+        self.counters.reseed(rows);
+        Ok(rows.len())
+    }
+
+    /// Lock the latest pending row matching `filter`, rewrite it, and commit.
+    async fn update_pending(
+        &self,
+        filter: &str,
+        key: &str,
+        update: impl FnOnce(&LedgerRow) -> (String, i64) + Send,
+    ) -> Result<Option<LedgerRow>> {
+        let mut client = self.client.lock().await;
+        let tx = client.transaction().await?;
+        let row = tx
+            .query_opt(
+                &format!(
+                    "SELECT id, {} FROM ledger WHERE result = 'pending' AND {} = $1 \
+                     ORDER BY id DESC LIMIT 1 FOR UPDATE",
+                    LEDGER_COLUMNS, filter
+                ),
+                &[&key],
+            )
+            .await?;
+        let Some(row) = row else {
+            tx.commit().await?;
+            return Ok(None);
+        };
+
+        let id: i64 = row.get("id");
+        let mut settled = ledger_row(&row);
+        let (result, pnl) = update(&settled);
+        settled.cumulative_cents += pnl;
+        settled.result = result;
+        settled.pnl_cents = pnl;
+
+        tx.execute(
+            "UPDATE ledger SET result = $1, pnl_cents = $2, cumulative_cents = $3 WHERE id = $4",
+            &[&settled.result, &settled.pnl_cents, &settled.cumulative_cents, &id],
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(Some(settled))
+    }
+}
+
+fn result_sql(result: ResultFilter) -> &'static str {
+    match result {
+        ResultFilter::Pending => "result = 'pending'",
+        ResultFilter::Win => "result = 'win'",
+        ResultFilter::Loss => "result = 'loss'",
+        ResultFilter::Settled => "result IN ('win', 'loss')",
+        ResultFilter::Exit => "result LIKE 'exit\\_%'",
+        ResultFilter::Cancelled => "result = 'cancelled'",
+    }
+}
+
+fn ledger_row(row: &Row) -> LedgerRow {
+    LedgerRow {
+        timestamp: row.get("timestamp"),
+        ticker: row.get("ticker"),
+        side: row.get("side"),
+        shares: row.get::<_, i64>("shares") as u32,
+        price: row.get::<_, i64>("price") as u32,
+        result: row.get("result"),
+        pnl_cents: row.get("pnl_cents"),
+        cumulative_cents: row.get("cumulative_cents"),
+        order_id: row.get("order_id"),
+        signal: row.get("signal"),
+    }
+}
+
+#[async_trait]
+impl Storage for PostgresStorage {
+    async fn read_prompt(&self) -> Result<String> {
+        // The prompt is per-deployment config, not shared state.
+        crate::storage::read_prompt()
+    }
+
+    async fn read_ledger(&self) -> Result<Vec<LedgerRow>> {
+        let rows = self
+            .client
+            .lock()
+            .await
+            .query(&format!("SELECT {} FROM ledger ORDER BY id", LEDGER_COLUMNS), &[])
+            .await?;
+        let ledger: Vec<LedgerRow> = rows.iter().map(ledger_row).collect();
+        // Other instances settle trades too — resync the risk counters.
+        self.counters.reseed(&ledger);
+        Ok(ledger)
+    }
+
+    async fn query_ledger(&self, filter: &QueryFilter) -> Result<Vec<LedgerRow>> {
+        let mut clauses: Vec<String> = Vec::new();
+        let mut params: Vec<Box<dyn ToSql + Sync + Send>> = Vec::new();
+        let mut bind = |clause: &str, value: Box<dyn ToSql + Sync + Send>| {
+            params.push(value);
+            clauses.push(clause.replace('?', &format!("${}", params.len())));
+        };
+
+        if let Some(ticker) = &filter.ticker {
+            bind("ticker = ?", Box::new(ticker.clone()));
+        }
+        if let Some(series) = &filter.series {
+            bind("split_part(ticker, '-', 1) = ?", Box::new(series.clone()));
+        }
+        if let Some(side) = &filter.side {
+            bind("side = ?", Box::new(side.to_string()));
+        }
+        if let Some(from) = filter.from {
+            bind("timestamp::timestamptz >= ?", Box::new(from));
+        }
+        if let Some(to) = filter.to {
+            bind("timestamp::timestamptz < ?", Box::new(to));
+        }
+        if let Some(result) = filter.result {
+            clauses.push(result_sql(result).to_string());
+        }
+
+        let where_sql = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", clauses.join(" AND "))
+        };
+        let params: Vec<&(dyn ToSql + Sync)> =
+            params.iter().map(|p| p.as_ref() as &(dyn ToSql + Sync)).collect();
+        let rows = self
+            .client
+            .lock()
+            .await
+            .query(
+                &format!("SELECT {} FROM ledger{} ORDER BY id", LEDGER_COLUMNS, where_sql),
+                &params,
+            )
+            .await?;
+        Ok(rows.iter().map(ledger_row).collect())
+    }
+
+    async fn append_ledger(&self, row: &LedgerRow) -> Result<()> {
+        self.client
+            .lock()
+            .await
+            .execute(
+                &format!(
+                    "INSERT INTO ledger ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+                    LEDGER_COLUMNS
+                ),
+                &[
+                    &row.timestamp,
+                    &row.ticker,
+                    &row.side,
+                    &(row.shares as i64),
+                    &(row.price as i64),
+                    &row.result,
+                    &row.pnl_cents,
+                    &row.cumulative_cents,
+                    &row.order_id,
+                    &row.signal,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn settle_trade(&self, settlement: &Settlement) -> Result<()> {
+        let result = settlement.result.clone();
+        let revenue = settlement.pnl_cents - settlement.fee_cents;
+        let settled = self
+            .update_pending("ticker", &settlement.ticker, move |row| {
+                (result, revenue - row.price as i64 * row.shares as i64)
+            })
+            .await?;
+        if let Some(row) = settled {
+            self.counters.record_result(&row.result, row.pnl_cents);
+        }
+        Ok(())
+    }
+
+    async fn cancel_trade(&self, order_id: &str) -> Result<()> {
+        self.update_pending("order_id", order_id, |_| ("cancelled".into(), 0))
+            .await?;
+        Ok(())
+    }
+
+    async fn record_early_exit(&self, exit: &ExitEvent) -> Result<()> {
+        let result = format!("exit_{}", exit.reason);
+        let pnl = exit.pnl_cents;
+        self.update_pending("ticker", &exit.ticker, move |_| (result, pnl))
+            .await?;
+        Ok(())
+    }
+
+    async fn amend_ledger(
+        &self,
+        order_id: &str,
+        amendment: &LedgerAmendment,
+    ) -> Result<(LedgerRow, LedgerRow)> {
+        let mut client = self.client.lock().await;
+        let tx = client.transaction().await?;
+        let rows = tx
+            .query(
+                &format!("SELECT id, {} FROM ledger WHERE order_id = $1 FOR UPDATE", LEDGER_COLUMNS),
+                &[&order_id],
+            )
+            .await?;
+        let row = match rows.as_slice() {
+            [row] => row,
+            [] => anyhow::bail!("No ledger row with order id {}", order_id),
+            _ => anyhow::bail!("{} ledger rows share order id {} — fix by hand", rows.len(), order_id),
+        };
+
+        let id: i64 = row.get("id");
+        let before = ledger_row(row);
+        let after = amendment.apply(&before);
+        tx.execute(
+            "UPDATE ledger SET side = $1, shares = $2, price = $3, result = $4, \
+             pnl_cents = $5, cumulative_cents = $6, order_id = $7 WHERE id = $8",
+            &[
+                &after.side,
+                &(after.shares as i64),
+                &(after.price as i64),
+                &after.result,
+                &after.pnl_cents,
+                &after.cumulative_cents,
+                &after.order_id,
+                &id,
+            ],
+        )
+        .await?;
+        tx.execute(
+            "UPDATE ledger SET cumulative_cents = cumulative_cents + $1 WHERE id > $2",
+            &[&(after.pnl_cents - before.pnl_cents), &id],
+        )
+        .await?;
+        tx.commit().await?;
+        drop(client);
+
+        // Resyncs the risk counters to the amended result and P&L
+        self.read_ledger().await?;
+        Ok((before, after))
+    }
+
+    async fn write_stats(&self, stats: &Stats) -> Result<()> {
+        self.client
+            .lock()
+            .await
+            .execute(
+                "INSERT INTO stats (name, body) VALUES ('global', $1) \
+                 ON CONFLICT (name) DO UPDATE SET body = EXCLUDED.body, updated_at = now()",
+                &[&serde_json::to_value(stats)?],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn append_stats_history(&self, ticker: &str, stats: &Stats) -> Result<()> {
+        self.client
+            .lock()
+            .await
+            .execute(
+                "INSERT INTO stats_history (ticker, body) VALUES ($1, $2)",
+                &[&ticker, &serde_json::to_value(stats)?],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn append_book_sample(&self, sample: &BookSample) -> Result<()> {
+        self.client
+            .lock()
+            .await
+            .execute(
+                "INSERT INTO book_samples (timestamp, ticker, spread_cents, bid_depth, ask_depth) \
+                 VALUES ($1, $2, $3, $4, $5)",
+                &[
+                    &sample.timestamp,
+                    &sample.ticker,
+                    &sample.spread_cents.map(|s| s as i64),
+                    &(sample.bid_depth as i64),
+                    &(sample.ask_depth as i64),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn append_equity_sample(&self, sample: &EquitySample) -> Result<()> {
+        self.client
+            .lock()
+            .await
+            .execute(
+                "INSERT INTO equity_samples (timestamp, balance_cents, open_positions, \
+                 cost_basis_cents, marked_value_cents, equity_cents) VALUES ($1, $2, $3, $4, $5, $6)",
+                &[
+                    &sample.timestamp,
+                    &(sample.balance_cents as i64),
+                    &(sample.open_positions as i64),
+                    &sample.cost_basis_cents,
+                    &sample.marked_value_cents,
+                    &sample.equity_cents(),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn write_decision(&self, record: &DecisionRecord<'_>) -> Result<()> {
+        let json = serde_json::to_value(record)?;
+        self.client
+            .lock()
+            .await
+            .execute(
+                "INSERT INTO decisions (order_id, record) VALUES ($1, $2) \
+                 ON CONFLICT (order_id) DO UPDATE SET record = EXCLUDED.record",
+                &[&record.order_id, &json],
+            )
+            .await?;
+        Ok(())
+    }
+
+    // The journal recovers this process's own in-flight orders, so it stays
+    // local rather than shared.
+    async fn journal(&self, event: &JournalEvent) -> Result<()> {
+        crate::journal::append(event)
+    }
+
+    async fn unresolved_orders(&self) -> Result<Vec<UnresolvedOrder>> {
+        crate::journal::unresolved()
+    }
+
+    async fn compact_journal(&self) -> Result<()> {
+        crate::journal::compact()
+    }
+
+    fn counters(&self) -> CounterSnapshot {
+        self.counters.snapshot()
+    }
+}
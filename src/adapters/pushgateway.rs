@@ -0,0 +1,68 @@
+use crate::core::types::{LabelledGauge, MetricsSample};
+use crate::ports::metrics::MetricsSink;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::fmt::Write;
+
+/// Prometheus Pushgateway: gauges in the text exposition format, grouped by
+/// job plus the instance labels so each instance replaces only its own group.
+pub struct PushgatewaySink {
+    client: reqwest::Client,
+    base_url: reqwest::Url,
+}
+
+impl PushgatewaySink {
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()?,
+            base_url: reqwest::Url::parse(base_url)?,
+        })
+    }
+}
+
+#[async_trait]
+impl MetricsSink for PushgatewaySink {
+    async fn push(&self, sample: &MetricsSample) -> Result<()> {
+        let mut url = self.base_url.clone();
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .map_err(|_| anyhow::anyhow!("Pushgateway URL cannot be a base: {}", self.base_url))?;
+            segments.pop_if_empty().extend(["metrics", "job", "kalshi_bot"]);
+            for (key, value) in &sample.labels {
+                segments.push(key).push(value);
+            }
+        }
+
+        let mut body = String::new();
+        for (name, value) in &sample.gauges {
+            writeln!(body, "# TYPE {} gauge\n{} {}", name, name, value)?;
+        }
+        // Each name's series must be contiguous, under a single TYPE line
+        let mut labelled: Vec<&LabelledGauge> = sample.labelled.iter().collect();
+        labelled.sort_by_key(|g| g.name);
+        let mut current = "";
+        for g in labelled {
+            if g.name != current {
+                writeln!(body, "# TYPE {} gauge", g.name)?;
+                current = g.name;
+            }
+            let labels: Vec<String> = g
+                .labels
+                .iter()
+                .map(|(k, v)| format!("{}=\"{}\"", k, v.replace('\\', "\\\\").replace('"', "\\\"")))
+                .collect();
+            writeln!(body, "{}{{{}}} {}", g.name, labels.join(","), g.value)?;
+        }
+
+        let resp = self.client.put(url).body(body).send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err_body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Pushgateway PUT -> {} : {}", status, err_body);
+        }
+        Ok(())
+    }
+}
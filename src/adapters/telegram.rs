@@ -0,0 +1,54 @@
+use crate::core::types::Config;
+use crate::ports::notifier::Notifier;
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub struct TelegramNotifier {
+    client: reqwest::Client,
+    bot_token: String,
+    chat_id: String,
+}
+
+impl TelegramNotifier {
+    pub fn new(config: &Config) -> Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(5))
+                .build()?,
+            bot_token: config.telegram_bot_token.clone(),
+            chat_id: config.telegram_chat_id.clone(),
+        })
+    }
+
+    /// Send to Telegram when a bot token and chat are configured.
+    async fn send(&self, message: &str) -> Result<()> {
+        if self.bot_token.is_empty() || self.chat_id.is_empty() {
+            return Ok(());
+        }
+
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        let body = serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": format!("kalshi-bot: {}", message),
+        });
+        let resp = self.client.post(&url).json(&body).send().await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Telegram sendMessage -> {}", resp.status());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    /// Always logs; also sends to Telegram when configured.
+    async fn alert(&self, message: &str) -> Result<()> {
+        tracing::warn!("ALERT: {}", message);
+        self.send(message).await
+    }
+
+    async fn notify(&self, message: &str) -> Result<()> {
+        tracing::info!("NOTIFY: {}", message);
+        self.send(message).await
+    }
+}
@@ -0,0 +1,67 @@
+use crate::ports::backup::BackupStore;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// WebDAV backup target (Nextcloud, Hetzner Storage Box, …): plain HTTP PUT
+/// with basic auth under a base collection URL.
+pub struct WebDavStore {
+    client: reqwest::Client,
+    base_url: String,
+    user: String,
+    password: String,
+}
+
+impl WebDavStore {
+    pub fn new(base_url: &str, user: &str, password: &str) -> Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(60))
+                .build()?,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            user: user.to_string(),
+            password: password.to_string(),
+        })
+    }
+
+    /// Create each parent collection of `key`; servers reject PUTs into
+    /// missing collections. 405 means it already exists.
+    async fn ensure_collections(&self, key: &str) -> Result<()> {
+        let mut path = String::new();
+        let Some((dirs, _)) = key.rsplit_once('/') else {
+            return Ok(());
+        };
+        for dir in dirs.split('/') {
+            path.push('/');
+            path.push_str(dir);
+            let resp = self
+                .client
+                .request(reqwest::Method::from_bytes(b"MKCOL")?, format!("{}{}", self.base_url, path))
+                .basic_auth(&self.user, Some(&self.password))
+                .send()
+                .await?;
+            let status = resp.status();
+            if !status.is_success() && status != reqwest::StatusCode::METHOD_NOT_ALLOWED {
+                anyhow::bail!("WebDAV MKCOL {} -> {}", path, status);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BackupStore for WebDavStore {
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        self.ensure_collections(key).await?;
+        let resp = self
+            .client
+            .put(format!("{}/{}", self.base_url, key))
+            .basic_auth(&self.user, Some(&self.password))
+            .body(body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("WebDAV PUT {} -> {}", key, resp.status());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::core::types::LabelledGauge;
+use std::sync::Mutex;
+use std::time::Instant;
+use tokio::sync::Notify;
+
+/// Connection health for one WS feed, updated by its loop and read at each
+/// metrics push, so a stale or flapping feed shows up on the dashboard.
+pub struct WsHealth {
+    feed: &'static str,
+    state: Mutex<HealthState>,
+    /// Wakes the feed's read loop to drop and redo its connection.
+    reconnect: Notify,
+}
+
+struct HealthState {
+    connected: bool,
+    connects: u64,
+    messages: u64,
+    parse_failures: u64,
+    last_message: Instant,
+    /// Message count and time at the previous read, for the rate.
+    rate_mark: (u64, Instant),
+}
+
+impl WsHealth {
+    pub fn new(feed: &'static str) -> Self {
+        let now = Instant::now();
+        Self {
+            feed,
+            state: Mutex::new(HealthState {
+                connected: false,
+                connects: 0,
+                messages: 0,
+                parse_failures: 0,
+                last_message: now,
+                rate_mark: (0, now),
+            }),
+            reconnect: Notify::new(),
+        }
+    }
+
+    pub fn on_connect(&self) {
+        let mut state = self.state.lock().expect("ws health poisoned");
+        state.connected = true;
+        state.connects += 1;
+    }
+
+    pub fn on_disconnect(&self) {
+        self.state.lock().expect("ws health poisoned").connected = false;
+    }
+
+    pub fn on_message(&self) {
+        let mut state = self.state.lock().expect("ws health poisoned");
+        state.messages += 1;
+        state.last_message = Instant::now();
+    }
+
+    /// Ask the feed's loop to reconnect, e.g. because it's connected but its
+    /// data went stale. A no-op while it isn't connected.
+    pub fn request_reconnect(&self) {
+        self.reconnect.notify_waiters();
+    }
+
+    /// Resolves when `request_reconnect` is called.
+    pub async fn reconnect_requested(&self) {
+        self.reconnect.notified().await;
+    }
+
+    /// A text frame that wasn't the JSON the feed sends.
+    pub fn on_parse_failure(&self) {
+        self.state.lock().expect("ws health poisoned").parse_failures += 1;
+    }
+
+    /// Current gauges, labelled by feed. The message rate covers the time
+    /// since the previous call.
+    pub fn gauges(&self) -> Vec<LabelledGauge> {
+        let mut state = self.state.lock().expect("ws health poisoned");
+        let now = Instant::now();
+        let (mark_count, mark_at) = state.rate_mark;
+        let elapsed = now.duration_since(mark_at).as_secs_f64();
+        let rate = if elapsed > 0.0 { (state.messages - mark_count) as f64 / elapsed } else { 0.0 };
+        state.rate_mark = (state.messages, now);
+
+        let gauge = |name: &'static str, value: f64| LabelledGauge {
+            name,
+            labels: vec![("feed", self.feed.to_string())],
+            value,
+        };
+        vec![
+            gauge("ws_connected", if state.connected { 1.0 } else { 0.0 }),
+            gauge("ws_messages_total", state.messages as f64),
+            gauge("ws_messages_per_second", rate),
+            gauge("ws_last_message_age_seconds", now.duration_since(state.last_message).as_secs_f64()),
+            gauge("ws_reconnects_total", state.connects.saturating_sub(1) as f64),
+            gauge("ws_parse_failures_total", state.parse_failures as f64),
+        ]
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// One text frame exactly as a WS adapter received it.
+#[derive(Debug, Clone)]
+pub struct RawFrame {
+    /// Which feed it came from: "kalshi" or "binance".
+    pub source: &'static str,
+    /// Receive time (unix millis).
+    pub recv_ms: i64,
+    pub text: String,
+}
+
+/// Optional copy of every raw frame for recording or protocol debugging.
+/// A full channel drops frames rather than stalling the WS read loop.
+#[derive(Clone)]
+pub struct RawTap {
+    tx: mpsc::Sender<RawFrame>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl RawTap {
+    pub fn new(tx: mpsc::Sender<RawFrame>) -> Self {
+        Self { tx, dropped: Arc::default() }
+    }
+
+    /// A tap that writes each frame to the `ws_raw` debug log target
+    /// (`RUST_LOG=ws_raw=debug`).
+    pub fn logging() -> Self {
+        let (tx, mut rx) = mpsc::channel::<RawFrame>(1024);
+        tokio::spawn(async move {
+            while let Some(frame) = rx.recv().await {
+                tracing::debug!(target: "ws_raw", "{} {} {}", frame.source, frame.recv_ms, frame.text);
+            }
+        });
+        Self::new(tx)
+    }
+
+    pub fn forward(&self, source: &'static str, text: &str) {
+        let frame = RawFrame {
+            source,
+            recv_ms: chrono::Utc::now().timestamp_millis(),
+            text: text.to_string(),
+        };
+        if self.tx.try_send(frame).is_err() {
+            let n = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if n.is_power_of_two() {
+                tracing::warn!("Raw WS tap backlog full — {} frames dropped so far", n);
+            }
+        }
+    }
+}
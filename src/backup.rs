@@ -0,0 +1,37 @@
+use crate::ports::backup::BackupStore;
+use crate::storage;
+
+/// brain/ files uploaded as-is, when present.
+const BACKUP_FILES: &[&str] = &["ledger.md", "stats.md", "stats_history.csv", "equity.csv"];
+
+/// Upload the ledger, stats, and decision logs under `<YYYY-MM-DD>/`, so each
+/// day keeps its last backup. Decisions go up as one JSONL file rather than
+/// thousands of small objects. Returns the number of objects uploaded.
+pub async fn run(store: &dyn BackupStore) -> anyhow::Result<usize> {
+    let day = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    let mut uploaded = 0;
+
+    for name in BACKUP_FILES {
+        let bytes = match std::fs::read(format!("brain/{}", name)) {
+            Ok(b) => b,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        store.put(&format!("{}/{}", day, name), bytes).await?;
+        uploaded += 1;
+    }
+
+    let decisions = storage::read_decisions()?;
+    if !decisions.is_empty() {
+        let mut jsonl = Vec::new();
+        for d in &decisions {
+            serde_json::to_writer(&mut jsonl, d)?;
+            jsonl.push(b'\n');
+        }
+        store.put(&format!("{}/decisions.jsonl", day), jsonl).await?;
+        uploaded += 1;
+    }
+
+    tracing::info!("Backup: uploaded {} objects to {}/", uploaded, day);
+    Ok(uploaded)
+}
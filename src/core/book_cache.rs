@@ -0,0 +1,120 @@
+use crate::core::types::{ContractTrade, MarketState, Orderbook, OrderbookUpdate, TickerUpdate, TradeFlow};
+use std::collections::{HashMap, VecDeque};
+
+/// How far back the trade tape reaches for order flow.
+const TRADE_WINDOW_MS: i64 = 5 * 60 * 1000;
+/// A book not updated in this long is left to a REST fetch.
+const BOOK_MAX_AGE_MS: i64 = 60_000;
+
+/// Latest WS book per subscribed ticker — held positions and the watchlist
+/// of each series' active market — so the entry path can skip the REST
+/// orderbook fetch. Entries are dropped when the subscription ends or the
+/// socket reconnects (the next snapshot refills them). Also holds the
+/// `ticker_v2` quote and recent public trades of every candidate market in
+/// the traded series. Pure — no IO.
+#[derive(Debug, Default)]
+pub struct BookCache {
+    /// Latest book and when it arrived (unix millis).
+    books: HashMap<String, (OrderbookUpdate, i64)>,
+    quotes: HashMap<String, TickerUpdate>,
+    trades: HashMap<String, VecDeque<ContractTrade>>,
+}
+
+impl BookCache {
+    pub fn on_update(&mut self, update: &OrderbookUpdate, recv_ms: i64) {
+        if let Some((current, _)) = self.books.get(&update.ticker) {
+            if update.is_older_than(current) {
+                return;
+            }
+        }
+        self.books.insert(update.ticker.clone(), (update.clone(), recv_ms));
+    }
+
+    /// Merge a quote update over what's held: absent fields keep their last
+    /// value, and a volume delta adds to the last total.
+    pub fn on_ticker(&mut self, update: &TickerUpdate) {
+        let quote = self.quotes.entry(update.ticker.clone()).or_insert_with(|| TickerUpdate {
+            ticker: update.ticker.clone(),
+            yes_bid: None,
+            yes_ask: None,
+            last_price: None,
+            volume: None,
+            volume_delta: None,
+            exchange_ts_ms: None,
+        });
+        quote.yes_bid = update.yes_bid.or(quote.yes_bid);
+        quote.yes_ask = update.yes_ask.or(quote.yes_ask);
+        quote.last_price = update.last_price.or(quote.last_price);
+        quote.volume = match (update.volume, update.volume_delta, quote.volume) {
+            (Some(total), _, _) => Some(total),
+            (None, Some(delta), Some(total)) => Some((total as i64 + delta).max(0) as u64),
+            (_, _, held) => held,
+        };
+        quote.exchange_ts_ms = update.exchange_ts_ms.or(quote.exchange_ts_ms);
+    }
+
+    /// `market` with its quote fields replaced by the live WS quote, if one
+    /// is held — fresher than a cached REST listing.
+    pub fn with_quote(&self, mut market: MarketState) -> MarketState {
+        let Some(q) = self.quotes.get(&market.ticker) else {
+            return market;
+        };
+        if let Some(bid) = q.yes_bid {
+            market.yes_bid = Some(bid);
+            market.no_ask = Some(100 - bid);
+        }
+        if let Some(ask) = q.yes_ask {
+            market.yes_ask = Some(ask);
+            market.no_bid = Some(100 - ask);
+        }
+        market.last_price = q.last_price.or(market.last_price);
+        if let Some(volume) = q.volume {
+            market.volume = volume;
+        }
+        market
+    }
+
+    /// Add a public trade to the ticker's tape, dropping trades older than
+    /// the window.
+    pub fn on_trade(&mut self, trade: &ContractTrade) {
+        let now_ms = trade.exchange_ts_ms.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+        let tape = self.trades.entry(trade.ticker.clone()).or_default();
+        tape.push_back(ContractTrade { exchange_ts_ms: Some(now_ms), ..trade.clone() });
+        while tape.front().and_then(|t| t.exchange_ts_ms).is_some_and(|ts| ts < now_ms - TRADE_WINDOW_MS) {
+            tape.pop_front();
+        }
+    }
+
+    /// Taker flow on a ticker over the last five minutes, as of `now_ms`.
+    pub fn trade_flow(&self, ticker: &str, now_ms: i64) -> TradeFlow {
+        let recent = self
+            .trades
+            .get(ticker)
+            .into_iter()
+            .flatten()
+            .filter(|t| t.exchange_ts_ms.is_some_and(|ts| ts >= now_ms - TRADE_WINDOW_MS));
+        TradeFlow::from_trades(recent)
+    }
+
+    /// The ticker's book, unless it's older than a minute at `now_ms`.
+    pub fn book(&self, ticker: &str, now_ms: i64) -> Option<Orderbook> {
+        let (b, recv_ms) = self.books.get(ticker)?;
+        (now_ms - recv_ms <= BOOK_MAX_AGE_MS).then(|| Orderbook { yes: b.yes.clone(), no: b.no.clone() })
+    }
+
+    pub fn forget(&mut self, ticker: &str) {
+        self.books.remove(ticker);
+    }
+
+    /// Drop a ticker's quote and trade tape once it's no longer a candidate.
+    pub fn forget_quote(&mut self, ticker: &str) {
+        self.quotes.remove(ticker);
+        self.trades.remove(ticker);
+    }
+
+    /// Trade tapes survive: trades missed during the gap are just absent.
+    pub fn clear(&mut self) {
+        self.books.clear();
+        self.quotes.clear();
+    }
+}
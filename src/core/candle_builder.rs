@@ -0,0 +1,186 @@
+use crate::core::types::{self, Candle, PerpQuote};
+use crate::ports::price_feed::PriceFeed;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Mutex;
+
+const MINUTE_MS: i64 = 60_000;
+/// 1m candles kept per symbol: two hours covers every interval the engine asks for.
+const HISTORY_MINUTES: usize = 120;
+
+/// Rolling 1m candle history per symbol, built from streamed klines and
+/// seeded from REST when it has a gap. Longer intervals are rolled up from
+/// it. No IO.
+#[derive(Debug, Default)]
+pub struct CandleHistory {
+    /// Oldest first, each flagged closed once final: a kline with `x: true`,
+    /// or a REST candle whose minute has ended.
+    candles: HashMap<String, VecDeque<(Candle, bool)>>,
+}
+
+impl CandleHistory {
+    /// Apply a streamed kline: replaces the in-progress candle or appends the
+    /// next one. Anything other than a 1m kline is ignored.
+    pub fn record(&mut self, symbol: &str, candle: &Candle, closed: bool) {
+        if candle.close_time - candle.open_time != MINUTE_MS - 1 {
+            return;
+        }
+        let history = self.candles.entry(symbol.to_string()).or_default();
+        match history.back_mut() {
+            Some((last, last_closed)) if last.open_time == candle.open_time => {
+                *last = candle.clone();
+                *last_closed |= closed;
+            }
+            Some((last, _)) if last.open_time > candle.open_time => return,
+            _ => history.push_back((candle.clone(), closed)),
+        }
+        while history.len() > HISTORY_MINUTES {
+            history.pop_front();
+        }
+    }
+
+    /// Merge REST 1m candles into the history. A candle whose minute has
+    /// ended at `now_ms` is final and replaces any streamed one that never
+    /// saw its close; the rest only fill gaps.
+    pub fn seed(&mut self, symbol: &str, fetched: &[Candle], now_ms: i64) {
+        let history = self.candles.entry(symbol.to_string()).or_default();
+        let mut merged: BTreeMap<i64, (Candle, bool)> =
+            history.drain(..).map(|(c, closed)| (c.open_time, (c, closed))).collect();
+        for candle in fetched.iter().filter(|c| c.close_time - c.open_time == MINUTE_MS - 1) {
+            let closed = candle.close_time < now_ms;
+            match merged.get(&candle.open_time) {
+                Some((_, true)) => {}
+                Some(_) if !closed => {}
+                _ => {
+                    merged.insert(candle.open_time, (candle.clone(), closed));
+                }
+            }
+        }
+        let excess = merged.len().saturating_sub(HISTORY_MINUTES);
+        history.extend(merged.into_values().skip(excess));
+    }
+
+    /// The newest `limit` candles of `interval` as of `now_ms`, or None when
+    /// the history has a gap in that span, a past minute never closed, is
+    /// stale, or can't serve the interval.
+    pub fn candles(&self, symbol: &str, interval: &str, limit: u32, now_ms: i64) -> Option<Vec<Candle>> {
+        let minutes = interval_minutes(interval)?;
+        let span_ms = minutes * MINUTE_MS;
+        let history = self.candles.get(symbol)?;
+        let (last, _) = history.back()?;
+        // The stream stalled: the in-progress minute hasn't arrived
+        if now_ms - last.open_time >= 2 * MINUTE_MS {
+            return None;
+        }
+        let last_open = last.open_time;
+
+        let first_open = (last_open / span_ms - (limit as i64 - 1)) * span_ms;
+        let mut needed = history.iter().skip_while(|(c, _)| c.open_time < first_open).peekable();
+        if needed.peek()?.0.open_time != first_open {
+            return None;
+        }
+        let mut out: Vec<Candle> = Vec::with_capacity(limit as usize);
+        let mut expected = first_open;
+        for (c, closed) in needed {
+            // A rolled-over minute whose close we missed is partial until REST fills it
+            if c.open_time != expected || (!closed && c.open_time != last_open) {
+                return None;
+            }
+            expected += MINUTE_MS;
+            let bucket = c.open_time - c.open_time.rem_euclid(span_ms);
+            match out.last_mut() {
+                Some(agg) if agg.open_time == bucket => {
+                    agg.high = agg.high.max(c.high);
+                    agg.low = agg.low.min(c.low);
+                    agg.close = c.close;
+                    agg.volume += c.volume;
+                    agg.close_time = bucket + span_ms - 1;
+                }
+                _ => out.push(Candle {
+                    open_time: bucket,
+                    close_time: bucket + span_ms - 1,
+                    ..c.clone()
+                }),
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Minutes in `interval` if the history can serve it.
+fn interval_minutes(interval: &str) -> Option<i64> {
+    types::interval_minutes(interval)
+        .filter(|&m| m as usize <= HISTORY_MINUTES / 2)
+        .map(|m| m as i64)
+}
+
+/// `PriceFeed` that serves candles from the streamed history. When the
+/// history has a gap, it's seeded with the wrapped feed's (REST) 1m candles
+/// and served from there, or the interval is fetched directly as a last resort.
+pub struct StreamedCandles {
+    inner: Box<dyn PriceFeed>,
+    history: Mutex<CandleHistory>,
+}
+
+impl StreamedCandles {
+    pub fn new(inner: Box<dyn PriceFeed>) -> Self {
+        Self { inner, history: Mutex::default() }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for StreamedCandles {
+    async fn candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: u32,
+    ) -> Result<Option<Vec<Candle>>> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let local = self.history.lock().ok().and_then(|h| h.candles(symbol, interval, limit, now_ms));
+        if let Some(candles) = local {
+            return Ok(Some(candles));
+        }
+        tracing::debug!("{} {} candles not in stream history — fetching", symbol, interval);
+        if interval_minutes(interval).is_some() {
+            match self.inner.candles(symbol, "1m", HISTORY_MINUTES as u32).await {
+                Ok(Some(fetched)) => {
+                    let now_ms = chrono::Utc::now().timestamp_millis();
+                    let seeded = self.history.lock().ok().and_then(|mut h| {
+                        h.seed(symbol, &fetched, now_ms);
+                        h.candles(symbol, interval, limit, now_ms)
+                    });
+                    if let Some(candles) = seeded {
+                        return Ok(Some(candles));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tracing::debug!("{} 1m history fetch failed: {}", symbol, e),
+            }
+        }
+        self.inner.candles(symbol, interval, limit).await
+    }
+
+    async fn spot_price(&self, symbol: &str) -> Result<Option<f64>> {
+        self.inner.spot_price(symbol).await
+    }
+
+    async fn perp(&self, symbol: &str) -> Result<Option<PerpQuote>> {
+        self.inner.perp(symbol).await
+    }
+
+    fn record_price(&self, symbol: &str, price: f64, ts_ms: i64) {
+        self.inner.record_price(symbol, price, ts_ms);
+    }
+
+    fn record_kline(&self, symbol: &str, candle: &Candle, closed: bool) {
+        if let Ok(mut history) = self.history.lock() {
+            history.record(symbol, candle, closed);
+        }
+    }
+
+    fn realized_vol(&self, symbol: &str) -> Option<f64> {
+        self.inner.realized_vol(symbol)
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+/// Levels per side that count toward the imbalance.
+const DEPTH_LEVELS: usize = 10;
+/// A reading older than this is treated as missing (stream down or lagging).
+const MAX_AGE_MS: i64 = 30_000;
+
+/// Distance-weighted bid/ask quantity ratio of a spot book, best levels
+/// first. > 1.0 means bid-heavy, < 1.0 ask-heavy; same scale as
+/// `compute_orderbook_imbalance` on the Kalshi book.
+pub fn compute_depth_imbalance(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> f64 {
+    fn weighted_qty(levels: &[(f64, f64)]) -> f64 {
+        levels
+            .iter()
+            .take(DEPTH_LEVELS)
+            .enumerate()
+            .map(|(i, (_price, qty))| qty / (i as f64 + 1.0))
+            .sum()
+    }
+
+    let bid_qty = weighted_qty(bids);
+    let ask_qty = weighted_qty(asks);
+    if ask_qty == 0.0 {
+        if bid_qty > 0.0 { 5.0 } else { 1.0 }
+    } else {
+        (bid_qty / ask_qty).clamp(0.2, 5.0)
+    }
+}
+
+/// Latest spot-book imbalance per symbol, from the depth stream. No IO.
+#[derive(Debug, Default)]
+pub struct DepthImbalance {
+    /// (imbalance, update time in unix millis)
+    latest: HashMap<String, (f64, i64)>,
+}
+
+impl DepthImbalance {
+    pub fn record(&mut self, symbol: &str, bids: &[(f64, f64)], asks: &[(f64, f64)], ts_ms: i64) {
+        self.latest.insert(symbol.to_string(), (compute_depth_imbalance(bids, asks), ts_ms));
+    }
+
+    /// The latest imbalance, unless it's older than 30s at `now_ms`.
+    pub fn get(&self, symbol: &str, now_ms: i64) -> Option<f64> {
+        let (imbalance, ts_ms) = self.latest.get(symbol)?;
+        (now_ms - ts_ms <= MAX_AGE_MS).then_some(*imbalance)
+    }
+}
@@ -1,52 +1,117 @@
+use crate::core::book_cache::BookCache;
+use crate::core::fill_model::{EntryPlan, EntryStyle, FillModel};
 use crate::core::position_manager::PositionManager;
-use crate::core::{indicators, risk, stats, types::*};
+use crate::core::spot_cache::SpotCache;
+use crate::core::{indicators, market_select, risk, settlement, stats, types::*};
 use crate::ports::brain::Brain;
 use crate::ports::exchange::Exchange;
+use crate::ports::metrics::MetricsSink;
+use crate::ports::notifier::Notifier;
 use crate::ports::price_feed::PriceFeed;
-use crate::storage;
+use crate::ports::storage::Storage;
+use crate::ports::vol_feed::VolFeed;
 use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use tokio_util::sync::CancellationToken;
 
 /// Run an entry cycle for a specific series (e.g., "KXBTC15M").
-/// Skips if we already hold a position for this series.
+/// Skips if we already hold a position for this series. `held_settlements`
+/// holds the order ids whose settlement failed verification and was already
+/// alerted, so a mismatch alerts once rather than every cycle.
+/// `resting_orders` are the daemon's tracked entries (fill timeout,
+/// repricing), which the stale-order sweep leaves alone. `target` names a
+/// pre-fetched market to trade (series rollover) instead of the soonest-
+/// expiring open one. With `at_capacity` set (open-position cap reached) a
+/// BUY that clears every gate comes back as `Queued` instead of being placed.
+/// Shutdown aborts the
+/// cycle with `ShutdownRequested` at any await up to order placement; once an
+/// order is sent, the cycle always runs through to the ledger write.
+#[allow(clippy::too_many_arguments)]
 pub async fn entry_cycle(
     exchange: &dyn Exchange,
     brain: &dyn Brain,
     price_feed: &dyn PriceFeed,
+    vol_feed: Option<&dyn VolFeed>,
+    notifier: &dyn Notifier,
+    storage: &dyn Storage,
     config: &Config,
     position_mgr: &PositionManager,
+    fill_model: &FillModel,
+    books: &BookCache,
+    spot: &SpotCache,
+    held_settlements: &mut HashSet<String>,
+    resting_orders: &HashMap<String, tokio::time::Instant>,
     series_ticker: &str,
-) -> Result<()> {
-    let asset = series_to_asset_label(series_ticker);
+    target: Option<&str>,
+    at_capacity: bool,
+    shutdown: &CancellationToken,
+) -> Result<EntryOutcome> {
+    let asset = config.asset_label(series_ticker);
 
     // Skip entry if we already hold a position for this series
     if position_mgr.has_position_for_series(series_ticker) {
         tracing::info!("[{}] Holding position — skipping entry cycle", asset);
-        return Ok(());
+        return Ok(EntryOutcome::NoTrade);
     }
 
-    // 1. CANCEL stale resting orders from previous cycles
-    let resting = exchange.resting_orders().await?;
-    for order in &resting {
+    // 1. CANCEL this series' stale resting orders — untracked entries still
+    // resting past their GoodTill expiry; the exchange expires the rest. The
+    // snapshot's balance predates the cancels, so the risk check below is
+    // conservative.
+    let portfolio = exchange.portfolio_snapshot().await?;
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let stale = portfolio.resting_orders.iter().filter(|o| {
+        ticker_series(&o.ticker) == series_ticker
+            && !resting_orders.contains_key(&o.order_id)
+            && o.expires_ms.is_some_and(|expires| expires <= now_ms)
+    });
+    for order in stale {
         exchange.cancel_order(&order.order_id).await?;
-        storage::cancel_trade(&order.order_id)?;
+        storage.cancel_trade(&order.order_id).await?;
         tracing::info!("[{}] Canceled stale order: {}", asset, order.order_id);
     }
 
-    // 2. SETTLE — check if previous trade settled, update ledger + stats
-    let mut ledger = storage::read_ledger()?;
-    if let Some(pending) = ledger.iter().rev().find(|r| r.result == "pending") {
-        let pending_ticker = pending.ticker.clone();
-        let pending_timestamp = pending.timestamp.clone();
+    // 2. SETTLE — check if this series' previous trade settled, update ledger + stats
+    let pending = storage
+        .query_ledger(&QueryFilter {
+            series: Some(series_ticker.to_string()),
+            result: Some(ResultFilter::Pending),
+            ..Default::default()
+        })
+        .await?;
+    if let Some(pending_row) = pending.last() {
+        let pending_ticker = pending_row.ticker.clone();
+        let pending_timestamp = pending_row.timestamp.clone();
         let settlements = exchange.settlements(&pending_ticker).await?;
         if let Some(s) = settlements.first() {
-            storage::settle_last_trade(s)?;
-            ledger = storage::read_ledger()?;
-            let settled_stats = stats::compute(&ledger);
-            storage::write_stats(&settled_stats)?;
-            tracing::info!(
-                "[{}] Settled: {} (market_result={}) | {} {}¢",
-                asset, s.result.to_uppercase(), s.market_result, s.ticker, s.pnl_cents
-            );
+            // Second source: the market's own final result
+            let market_result = exchange.market_result(&pending_ticker).await?;
+            match settlement::verify(s, market_result.as_deref(), pending_row) {
+                Ok(()) => {
+                    storage.settle_trade(s).await?;
+                    held_settlements.remove(&pending_row.order_id);
+                    let settled_stats = stats::compute(&storage.read_ledger().await?);
+                    storage.write_stats(&settled_stats).await?;
+                    storage.append_stats_history(&s.ticker, &settled_stats).await?;
+                    tracing::info!(
+                        "[{}] Settled: {} (market_result={}) | {} {} {}x @ {}¢ → {}¢ (fees {}¢)",
+                        asset, s.result.to_uppercase(), s.market_result, s.ticker,
+                        s.side, s.count, s.price_cents, s.pnl_cents, s.fee_cents
+                    );
+                }
+                Err(reason) => {
+                    let msg = format!(
+                        "[{}] Holding settlement of {} ({}): {}",
+                        asset, pending_ticker, pending_row.order_id, reason
+                    );
+                    if !held_settlements.insert(pending_row.order_id.clone()) {
+                        tracing::warn!("{}", msg);
+                    } else if let Err(e) = notifier.alert(&msg).await {
+                        tracing::error!("{} (alert failed: {})", msg, e);
+                    }
+                }
+            }
         } else {
             if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(&pending_timestamp) {
                 let age_min = (chrono::Utc::now() - ts.with_timezone(&chrono::Utc)).num_minutes();
@@ -58,11 +123,11 @@ pub async fn entry_cycle(
                         price_cents: 0,
                         result: "unknown".into(),
                         pnl_cents: 0,
+                        fee_cents: 0,
                         settled_time: chrono::Utc::now().to_rfc3339(),
                         market_result: "unknown".into(),
                     };
-                    storage::settle_last_trade(&zombie)?;
-                    ledger = storage::read_ledger()?;
+                    storage.settle_trade(&zombie).await?;
                     tracing::warn!(
                         "[{}] Zombie cleanup: pending entry for {} was {}min old",
                         asset, pending_ticker, age_min
@@ -72,38 +137,75 @@ pub async fn entry_cycle(
         }
     }
 
-    // 3. RISK
-    let computed_stats = stats::compute(&ledger);
-    let balance = exchange.balance().await?;
-
-    if let Some(veto) = risk::check(&computed_stats, balance, config) {
+    // 3. RISK — account-wide limits from the in-memory counters
+    if shutdown.is_cancelled() {
+        return Err(ShutdownRequested.into());
+    }
+    if let Some(veto) = risk::check(&storage.counters(), portfolio.balance_cents, config) {
         tracing::info!("[{}] Risk veto: {}", asset, veto);
-        return Ok(());
+        return Ok(EntryOutcome::NoTrade);
     }
 
-    // 4. MARKET — fetch active market for this series
-    let market = match exchange.active_market(series_ticker).await? {
+    // 4. MARKET — soonest-expiring open market (or the rollover target),
+    // after config pins/exclusions
+    let candidates = match target {
+        Some(ticker) => vec![until_shutdown(shutdown, exchange.market(ticker)).await?],
+        None => until_shutdown(shutdown, exchange.open_markets(series_ticker)).await?,
+    };
+    let candidates = candidates.into_iter().map(|m| books.with_quote(m)).collect();
+    let market = match market_select::select(candidates, series_ticker, &config.market_overrides) {
         Some(m) if m.minutes_to_expiry >= config.min_minutes_to_expiry => m,
         Some(m) => {
             tracing::info!("[{}] Too close to expiry: {:.1}min", asset, m.minutes_to_expiry);
-            return Ok(());
+            return Ok(EntryOutcome::NoTrade);
         }
         None => {
             tracing::info!("[{}] No active market", asset);
-            return Ok(());
+            return Ok(EntryOutcome::NoTrade);
         }
     };
 
-    // 5. ORDERBOOK
-    let orderbook = exchange.orderbook(&market.ticker).await?;
+    // 5. ORDERBOOK — from the WS book cache when the market is watched and
+    // its book is fresh
+    let orderbook = match books.book(&market.ticker, chrono::Utc::now().timestamp_millis()) {
+        Some(book) => book,
+        None => until_shutdown(shutdown, exchange.orderbook(&market.ticker)).await?,
+    };
+    let sample = BookSample::from_orderbook(&market.ticker, &orderbook);
+    if let Err(e) = storage.append_book_sample(&sample).await {
+        tracing::warn!("[{}] Failed to record book sample: {}", asset, e);
+    }
 
     // 5.5. CRYPTO PRICE — fetch for the relevant asset
-    let binance_symbol = series_to_binance_symbol(series_ticker);
-    let crypto_price = fetch_crypto_price(price_feed, binance_symbol).await;
+    let price_symbol = config.price_symbol(series_ticker);
+    let strategy = config.strategy_for(series_ticker);
+    let (crypto_price, implied_vol) = match price_symbol {
+        Some(symbol) => {
+            until_shutdown(shutdown, async {
+                Ok(tokio::join!(
+                    fetch_crypto_price(price_feed, spot, symbol, config.candles_for(series_ticker)),
+                    fetch_implied_vol(vol_feed, symbol),
+                ))
+            })
+            .await?
+        }
+        None => {
+            tracing::warn!("[{}] No price symbol for {} — add it to SERIES_SYMBOLS", asset, series_ticker);
+            (Ok(None), None)
+        }
+    };
+    let crypto_price = match crypto_price {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            tracing::warn!("[{}] Price feed down — skipping cycle: {}", asset, e);
+            return Ok(EntryOutcome::NoTrade);
+        }
+    };
 
     // 5.6. SIGNAL SUMMARY — compute from indicators + orderbook + market
+    let trade_flow = books.trade_flow(&market.ticker, chrono::Utc::now().timestamp_millis());
     let signal_summary = crypto_price.as_ref().map(|snap| {
-        indicators::compute_signal_summary(&snap.indicators, &orderbook, &market)
+        indicators::compute_signal_summary(strategy, &snap.indicators, &orderbook, &market, &trade_flow, implied_vol)
     });
 
     // 5.7. PRE-FILTER — skip LLM call if no signal (saves ~$0.05/cycle)
@@ -113,35 +215,76 @@ pub async fn entry_cycle(
                 "[{}] Pre-filter: no signal (edge={:.1}pt) — skipping LLM call",
                 asset, summary.estimated_edge
             );
-            return Ok(());
+            return Ok(EntryOutcome::NoTrade);
         }
     }
 
-    // 6. BRAIN
+    // 6. BRAIN — stats and history scoped to this series
+    let series_ledger = storage
+        .query_ledger(&QueryFilter {
+            series: Some(series_ticker.to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let series_stats = stats::compute(&series_ledger);
+    // Strike ladder and contract history side by side — neither gates the cycle.
+    // Reach past the 15m lookback so a candle at or before its cutoff exists
+    let since = chrono::Utc::now() - chrono::Duration::minutes(20);
+    let (event, contract_candles) = until_shutdown(shutdown, async {
+        Ok(tokio::join!(
+            exchange.event(&market.event_ticker),
+            exchange.market_candles(&market.ticker, since, 1),
+        ))
+    })
+    .await?;
+    let strikes = match event {
+        Ok(event) => {
+            tracing::debug!(
+                "[{}] {} {} \"{}\": {} strikes",
+                asset, event.series_ticker, event.event_ticker, event.title, event.markets.len()
+            );
+            event.markets
+        }
+        Err(e) => {
+            tracing::warn!("[{}] Strike ladder unavailable: {}", asset, e);
+            Vec::new()
+        }
+    };
+    let contract_candles = contract_candles.unwrap_or_else(|e| {
+        tracing::warn!("[{}] Contract candles unavailable: {}", asset, e);
+        Vec::new()
+    });
+    let current_streak = series_stats.current_streak;
+
     let context = DecisionContext {
-        prompt_md: storage::read_prompt()?,
-        stats: computed_stats,
-        last_n_trades: ledger.iter().rev().take(20).cloned().collect(),
+        prompt_md: storage.read_prompt().await?,
+        stats: series_stats,
+        last_n_trades: series_ledger.iter().rev().take(20).cloned().collect(),
         market: market.clone(),
+        strikes,
+        contract_candles,
         orderbook,
         crypto_price,
-        crypto_label: format!("{} (Binance {})", asset, binance_symbol),
+        implied_vol,
+        crypto_label: match price_symbol {
+            Some(symbol) => format!("{} (Binance {})", asset, symbol),
+            None => asset.to_string(),
+        },
         signal_summary: signal_summary.clone(),
     };
 
-    let decision = brain.decide(&context).await?;
+    let decision = until_shutdown(shutdown, brain.decide(&context)).await?;
 
     // 7. VALIDATE
     if decision.action == Action::Pass {
         tracing::info!("[{}] PASS: {}", asset, decision.reasoning);
-        return Ok(());
+        return Ok(EntryOutcome::NoTrade);
     }
 
-    let side = decision.side.unwrap_or(Side::Yes);
+    let side = decision.side.clone().unwrap_or(Side::Yes);
     let price = decision.max_price_cents.unwrap_or(50).clamp(1, 99);
 
     // 7.5. EDGE VALIDATION GATE — block insufficient edge
-    let current_streak = stats::compute(&ledger).current_streak;
     if let Some(veto) = risk::validate_edge(
         decision.estimated_probability,
         decision.estimated_edge,
@@ -149,7 +292,7 @@ pub async fn entry_cycle(
         current_streak,
     ) {
         tracing::info!("[{}] Edge gate veto: {}", asset, veto);
-        return Ok(());
+        return Ok(EntryOutcome::NoTrade);
     }
 
     // 7.6. KELLY CAP — clamp LLM's shares to Kelly-optimal
@@ -173,15 +316,196 @@ pub async fn entry_cycle(
         asset, proposed_shares, kelly_cap, shares
     );
 
+    // 7.7. ENTRY STYLE — maker at the bid vs taker at the ask, per trade
+    let win_prob = decision.estimated_probability.unwrap_or(50.0);
+    let plan = match fill_model.plan_entry(
+        series_ticker, &context.orderbook, &side, shares, win_prob, price,
+    ) {
+        Some(plan) => plan,
+        None => {
+            tracing::info!(
+                "[{}] No viable entry: ask above {}¢ and maker fill unlikely",
+                asset, price
+            );
+            return Ok(EntryOutcome::NoTrade);
+        }
+    };
+    tracing::info!(
+        "[{}] Entry: {} @ {}¢ (limit {}¢, fill prob {})",
+        asset, plan.style, plan.price_cents, price,
+        plan.fill_probability.map(|p| format!("{:.0}%", p * 100.0)).unwrap_or_else(|| "n/a".into())
+    );
+    let limit_price = price;
+    let price = plan.price_cents;
+
+    // 7.8. EXPOSURE — cap correlated cost basis per event and per asset
+    let open_rows = storage
+        .query_ledger(&QueryFilter {
+            result: Some(ResultFilter::Pending),
+            ..Default::default()
+        })
+        .await?;
+    if let Some(veto) = risk::check_exposure(&open_rows, &market.ticker, price as i64 * shares as i64, config) {
+        tracing::info!("[{}] Exposure veto: {}", asset, veto);
+        return Ok(EntryOutcome::NoTrade);
+    }
+
+    // 7.9. POSITION CAP — hold the BUY until a slot frees up
+    if at_capacity {
+        tracing::info!(
+            "[{}] At max open positions ({}) — queueing {:?} {}x ≤{}¢ on {} for {}s",
+            asset, config.max_open_positions, side, shares, limit_price,
+            market.ticker, config.entry_queue_ttl_secs
+        );
+        return Ok(EntryOutcome::Queued(Box::new(QueuedEntry {
+            series: series_ticker.to_string(),
+            side,
+            shares,
+            limit_price_cents: limit_price,
+            win_prob,
+            strategy,
+            expires: chrono::Utc::now()
+                + chrono::Duration::seconds(config.entry_queue_ttl_secs as i64),
+            context,
+            decision,
+        })));
+    }
+
+    place_entry(exchange, notifier, storage, config, asset, &context, &decision, &plan, side, shares, strategy, shutdown).await
+}
+
+/// Why entries should wait right now (trading halted, exchange inactive, or
+/// inside a maintenance window), or None if trading is open. Checked once
+/// before a batch of entry cycles so a closed exchange is skipped quietly
+/// instead of every cycle erroring.
+pub async fn entry_block(exchange: &dyn Exchange) -> Result<Option<String>> {
+    Ok(exchange.exchange_status().await?.entry_block(chrono::Utc::now()))
+}
+
+/// Series to trade when none are configured: those in `series_category`
+/// on the `series_frequency` schedule whose underlying has a price feed.
+pub async fn discover_series(exchange: &dyn Exchange, config: &Config) -> Result<Vec<SeriesInfo>> {
+    let mut found: Vec<SeriesInfo> = exchange
+        .series(&config.series_category)
+        .await?
+        .into_iter()
+        .filter(|s| {
+            s.category.eq_ignore_ascii_case(&config.series_category)
+                && s.frequency == config.series_frequency
+                && config.price_symbol(&s.ticker).is_some()
+        })
+        .collect();
+    found.sort_by(|a, b| a.ticker.cmp(&b.ticker));
+    Ok(found)
+}
+
+/// Re-validate a queued BUY against fresh quotes and place it. The market
+/// must still be open with time left, the series still flat, and the entry
+/// still viable at the original limit price; otherwise the signal is dropped.
+#[allow(clippy::too_many_arguments)]
+pub async fn execute_queued(
+    exchange: &dyn Exchange,
+    notifier: &dyn Notifier,
+    storage: &dyn Storage,
+    config: &Config,
+    position_mgr: &PositionManager,
+    fill_model: &FillModel,
+    books: &BookCache,
+    entry: QueuedEntry,
+    shutdown: &CancellationToken,
+) -> Result<EntryOutcome> {
+    let QueuedEntry { series, side, shares, limit_price_cents, win_prob, strategy, mut context, decision, .. } = entry;
+    let asset = config.asset_label(&series);
+
+    if position_mgr.has_position_for_series(&series) {
+        tracing::info!("[{}] Holding position — dropping queued entry", asset);
+        return Ok(EntryOutcome::NoTrade);
+    }
+
+    let balance = until_shutdown(shutdown, exchange.balance()).await?;
+    if let Some(veto) = risk::check(&storage.counters(), balance, config) {
+        tracing::info!("[{}] Risk veto on queued entry: {}", asset, veto);
+        return Ok(EntryOutcome::NoTrade);
+    }
+
+    let market = until_shutdown(shutdown, exchange.market(&context.market.ticker)).await?;
+    if market.minutes_to_expiry < config.min_minutes_to_expiry {
+        tracing::info!(
+            "[{}] Queued entry on {} stale: {:.1}min to expiry",
+            asset, market.ticker, market.minutes_to_expiry
+        );
+        return Ok(EntryOutcome::NoTrade);
+    }
+
+    let orderbook = match books.book(&market.ticker, chrono::Utc::now().timestamp_millis()) {
+        Some(book) => book,
+        None => until_shutdown(shutdown, exchange.orderbook(&market.ticker)).await?,
+    };
+    let plan = match fill_model.plan_entry(&series, &orderbook, &side, shares, win_prob, limit_price_cents) {
+        Some(plan) => plan,
+        None => {
+            tracing::info!(
+                "[{}] Queued entry on {} no longer viable at {}¢",
+                asset, market.ticker, limit_price_cents
+            );
+            return Ok(EntryOutcome::NoTrade);
+        }
+    };
+
+    let open_rows = storage
+        .query_ledger(&QueryFilter {
+            result: Some(ResultFilter::Pending),
+            ..Default::default()
+        })
+        .await?;
+    let cost = plan.price_cents as i64 * shares as i64;
+    if let Some(veto) = risk::check_exposure(&open_rows, &market.ticker, cost, config) {
+        tracing::info!("[{}] Exposure veto on queued entry: {}", asset, veto);
+        return Ok(EntryOutcome::NoTrade);
+    }
+
+    tracing::info!(
+        "[{}] Slot free — executing queued entry: {} @ {}¢ (limit {}¢)",
+        asset, plan.style, plan.price_cents, limit_price_cents
+    );
+    context.market = market;
+    context.orderbook = orderbook;
+    place_entry(exchange, notifier, storage, config, asset, &context, &decision, &plan, side, shares, strategy, shutdown).await
+}
+
+/// Final position check, then place the entry (or record it on paper),
+/// write the ledger row and decision record, and notify.
+#[allow(clippy::too_many_arguments)]
+async fn place_entry(
+    exchange: &dyn Exchange,
+    notifier: &dyn Notifier,
+    storage: &dyn Storage,
+    config: &Config,
+    asset: &str,
+    context: &DecisionContext,
+    decision: &TradeDecision,
+    plan: &EntryPlan,
+    side: Side,
+    shares: u32,
+    strategy: Strategy,
+    shutdown: &CancellationToken,
+) -> Result<EntryOutcome> {
+    let market = &context.market;
+    let price = plan.price_cents;
+    let total_pnl_cents: i64 = storage.read_ledger().await?.iter().map(|r| r.pnl_cents).sum();
+    let signal = context.signal_summary.as_ref().map(|s| s.narrative.clone()).unwrap_or_default();
+
     // 8. FINAL POSITION CHECK
-    let fresh_positions = exchange.positions().await?;
+    let fresh_positions = until_shutdown(shutdown, exchange.positions()).await?;
     if fresh_positions.iter().any(|p| p.ticker == market.ticker) {
         tracing::warn!("[{}] Position on {} — aborting order", asset, market.ticker);
-        return Ok(());
+        return Ok(EntryOutcome::NoTrade);
     }
 
-    // 9. EXECUTE
-    let current_stats = stats::compute(&ledger);
+    // 9. EXECUTE — last point at which shutdown may abort the cycle
+    if shutdown.is_cancelled() {
+        return Err(ShutdownRequested.into());
+    }
 
     if config.paper_trade {
         let paper_id = format!("paper-{}", chrono::Utc::now().timestamp_millis());
@@ -189,7 +513,7 @@ pub async fn entry_cycle(
             "[{}] PAPER: {:?} {}x @ {}¢ | {} ({})",
             asset, side, shares, price, market.ticker, paper_id
         );
-        storage::append_ledger(&LedgerRow {
+        storage.append_ledger(&LedgerRow {
             timestamp: chrono::Utc::now().to_rfc3339(),
             ticker: market.ticker.clone(),
             side: format!("{:?}", side).to_lowercase(),
@@ -197,62 +521,535 @@ pub async fn entry_cycle(
             price,
             result: "pending".into(),
             pnl_cents: 0,
-            cumulative_cents: current_stats.total_pnl_cents,
-            order_id: paper_id,
-        })?;
-    } else {
-        let order_result = exchange
-            .place_order(&OrderRequest {
+            cumulative_cents: total_pnl_cents,
+            order_id: paper_id.clone(),
+            signal,
+        }).await?;
+        record_decision(storage, &paper_id, context, decision, plan, shares, strategy).await;
+        notify_entry(notifier, asset, "PAPER", &side, shares, price, context).await;
+        return Ok(EntryOutcome::Paper);
+    }
+
+    // Journal the intent first so a crash after placement can be recovered
+    let mut order = OrderRequest::new(market.ticker.clone(), side.clone(), shares, price);
+    order.time_in_force = TimeInForce::GoodTill(entry_expiry(market, config, chrono::Utc::now()));
+    // A maker entry priced to rest must never take liquidity if the book moved
+    order.post_only = plan.style == EntryStyle::Maker;
+    storage.journal(&JournalEvent::Submitted { order: order.clone() }).await?;
+
+    let result = match exchange.place_order(&order).await {
+        Ok(result) => result,
+        Err(e) => {
+            let refusal = e.downcast_ref::<KalshiError>().filter(|k| k.is_order_refusal());
+            if refusal.is_some() {
+                journal_or_warn(storage, JournalEvent::Abandoned {
+                    client_order_id: order.client_order_id.clone(),
+                }).await;
+            }
+            if let Some(KalshiError::InsufficientBalance(_) | KalshiError::MarketClosed(_) | KalshiError::PostOnlyCross(_)) = refusal {
+                tracing::warn!("[{}] Order refused — no trade: {}", asset, e);
+                return Ok(EntryOutcome::NoTrade);
+            }
+            tracing::error!("[{}] Order placement failed: {}", asset, e);
+            return Err(e);
+        }
+    };
+    journal_or_warn(storage, JournalEvent::Placed {
+        client_order_id: order.client_order_id.clone(),
+        order_id: result.order_id.clone(),
+        created_ms: result.created_ms,
+    }).await;
+
+    tracing::info!(
+        "[{}] LIVE: {:?} {}x @ {}¢ | {} (order {} status: {})",
+        asset, side, shares, price, market.ticker, result.order_id, result.status
+    );
+
+    if matches!(result.status, OrderStatus::Rejected | OrderStatus::Canceled) {
+        journal_or_warn(storage, JournalEvent::Abandoned {
+            client_order_id: order.client_order_id.clone(),
+        }).await;
+    }
+
+    let outcome = match &result.status {
+        OrderStatus::Rejected => {
+            return Err(OrderRejected {
+                order_id: result.order_id,
                 ticker: market.ticker.clone(),
-                side: side.clone(),
-                shares,
-                price_cents: price,
-            })
-            .await;
+            }
+            .into());
+        }
+        OrderStatus::Canceled => {
+            tracing::warn!("[{}] Order {} canceled on placement — no trade", asset, result.order_id);
+            return Ok(EntryOutcome::NoTrade);
+        }
+        OrderStatus::Executed => EntryOutcome::Filled(FillEvent {
+            order_id: result.order_id.clone(),
+            ticker: market.ticker.clone(),
+            side: side.clone(),
+            shares,
+            price_cents: price,
+            exchange_ts_ms: result.created_ms,
+        }),
+        OrderStatus::Resting | OrderStatus::Pending | OrderStatus::Unknown(_) => {
+            EntryOutcome::Resting {
+                order_id: result.order_id.clone(),
+                ticker: market.ticker.clone(),
+            }
+        }
+    };
 
-        match order_result {
-            Ok(result) => {
-                tracing::info!(
-                    "[{}] LIVE: {:?} {}x @ {}¢ | {} (order {} status: {})",
-                    asset, side, shares, price, market.ticker, result.order_id, result.status
+    if let Err(e) = storage.append_ledger(&LedgerRow {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        ticker: market.ticker.clone(),
+        side: format!("{:?}", side).to_lowercase(),
+        shares,
+        price,
+        result: "pending".into(),
+        pnl_cents: 0,
+        cumulative_cents: total_pnl_cents,
+        order_id: result.order_id.clone(),
+        signal,
+    }).await {
+        tracing::error!(
+            "CRITICAL: Order {} placed but ledger write failed: {}",
+            result.order_id, e
+        );
+        return Err(e);
+    }
+    journal_or_warn(storage, JournalEvent::Recorded {
+        client_order_id: order.client_order_id.clone(),
+    }).await;
+    record_decision(storage, &result.order_id, context, decision, plan, shares, strategy).await;
+    notify_entry(notifier, asset, "LIVE", &side, shares, price, context).await;
+
+    Ok(outcome)
+}
+
+/// When an entry order expires on the exchange: after the fill timeout, but
+/// no later than the point the market gets too close to expiry to enter.
+fn entry_expiry(market: &MarketState, config: &Config, now: chrono::DateTime<chrono::Utc>) -> i64 {
+    let timeout = now + chrono::Duration::seconds(config.fill_timeout_secs as i64);
+    let cutoff = chrono::DateTime::parse_from_rfc3339(&market.expiration_time)
+        .map(|exp| exp.with_timezone(&chrono::Utc) - chrono::Duration::seconds((config.min_minutes_to_expiry * 60.0) as i64));
+    match cutoff {
+        Ok(cutoff) if cutoff > now => timeout.min(cutoff).timestamp(),
+        _ => timeout.timestamp(),
+    }
+}
+
+/// Best-effort trade notification with the condensed signal summary, so a
+/// trade can be judged from the message alone.
+async fn notify_entry(
+    notifier: &dyn Notifier,
+    asset: &str,
+    mode: &str,
+    side: &Side,
+    shares: u32,
+    price: u32,
+    context: &DecisionContext,
+) {
+    let mut msg = format!(
+        "[{}] {} BUY {} {}x @ {}¢ on {}",
+        asset, mode, side, shares, price, context.market.ticker
+    );
+    if let Some(summary) = &context.signal_summary {
+        msg.push('\n');
+        msg.push_str(&summary.condensed());
+    }
+    if let Err(e) = notifier.notify(&msg).await {
+        tracing::warn!("[{}] Trade notification failed: {}", asset, e);
+    }
+}
+
+/// Journal writes after placement are best-effort: a missing entry only
+/// means reconciliation re-checks the order on the next start.
+async fn journal_or_warn(storage: &dyn Storage, event: JournalEvent) {
+    if let Err(e) = storage.journal(&event).await {
+        tracing::warn!("Journal write failed ({:?}): {}", event, e);
+    }
+}
+
+/// Recover orders the journal shows as submitted but never recorded — the
+/// process died between `place_order` and `append_ledger`. Missing ledger
+/// rows are written as pending; the returned outcomes let the daemon track
+/// the recovered positions and resting orders. Run once at startup.
+pub async fn reconcile_journal(
+    exchange: &dyn Exchange,
+    storage: &dyn Storage,
+) -> Result<Vec<EntryOutcome>> {
+    let unresolved = storage.unresolved_orders().await?;
+    if unresolved.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ledger = storage.read_ledger().await?;
+    let total_pnl_cents: i64 = ledger.iter().map(|r| r.pnl_cents).sum();
+    let mut recovered = Vec::new();
+
+    for u in unresolved {
+        let order = &u.order;
+        let client_order_id = order.client_order_id.clone();
+
+        let found = match exchange.order_by_client_id(&order.ticker, &client_order_id).await {
+            Ok(found) => found,
+            Err(e) => {
+                tracing::warn!(
+                    "Journal: lookup of {} on {} failed, retrying next start: {}",
+                    client_order_id, order.ticker, e
                 );
-                if let Err(e) = storage::append_ledger(&LedgerRow {
-                    timestamp: chrono::Utc::now().to_rfc3339(),
-                    ticker: market.ticker.clone(),
-                    side: format!("{:?}", side).to_lowercase(),
-                    shares,
-                    price,
-                    result: "pending".into(),
-                    pnl_cents: 0,
-                    cumulative_cents: current_stats.total_pnl_cents,
-                    order_id: result.order_id.clone(),
-                }) {
-                    tracing::error!(
-                        "CRITICAL: Order {} placed but ledger write failed: {}",
-                        result.order_id, e
-                    );
-                    return Err(e.into());
-                }
+                continue;
             }
-            Err(e) => {
-                tracing::error!("[{}] Order placement failed: {}", asset, e);
-                return Err(e);
+        };
+        // The exchange listing is authoritative; fall back to the journaled id
+        let result = match (found, u.order_id) {
+            (Some(result), _) => result,
+            (None, Some(order_id)) => OrderResult { order_id, status: OrderStatus::Resting, created_ms: None },
+            (None, None) => {
+                tracing::info!("Journal: {} never reached the exchange", client_order_id);
+                journal_or_warn(storage, JournalEvent::Abandoned { client_order_id }).await;
+                continue;
             }
+        };
+
+        if matches!(result.status, OrderStatus::Rejected | OrderStatus::Canceled) {
+            journal_or_warn(storage, JournalEvent::Abandoned { client_order_id }).await;
+            continue;
+        }
+
+        if !ledger.iter().any(|r| r.order_id == result.order_id) {
+            storage.append_ledger(&LedgerRow {
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                ticker: order.ticker.clone(),
+                side: format!("{:?}", order.side).to_lowercase(),
+                shares: order.shares,
+                price: order.price_cents,
+                result: "pending".into(),
+                pnl_cents: 0,
+                cumulative_cents: total_pnl_cents,
+                order_id: result.order_id.clone(),
+                signal: String::new(),
+            }).await?;
+            tracing::warn!(
+                "Journal: recovered order {} ({:?} {}x @ {}¢ on {}, status {}) into ledger",
+                result.order_id, order.side, order.shares, order.price_cents, order.ticker, result.status
+            );
         }
+        journal_or_warn(storage, JournalEvent::Recorded { client_order_id }).await;
+
+        recovered.push(match result.status {
+            OrderStatus::Executed => EntryOutcome::Filled(FillEvent {
+                order_id: result.order_id,
+                ticker: order.ticker.clone(),
+                side: order.side.clone(),
+                shares: order.shares,
+                price_cents: order.price_cents,
+                exchange_ts_ms: result.created_ms,
+            }),
+            _ => EntryOutcome::Resting {
+                order_id: result.order_id,
+                ticker: order.ticker.clone(),
+            },
+        });
     }
 
-    Ok(())
+    storage.compact_journal().await?;
+    Ok(recovered)
+}
+
+/// Persist the decision context for a placed order. Failures are logged only —
+/// the order and ledger row already exist.
+async fn record_decision(
+    storage: &dyn Storage,
+    order_id: &str,
+    context: &DecisionContext,
+    decision: &TradeDecision,
+    plan: &EntryPlan,
+    shares: u32,
+    strategy: Strategy,
+) {
+    let record = DecisionRecord {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        order_id,
+        ticker: &context.market.ticker,
+        strategy,
+        prompt: &decision.prompt,
+        raw_response: &decision.raw_response,
+        decision,
+        signal_summary: context.signal_summary.as_ref(),
+        orderbook: &context.orderbook,
+        context,
+        entry_style: plan.style.to_string(),
+        entry_price_cents: plan.price_cents,
+        shares,
+    };
+    if let Err(e) = storage.write_decision(&record).await {
+        tracing::warn!("Failed to persist decision context for {}: {}", order_id, e);
+    }
+}
+
+/// Recover entry fills the WS feed missed while disconnected: buy fills
+/// since `since` on orders still tracked as resting, merged into one fill
+/// per order (total shares, volume-weighted price). Fills the feed did
+/// deliver already took their order off the resting list, so nothing is
+/// counted twice.
+pub async fn reconcile_fills(
+    exchange: &dyn Exchange,
+    resting_order_ids: &[String],
+    since: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<FillEvent>> {
+    if resting_order_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut merged: Vec<FillEvent> = Vec::new();
+    for fill in exchange.fills(None, since).await? {
+        if !resting_order_ids.contains(&fill.order_id) {
+            continue;
+        }
+        match merged.iter_mut().find(|m| m.order_id == fill.order_id) {
+            Some(m) => {
+                let total = m.shares + fill.shares;
+                m.price_cents = (m.price_cents * m.shares + fill.price_cents * fill.shares) / total.max(1);
+                m.shares = total;
+            }
+            None => merged.push(fill),
+        }
+    }
+    for fill in &merged {
+        tracing::warn!(
+            "Recovered missed fill: {:?} {}x @ {}¢ on {} (order {})",
+            fill.side, fill.shares, fill.price_cents, fill.ticker, fill.order_id
+        );
+    }
+    Ok(merged)
+}
+
+/// Record one point on the equity curve: exchange balance plus open
+/// positions marked to market.
+pub async fn record_equity(
+    exchange: &dyn Exchange,
+    storage: &dyn Storage,
+    position_mgr: &PositionManager,
+) -> Result<()> {
+    let balance = exchange.balance().await?;
+    let (cost_basis_cents, marked_value_cents) = position_mgr.mark_to_market();
+    let sample = EquitySample {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        balance_cents: balance,
+        open_positions: position_mgr.position_count() as u32,
+        cost_basis_cents,
+        marked_value_cents,
+    };
+    tracing::debug!(
+        "Equity: {}¢ (balance {}¢, {} positions marked {}¢ vs cost {}¢)",
+        sample.equity_cents(), balance, sample.open_positions, marked_value_cents, cost_basis_cents
+    );
+    storage.append_equity_sample(&sample).await
+}
+
+/// Push this instance's balance, open positions, and ledger stats — plus
+/// `labelled` adapter gauges such as Kalshi endpoint latency — to the
+/// shared metrics collector, labelled so several instances can be told apart.
+pub async fn push_metrics(
+    exchange: &dyn Exchange,
+    storage: &dyn Storage,
+    position_mgr: &PositionManager,
+    sink: &dyn MetricsSink,
+    config: &Config,
+    labelled: Vec<LabelledGauge>,
+) -> Result<()> {
+    let balance = exchange.balance().await?;
+    let st = stats::compute(&storage.read_ledger().await?);
+    let sample = MetricsSample {
+        labels: vec![
+            ("instance", config.metrics_instance.clone()),
+            ("mode", if config.paper_trade { "paper" } else { "live" }.to_string()),
+            ("series", config.series_tickers.join(",")),
+        ],
+        gauges: vec![
+            ("kalshi_bot_balance_cents", balance as f64),
+            ("kalshi_bot_open_positions", position_mgr.position_count() as f64),
+            ("kalshi_bot_total_trades", st.total_trades as f64),
+            ("kalshi_bot_wins", st.wins as f64),
+            ("kalshi_bot_losses", st.losses as f64),
+            ("kalshi_bot_win_rate", st.win_rate),
+            ("kalshi_bot_total_pnl_cents", st.total_pnl_cents as f64),
+            ("kalshi_bot_today_pnl_cents", st.today_pnl_cents as f64),
+            ("kalshi_bot_current_streak", st.current_streak as f64),
+            ("kalshi_bot_max_drawdown_cents", st.max_drawdown_cents as f64),
+        ],
+        labelled,
+    };
+    sink.push(&sample).await
+}
+
+/// Resolve a resting entry order at its fill timeout. Its state is checked
+/// with the exchange first, since a WS fill event can be missed: anything
+/// still resting is canceled, and contracts that did fill are returned as a
+/// fill (the ledger row trimmed to them if the fill was partial).
+pub async fn cancel_unfilled(
+    exchange: &dyn Exchange,
+    storage: &dyn Storage,
+    order_id: &str,
+) -> Result<Option<FillEvent>> {
+    let state = exchange.get_order(order_id).await?;
+    if state.remaining > 0 && state.status == OrderStatus::Resting {
+        exchange.cancel_order(order_id).await?;
+    }
+    close_out_order(storage, state, "at fill timeout").await
+}
+
+/// The bot's own open entry on `ticker`, if any: a pending ledger row in a
+/// configured series. Positions the exchange reports without one aren't
+/// ours to exit.
+pub async fn own_entry(storage: &dyn Storage, config: &Config, ticker: &str) -> Result<Option<String>> {
+    if !config.series_tickers.iter().any(|s| s == ticker_series(ticker)) {
+        return Ok(None);
+    }
+    let rows = storage
+        .query_ledger(&QueryFilter {
+            ticker: Some(ticker.to_string()),
+            result: Some(ResultFilter::Pending),
+            ..Default::default()
+        })
+        .await?;
+    Ok(rows.into_iter().next_back().map(|r| r.order_id))
+}
+
+/// Settle the ledger row of a resting entry the exchange canceled or
+/// expired on its own (`user_orders` channel) — no REST lookup needed.
+pub async fn on_order_closed(storage: &dyn Storage, state: &OrderState) -> Result<Option<FillEvent>> {
+    close_out_order(storage, state.clone(), "by the exchange").await
+}
+
+/// Cancel the ledger row of an order that never filled, or trim it to the
+/// contracts that did and return them as a fill.
+async fn close_out_order(storage: &dyn Storage, state: OrderState, when: &str) -> Result<Option<FillEvent>> {
+    let order_id = state.order_id.as_str();
+    if state.filled == 0 {
+        storage.cancel_trade(order_id).await?;
+        tracing::info!("Canceled unfilled order {} {}", order_id, when);
+        return Ok(None);
+    }
+
+    if state.remaining > 0 {
+        let amendment = LedgerAmendment { shares: Some(state.filled), ..Default::default() };
+        storage.amend_ledger(order_id, &amendment).await?;
+        tracing::info!(
+            "Order {} partially filled ({}x, {} canceled) {}",
+            order_id, state.filled, state.remaining, when
+        );
+    } else {
+        tracing::warn!("Order {} filled without a WS fill event — opening position", order_id);
+    }
+    Ok(Some(FillEvent {
+        order_id: state.order_id,
+        ticker: state.ticker,
+        side: state.side,
+        shares: state.filled,
+        price_cents: state.price_cents,
+        exchange_ts_ms: None,
+    }))
+}
+
+/// Reprice a resting entry stuck deep in the queue: with more than
+/// `reprice_queue_ahead` contracts estimated ahead, amend it to one tick
+/// above the best bid — at most `reprice_max_cents` higher, and never onto
+/// the ask. Returns the order's id after the amend — Kalshi may replace
+/// it — if the order was amended.
+pub async fn reprice_stuck_entry(
+    exchange: &dyn Exchange,
+    storage: &dyn Storage,
+    order_id: &str,
+    config: &Config,
+) -> Result<Option<String>> {
+    let queue = exchange.queue_position(order_id).await?;
+    if queue.remaining == 0 || queue.ahead <= config.reprice_queue_ahead {
+        return Ok(None);
+    }
+    let mut price = queue
+        .best_bid
+        .map_or(queue.price_cents, |bid| bid + 1)
+        .min(queue.price_cents + config.reprice_max_cents);
+    if let Some(ask) = queue.best_ask {
+        price = price.min(ask.saturating_sub(1));
+    }
+    if price <= queue.price_cents {
+        tracing::info!(
+            "Order {} on {} has ~{} contracts ahead at {}¢ but no room to reprice",
+            order_id, queue.ticker, queue.ahead, queue.price_cents
+        );
+        return Ok(None);
+    }
+
+    // Amend counts cover the whole order, filled contracts included
+    let amended = exchange.amend_order(order_id, price, queue.filled + queue.remaining).await?;
+    let new_id = (amended.order_id != order_id).then(|| amended.order_id.clone());
+    let amendment = LedgerAmendment { price: Some(price), order_id: new_id, ..Default::default() };
+    storage.amend_ledger(order_id, &amendment).await?;
+    tracing::info!(
+        "Repriced order {} → {} on {} {}: {}¢ → {}¢ (~{} contracts were ahead)",
+        queue.order_id, amended.order_id, queue.ticker, queue.side, queue.price_cents, price, queue.ahead
+    );
+    Ok(Some(amended.order_id))
+}
+
+/// Cancel this instance's resting orders on shutdown: one batched cancel per
+/// series, then each order's ledger row is settled like a fill timeout
+/// (canceled, or cut to what filled). Returns the fills to log.
+pub async fn cancel_on_shutdown(
+    exchange: &dyn Exchange,
+    storage: &dyn Storage,
+    series_tickers: &[String],
+) -> Result<Vec<FillEvent>> {
+    let mut fills = Vec::new();
+    for series in series_tickers {
+        for order_id in exchange.cancel_all_orders(Some(series)).await? {
+            if let Some(fill) = cancel_unfilled(exchange, storage, &order_id).await? {
+                fills.push(fill);
+            }
+        }
+    }
+    Ok(fills)
+}
+
+/// Self-trade prevention: a sell on one side would match our own resting
+/// buys on that side and ticker first, so cancel them — settling their
+/// ledger rows like a fill timeout — before the exit goes out. Returns the
+/// contracts they filled before the cancel.
+async fn cancel_crossing_orders(
+    exchange: &dyn Exchange,
+    storage: &dyn Storage,
+    exit_order: &OrderRequest,
+) -> Result<Vec<FillEvent>> {
+    let crossing: Vec<String> = exchange
+        .resting_orders()
+        .await?
+        .into_iter()
+        .filter(|o| o.ticker == exit_order.ticker && o.side == exit_order.side && o.action == "buy")
+        .map(|o| o.order_id)
+        .collect();
+    let mut fills = Vec::new();
+    for order_id in crossing {
+        tracing::info!("Canceling own resting buy {} before exiting {}", order_id, exit_order.ticker);
+        if let Some(fill) = cancel_unfilled(exchange, storage, &order_id).await? {
+            fills.push(fill);
+        }
+    }
+    Ok(fills)
 }
 
 /// Execute an early exit (TP/SL sell) for a specific position by market ticker.
 pub async fn execute_exit(
     exchange: &dyn Exchange,
+    storage: &dyn Storage,
     position_mgr: &mut PositionManager,
     ticker: &str,
     reason: ExitReason,
     config: &Config,
+    shutdown: &CancellationToken,
 ) -> Result<()> {
-    let exit_event = match position_mgr.build_exit_event(ticker, reason.clone()) {
+    let mut exit_event = match position_mgr.build_exit_event(ticker, reason.clone()) {
         Some(e) => e,
         None => {
             tracing::warn!("Cannot build exit event for {} — no position or orderbook", ticker);
@@ -260,7 +1057,7 @@ pub async fn execute_exit(
         }
     };
 
-    let exit_order = match position_mgr.build_exit_order(ticker) {
+    let mut exit_order = match position_mgr.build_exit_order(ticker, &reason) {
         Some(o) => o,
         None => {
             tracing::warn!("Cannot build exit order for {} — no position or orderbook", ticker);
@@ -269,18 +1066,78 @@ pub async fn execute_exit(
     };
 
     tracing::info!(
-        "EXIT {}: {:?} {}x | entry={}¢ exit={}¢ pnl={}¢ on {}",
-        reason, exit_order.side, exit_order.shares,
+        "EXIT {} ({}): {:?} {}x | entry={}¢ exit={}¢ pnl={}¢ on {}",
+        reason, exit_order.order_type, exit_order.side, exit_order.shares,
         exit_event.entry_price_cents, exit_event.exit_price_cents,
         exit_event.pnl_cents, ticker
     );
 
+    if shutdown.is_cancelled() {
+        return Err(ShutdownRequested.into());
+    }
+
     if config.paper_trade {
         tracing::info!("PAPER EXIT: {} on {}", reason, ticker);
     } else {
+        let fills = cancel_crossing_orders(exchange, storage, &exit_order).await?;
+        if !fills.is_empty() {
+            // Buys that filled before the cancel grew the position: size the exit to it
+            for fill in &fills {
+                position_mgr.on_fill(fill);
+            }
+            match (position_mgr.build_exit_event(ticker, reason.clone()), position_mgr.build_exit_order(ticker, &reason)) {
+                (Some(event), Some(order)) => {
+                    tracing::info!("Exit on {} resized to {}x after canceling own buys", ticker, order.shares);
+                    exit_event = event;
+                    exit_order = order;
+                }
+                _ => {
+                    tracing::warn!("Cannot rebuild exit for {} after canceling own buys", ticker);
+                    return Ok(());
+                }
+            }
+        }
         match exchange.sell_order(&exit_order).await {
+            Ok(result) if result.order.status == OrderStatus::Rejected => {
+                return Err(OrderRejected {
+                    order_id: result.order.order_id,
+                    ticker: ticker.to_string(),
+                }
+                .into());
+            }
             Ok(result) => {
-                tracing::info!("Sell order placed: {} status={}", result.order_id, result.status);
+                tracing::info!(
+                    "Sell order placed: {} status={} | held={} remaining={}",
+                    result.order.order_id, result.order.status, result.held, result.remaining
+                );
+                // Clamped to what the exchange holds: book the P&L on that
+                let sold = result.held - result.remaining;
+                if sold < exit_event.shares {
+                    let per_share = exit_event.exit_price_cents as i64 - exit_event.entry_price_cents as i64;
+                    exit_event.shares = sold;
+                    exit_event.pnl_cents = per_share * sold as i64;
+                }
+            }
+            Err(e) if e.is::<SellExceedsPosition>() => {
+                // Nothing held on that side: take the exchange's view before the next tick
+                tracing::warn!("{} — resyncing {} from the exchange", e, ticker);
+                let position = exchange
+                    .positions()
+                    .await?
+                    .into_iter()
+                    .find(|p| p.ticker == ticker)
+                    .map_or(0, |p| match p.side {
+                        Side::Yes => p.count as i64,
+                        Side::No => -(p.count as i64),
+                    });
+                let update = PositionUpdate {
+                    ticker: ticker.to_string(),
+                    position,
+                    avg_price_cents: None,
+                    exchange_ts_ms: None,
+                };
+                position_mgr.on_position_update(&update, None);
+                return Ok(());
             }
             Err(e) => {
                 tracing::error!("Sell order failed on {}: {}", ticker, e);
@@ -289,40 +1146,96 @@ pub async fn execute_exit(
         }
     }
 
-    if let Err(e) = storage::record_early_exit(&exit_event) {
+    if let Err(e) = storage.record_early_exit(&exit_event).await {
         tracing::error!("Failed to record early exit in ledger: {}", e);
     }
 
-    let ledger = storage::read_ledger()?;
+    let ledger = storage.read_ledger().await?;
     let updated_stats = stats::compute(&ledger);
-    storage::write_stats(&updated_stats)?;
+    storage.write_stats(&updated_stats).await?;
+    storage.append_stats_history(ticker, &updated_stats).await?;
 
     position_mgr.clear_position(ticker);
     Ok(())
 }
 
-async fn fetch_crypto_price(price_feed: &dyn PriceFeed, symbol: &str) -> Option<PriceSnapshot> {
-    let (candles_1m, candles_5m, spot) = tokio::join!(
-        price_feed.candles(symbol, "1m", 15),
-        price_feed.candles(symbol, "5m", 12),
+/// Await `fut` unless shutdown is requested first. Only for awaits before
+/// order placement — dropping a future mid-flight there loses nothing.
+async fn until_shutdown<T>(
+    shutdown: &CancellationToken,
+    fut: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    tokio::select! {
+        biased;
+        _ = shutdown.cancelled() => Err(ShutdownRequested.into()),
+        r = fut => r,
+    }
+}
+
+async fn fetch_implied_vol(vol_feed: Option<&dyn VolFeed>, symbol: &str) -> Option<f64> {
+    match vol_feed?.implied_vol(symbol).await {
+        Ok(iv) => iv,
+        Err(e) => {
+            tracing::warn!("Implied vol for {} unavailable: {}", symbol, e);
+            None
+        }
+    }
+}
+
+/// The price snapshot, or None when the feed had nothing (the brain then
+/// decides without it). Errs only when the feed reports itself down.
+async fn fetch_crypto_price(
+    price_feed: &dyn PriceFeed,
+    spot_cache: &SpotCache,
+    symbol: &str,
+    windows: CandleWindows,
+) -> Result<Option<PriceSnapshot>> {
+    let (candles_1m, candles_5m, spot, perp) = tokio::join!(
+        price_feed.candles(symbol, &windows.short_interval, windows.short_limit),
+        price_feed.candles(symbol, &windows.long_interval, windows.long_limit),
         price_feed.spot_price(symbol),
+        price_feed.perp(symbol),
     );
 
-    let candles_1m = candles_1m.ok().flatten()?;
-    let candles_5m = candles_5m.ok().flatten()?;
-    let spot = spot.ok().flatten()?;
+    // A blip leaves the snapshot out; an outage skips the cycle
+    for err in [candles_1m.as_ref().err(), candles_5m.as_ref().err(), spot.as_ref().err()].into_iter().flatten() {
+        if PriceFeedError::is_down(err) {
+            anyhow::bail!("{}", err);
+        }
+        tracing::warn!("Price feed for {} failed: {}", symbol, err);
+    }
+    let (Ok(Some(candles_1m)), Ok(Some(candles_5m)), Ok(Some(spot))) = (candles_1m, candles_5m, spot) else {
+        return Ok(None);
+    };
 
     if candles_1m.is_empty() {
-        tracing::warn!("Binance returned empty 1m candles for {}", symbol);
-        return None;
+        tracing::warn!("Binance returned empty {} candles for {}", windows.short_interval, symbol);
+        return Ok(None);
     }
 
-    let ind = indicators::compute(&candles_1m, &candles_5m, spot);
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let ind = indicators::compute(
+        &candles_1m, &candles_5m, spot,
+        price_feed.realized_vol(symbol), spot_cache.depth_imbalance(symbol, now_ms), spot_cache.order_flow(symbol, now_ms),
+    );
 
-    Some(PriceSnapshot {
+    // Missing funding data doesn't hold up the snapshot
+    let perp = perp.unwrap_or_else(|e| {
+        tracing::warn!("Perp quote for {} failed: {}", symbol, e);
+        None
+    });
+    let basis_pct = perp
+        .as_ref()
+        .filter(|_| spot > 0.0)
+        .map(|p| (p.mark_price - spot) / spot * 100.0);
+
+    Ok(Some(PriceSnapshot {
         candles_1m,
         candles_5m,
+        candle_windows: windows,
         spot_price: spot,
         indicators: ind,
-    })
+        perp,
+        basis_pct,
+    }))
 }
@@ -0,0 +1,51 @@
+use crate::core::types::{Config, QueuedEntry};
+use chrono::{DateTime, Utc};
+
+/// BUY signals held back by the global open-position cap, oldest first.
+/// One entry per series: a newer signal replaces the older one. Pure — no IO.
+#[derive(Debug)]
+pub struct EntryQueue {
+    max_open: usize,
+    pending: Vec<QueuedEntry>,
+}
+
+impl EntryQueue {
+    pub fn new(config: &Config) -> Self {
+        Self { max_open: config.max_open_positions, pending: Vec::new() }
+    }
+
+    /// Whether `open` positions and resting entries fill every slot.
+    pub fn at_capacity(&self, open: usize) -> bool {
+        self.max_open > 0 && open >= self.max_open
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn push(&mut self, entry: QueuedEntry) {
+        self.pending.retain(|e| e.series != entry.series);
+        self.pending.push(entry);
+    }
+
+    /// Drop expired entries and return the oldest one still valid at `now`
+    /// that `ready` accepts; the others stay queued.
+    pub fn pop(&mut self, now: DateTime<Utc>, ready: impl Fn(&QueuedEntry) -> bool) -> Option<QueuedEntry> {
+        self.pending.retain(|e| {
+            let live = e.expires > now;
+            if !live {
+                tracing::info!(
+                    "[{}] Queued {} entry on {} expired",
+                    e.series, e.side, e.context.market.ticker
+                );
+            }
+            live
+        });
+        let i = self.pending.iter().position(ready)?;
+        Some(self.pending.remove(i))
+    }
+}
@@ -0,0 +1,238 @@
+use crate::core::types::*;
+use std::collections::HashMap;
+
+/// Gaps longer than this between two book observations (reconnects, quiet
+/// markets) are not counted as observed time.
+const MAX_OBSERVATION_GAP_MS: i64 = 60_000;
+
+/// Minimum observed book time per series before the model is trusted.
+const MIN_OBSERVED_SECS: f64 = 120.0;
+
+/// Per-series queue dynamics learned from WS orderbook updates.
+#[derive(Debug, Default, Clone)]
+struct QueueStats {
+    /// Contracts removed from the front of the bid queue (fills + cancels).
+    depleted_contracts: u64,
+    /// Number of distinct depletion events.
+    depletion_events: u64,
+    observed_ms: i64,
+}
+
+#[derive(Debug, Clone)]
+struct LastBook {
+    at_ms: i64,
+    yes_best: Option<(u32, u32)>,
+    no_best: Option<(u32, u32)>,
+}
+
+/// Estimates how likely a passive (maker) entry is to fill before the
+/// decision thesis decays, from historical book churn per series.
+#[derive(Debug)]
+pub struct FillModel {
+    stats: HashMap<String, QueueStats>,
+    last_books: HashMap<String, LastBook>,
+    min_fill_probability: f64,
+    horizon_secs: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryStyle {
+    /// Rest at the bid and wait for the queue to clear.
+    Maker,
+    /// Cross the spread at the ask.
+    Taker,
+}
+
+impl std::fmt::Display for EntryStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntryStyle::Maker => write!(f, "maker"),
+            EntryStyle::Taker => write!(f, "taker"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EntryPlan {
+    pub style: EntryStyle,
+    pub price_cents: u32,
+    /// Estimated fill probability for a maker entry, if the model had data.
+    pub fill_probability: Option<f64>,
+}
+
+impl FillModel {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            stats: HashMap::new(),
+            last_books: HashMap::new(),
+            min_fill_probability: config.min_fill_probability,
+            horizon_secs: config.maker_horizon_secs as f64,
+        }
+    }
+
+    /// Record an orderbook update stamped `now_ms` (unix millis, exchange
+    /// time when available). Updates older than the last one seen for the
+    /// ticker are backlog from a reconnect and are skipped.
+    pub fn on_orderbook_update(&mut self, update: &OrderbookUpdate, now_ms: i64) {
+        // Delta frames carry no levels — nothing to compare against.
+        if update.yes.is_empty() && update.no.is_empty() {
+            return;
+        }
+
+        let book = LastBook {
+            at_ms: now_ms,
+            yes_best: best_level(&update.yes),
+            no_best: best_level(&update.no),
+        };
+
+        if let Some(prev) = self.last_books.get(&update.ticker) {
+            if now_ms < prev.at_ms {
+                return;
+            }
+            let series = ticker_series(&update.ticker).to_string();
+            let stats = self.stats.entry(series).or_default();
+
+            let gap = now_ms - prev.at_ms;
+            if gap > 0 && gap <= MAX_OBSERVATION_GAP_MS {
+                stats.observed_ms += gap;
+            }
+
+            for (before, after) in [(prev.yes_best, book.yes_best), (prev.no_best, book.no_best)] {
+                let depleted = front_depletion(before, after);
+                if depleted > 0 {
+                    stats.depleted_contracts += depleted as u64;
+                    stats.depletion_events += 1;
+                }
+            }
+        }
+
+        self.last_books.insert(update.ticker.clone(), book);
+    }
+
+    /// Forget the last book for a ticker (e.g. after unsubscribing) so the
+    /// next snapshot isn't diffed against stale state.
+    pub fn forget_ticker(&mut self, ticker: &str) {
+        self.last_books.remove(ticker);
+    }
+
+    /// Probability that `queue_ahead + shares` contracts are depleted from the
+    /// front of the queue within `horizon_secs`. None without enough history.
+    ///
+    /// Depletion events arrive as a Poisson process at the series' observed
+    /// rate, each removing the observed average size.
+    pub fn fill_probability(
+        &self,
+        series: &str,
+        queue_ahead: u32,
+        shares: u32,
+        horizon_secs: f64,
+    ) -> Option<f64> {
+        let stats = self.stats.get(series)?;
+        let observed_secs = stats.observed_ms as f64 / 1000.0;
+        if observed_secs < MIN_OBSERVED_SECS {
+            return None;
+        }
+        if stats.depletion_events == 0 {
+            return Some(0.0);
+        }
+
+        let event_rate = stats.depletion_events as f64 / observed_secs;
+        let avg_size = stats.depleted_contracts as f64 / stats.depletion_events as f64;
+        let needed = (queue_ahead + shares) as f64;
+        let events_needed = (needed / avg_size).ceil().max(1.0) as u32;
+        let mu = event_rate * horizon_secs;
+
+        Some(1.0 - poisson_cdf(events_needed - 1, mu))
+    }
+
+    /// Pick maker vs taker for one entry.
+    ///
+    /// `win_prob` is the probability (0–100) that `side` wins; `max_price` is
+    /// the highest price we are willing to pay. Maker is chosen when its
+    /// fill-weighted edge beats crossing the spread and its fill probability
+    /// clears the configured minimum. Returns None if neither is viable.
+    pub fn plan_entry(
+        &self,
+        series: &str,
+        orderbook: &Orderbook,
+        side: &Side,
+        shares: u32,
+        win_prob: f64,
+        max_price: u32,
+    ) -> Option<EntryPlan> {
+        let (own, opposite) = match side {
+            Side::Yes => (&orderbook.yes, &orderbook.no),
+            Side::No => (&orderbook.no, &orderbook.yes),
+        };
+
+        let ask = best_level(opposite).map(|(p, _)| 100 - p);
+        let taker_price = ask.filter(|a| *a <= max_price);
+
+        let maker_price = best_level(own)
+            .map(|(p, _)| p.min(max_price))
+            .unwrap_or(max_price)
+            // Never rest at a price that would cross.
+            .min(ask.map(|a| a.saturating_sub(1)).unwrap_or(99))
+            .max(1);
+        let queue_ahead: u32 = own
+            .iter()
+            .filter(|(p, _)| *p >= maker_price)
+            .map(|(_, q)| *q)
+            .sum();
+
+        let fill_probability =
+            self.fill_probability(series, queue_ahead, shares, self.horizon_secs);
+
+        let Some(p_fill) = fill_probability else {
+            // No history — keep the old behavior: limit at the decision price.
+            return Some(EntryPlan {
+                style: if taker_price.is_some() { EntryStyle::Taker } else { EntryStyle::Maker },
+                price_cents: max_price,
+                fill_probability: None,
+            });
+        };
+
+        let maker_ok = p_fill >= self.min_fill_probability;
+        let maker_ev = p_fill * (win_prob - maker_price as f64);
+
+        match taker_price {
+            Some(tp) => {
+                let taker_ev = win_prob - tp as f64;
+                if maker_ok && maker_ev > taker_ev {
+                    Some(EntryPlan { style: EntryStyle::Maker, price_cents: maker_price, fill_probability })
+                } else {
+                    Some(EntryPlan { style: EntryStyle::Taker, price_cents: tp, fill_probability })
+                }
+            }
+            None if maker_ok => {
+                Some(EntryPlan { style: EntryStyle::Maker, price_cents: maker_price, fill_probability })
+            }
+            None => None,
+        }
+    }
+}
+
+fn best_level(levels: &[(u32, u32)]) -> Option<(u32, u32)> {
+    levels.iter().copied().max_by_key(|(price, _)| *price)
+}
+
+/// Contracts removed from the front of the queue between two observations of
+/// the best bid. A higher new best bid is a new queue, not depletion.
+fn front_depletion(before: Option<(u32, u32)>, after: Option<(u32, u32)>) -> u32 {
+    match (before, after) {
+        (Some((p0, q0)), Some((p1, q1))) if p1 == p0 => q0.saturating_sub(q1),
+        (Some((p0, q0)), Some((p1, _))) if p1 < p0 => q0,
+        (Some((_, q0)), None) => q0,
+        _ => 0,
+    }
+}
+
+fn poisson_cdf(k: u32, mu: f64) -> f64 {
+    let mut term = (-mu).exp();
+    let mut sum = term;
+    for i in 1..=k {
+        term *= mu / i as f64;
+        sum += term;
+    }
+    sum.min(1.0)
+}
@@ -75,6 +75,33 @@ pub fn compute_orderbook_imbalance(orderbook: &Orderbook) -> f64 {
     }
 }
 
+/// Standard normal CDF (Abramowitz–Stegun 7.1.26, error < 1.5e-7).
+fn norm_cdf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs() / std::f64::consts::SQRT_2);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-(x * x) / 2.0).exp();
+    if x >= 0.0 { (1.0 + erf) / 2.0 } else { (1.0 - erf) / 2.0 }
+}
+
+/// P(YES) in percent that spot finishes on the YES side of `strike` (above,
+/// below, or inside the range) in `minutes`, for a driftless lognormal move
+/// at annualized implied vol `iv_pct`. None without a strike, a vol, or time left.
+pub fn iv_probability_yes(spot: f64, strike: Option<StrikeKind>, iv_pct: Option<f64>, minutes: f64) -> Option<f64> {
+    let iv = iv_pct.filter(|v| *v > 0.0)? / 100.0;
+    if spot <= 0.0 || minutes <= 0.0 {
+        return None;
+    }
+    let sigma = iv * (minutes / (365.0 * 24.0 * 60.0)).sqrt();
+    // P(finish above k)
+    let above = |k: f64| (k > 0.0).then(|| norm_cdf((spot / k).ln() / sigma));
+    let p = match strike? {
+        StrikeKind::Above(k) => above(k)?,
+        StrikeKind::Below(k) => 1.0 - above(k)?,
+        StrikeKind::Between(floor, cap) => (above(floor)? - above(cap)?).max(0.0),
+    };
+    Some(p * 100.0)
+}
+
 /// Check if 5m, 15m, and 1h trends all agree.
 pub fn compute_trend_alignment(pct_5m: f64, pct_15m: f64, pct_1h: f64) -> TrendAlignment {
     let threshold = 0.05;
@@ -99,16 +126,104 @@ pub fn compute_trend_alignment(pct_5m: f64, pct_15m: f64, pct_1h: f64) -> TrendA
     }
 }
 
+/// Probability inputs shared by every strategy's summary.
+struct Scored {
+    prob_yes: f64,
+    trend: TrendAlignment,
+    rsi: f64,
+    rsi_signal: String,
+    ema_diff_pct: f64,
+    imbalance: f64,
+    /// Strategy-specific detail appended to the narrative.
+    detail: String,
+}
+
 /// Master signal summary function.
-/// Builds a probability estimate from all indicators, computes edge, picks side,
-/// computes half-Kelly shares, and generates a narrative for the LLM.
+/// Builds a probability estimate with the series' strategy, computes edge,
+/// picks side, computes half-Kelly shares, and generates a narrative for the LLM.
 pub fn compute_signal_summary(
+    strategy: Strategy,
     indicators: &PriceIndicators,
     orderbook: &Orderbook,
     market: &MarketState,
+    trade_flow: &TradeFlow,
+    implied_vol: Option<f64>,
 ) -> SignalSummary {
-    // Start at 50% base probability for YES
-    let mut prob_yes: f64 = 50.0;
+    // With implied vol, strategies adjust from the chance the expected move
+    // covers the strike distance rather than from a coin flip
+    let iv_base = iv_probability_yes(indicators.spot_price, market.strike_kind, implied_vol, market.minutes_to_expiry);
+    let base = iv_base.unwrap_or(50.0);
+    let scored = match strategy {
+        Strategy::Momentum => score_momentum(indicators, orderbook, base),
+        Strategy::MeanReversion => score_mean_reversion(indicators, orderbook, market, base),
+    };
+    let mut summary = summarize(strategy, scored, market);
+    if let (Some(iv), Some(p)) = (implied_vol, iv_base) {
+        summary.narrative.push_str(&format!(" | IV {:.0}% → base P(YES) {:.0}%", iv, p));
+    }
+    if let Some(depth) = indicators.depth_imbalance {
+        summary.narrative.push_str(&format!(" | Spot depth imbalance: {:.2}", depth));
+    }
+    if let Some(flow) = &indicators.order_flow {
+        summary.narrative.push_str(&format!(
+            " | Spot flow {}s: {:+.2} ({:+.4} delta)", flow.window_secs, flow.imbalance(), flow.delta()
+        ));
+    }
+    // Reported alongside the score, not part of it
+    summary.trade_flow = trade_flow.imbalance();
+    if let Some(flow) = summary.trade_flow {
+        summary.narrative.push_str(&format!(
+            " | Tape flow: {:+.2} ({} trades, {} YES / {} NO taker)",
+            flow, trade_flow.trades, trade_flow.yes_taker_contracts, trade_flow.no_taker_contracts
+        ));
+    }
+    summary
+}
+
+/// Change in the contract's implied P(YES), in points, over the last
+/// `minutes` of `candles` (oldest first). None without two priced candles
+/// that far apart.
+pub fn implied_probability_change(candles: &[ContractCandle], minutes: i64) -> Option<f64> {
+    let last = candles.iter().rev().find(|c| c.implied_probability().is_some())?;
+    let cutoff = last.end_ts - minutes * 60;
+    let first = candles
+        .iter()
+        .rev()
+        .find(|c| c.end_ts <= cutoff && c.implied_probability().is_some())?;
+    Some(last.implied_probability()? - first.implied_probability()?)
+}
+
+fn ema_gap_pct(indicators: &PriceIndicators) -> f64 {
+    if indicators.ema_9 > 0.0 {
+        ((indicators.spot_price - indicators.ema_9) / indicators.ema_9) * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Spot-book lean: a nudge in the same direction as the Kalshi book's, but
+/// smaller, since a deep spot book moves the 15m outcome less directly.
+fn depth_adjustment(depth_imbalance: Option<f64>) -> f64 {
+    match depth_imbalance {
+        Some(d) if d > 2.0 => 2.0,
+        Some(d) if d < 0.5 => -2.0,
+        _ => 0.0,
+    }
+}
+
+fn imbalance_adjustment(imbalance: f64) -> f64 {
+    if imbalance > 2.0 {
+        3.0 // heavy yes-side buying
+    } else if imbalance < 0.5 {
+        -3.0 // heavy no-side buying
+    } else {
+        0.0
+    }
+}
+
+fn score_momentum(indicators: &PriceIndicators, orderbook: &Orderbook, base: f64) -> Scored {
+    // Start from the base probability for YES (50% without implied vol)
+    let mut prob_yes: f64 = base;
 
     // Momentum adjustment (±0.15% threshold, raised from ±0.05%)
     if indicators.pct_change_15m > 0.15 {
@@ -134,11 +249,7 @@ pub fn compute_signal_summary(
     }
 
     // EMA alignment
-    let ema_diff_pct = if indicators.ema_9 > 0.0 {
-        ((indicators.spot_price - indicators.ema_9) / indicators.ema_9) * 100.0
-    } else {
-        0.0
-    };
+    let ema_diff_pct = ema_gap_pct(indicators);
     if ema_diff_pct > 0.05 {
         prob_yes += 3.0;
     } else if ema_diff_pct < -0.05 {
@@ -157,16 +268,91 @@ pub fn compute_signal_summary(
         "NEUTRAL".to_string()
     };
 
-    // Orderbook imbalance
+    // Orderbook imbalance, Kalshi and spot
     let imbalance = compute_orderbook_imbalance(orderbook);
-    if imbalance > 2.0 {
-        prob_yes += 3.0; // heavy yes-side buying
-    } else if imbalance < 0.5 {
-        prob_yes -= 3.0; // heavy no-side buying
+    prob_yes += imbalance_adjustment(imbalance);
+    prob_yes += depth_adjustment(indicators.depth_imbalance);
+
+    Scored { prob_yes, trend, rsi, rsi_signal, ema_diff_pct, imbalance, detail: String::new() }
+}
+
+/// Fade a stretched 15-minute move. Only an extreme move confirmed by RSI
+/// counts, and the fade is sized by how close spot is to the strike: a
+/// pullback only changes the outcome if it can cross the strike.
+fn score_mean_reversion(
+    indicators: &PriceIndicators,
+    orderbook: &Orderbook,
+    market: &MarketState,
+    base: f64,
+) -> Scored {
+    let mut prob_yes: f64 = base;
+    let move_15m = indicators.pct_change_15m;
+    let rsi = indicators.rsi_9;
+
+    // Direction of the fade: -1 bets on a pullback from a rally, +1 on a bounce.
+    let (fade, rsi_signal) = if move_15m > 0.30 && rsi > 70.0 {
+        prob_yes -= 10.0;
+        (-1.0, "OVERBOUGHT (>70) — fade".to_string())
+    } else if move_15m < -0.30 && rsi < 30.0 {
+        prob_yes += 10.0;
+        (1.0, "OVERSOLD (<30) — fade".to_string())
+    } else if move_15m > 0.15 && rsi > 65.0 {
+        prob_yes -= 4.0;
+        (-1.0, "STRETCHED (>65)".to_string())
+    } else if move_15m < -0.15 && rsi < 35.0 {
+        prob_yes += 4.0;
+        (1.0, "STRETCHED (<35)".to_string())
+    } else {
+        (0.0, "NEUTRAL".to_string())
+    };
+
+    // Distance to strike: a fade is worth most when spot sits just past the
+    // strike, and little once the move has carried it far beyond.
+    let strike_gap_pct = market
+        .strike
+        .filter(|k| *k > 0.0)
+        .map(|k| (indicators.spot_price - k) / k * 100.0);
+    if let Some(gap) = strike_gap_pct {
+        if fade != 0.0 {
+            if gap.abs() <= 0.10 {
+                prob_yes += fade * 6.0;
+            } else if gap.abs() > 0.30 {
+                prob_yes -= fade * 6.0;
+            }
+        }
+    }
+
+    // Price stretched away from EMA(9) reinforces the fade
+    let ema_diff_pct = ema_gap_pct(indicators);
+    if fade < 0.0 && ema_diff_pct > 0.10 {
+        prob_yes -= 3.0;
+    } else if fade > 0.0 && ema_diff_pct < -0.10 {
+        prob_yes += 3.0;
     }
 
+    let trend = compute_trend_alignment(
+        indicators.pct_change_5m,
+        indicators.pct_change_15m,
+        indicators.pct_change_1h,
+    );
+
+    let imbalance = compute_orderbook_imbalance(orderbook);
+    prob_yes += imbalance_adjustment(imbalance);
+    prob_yes += depth_adjustment(indicators.depth_imbalance);
+
+    let detail = match strike_gap_pct {
+        Some(gap) => format!(" | 15m move: {:+.3}% | Strike gap: {:+.3}%", move_15m, gap),
+        None => format!(" | 15m move: {:+.3}% | Strike gap: n/a", move_15m),
+    };
+    Scored { prob_yes, trend, rsi, rsi_signal, ema_diff_pct, imbalance, detail }
+}
+
+/// Edge, side, Kelly sizing, and narrative from a strategy's probability.
+fn summarize(strategy: Strategy, scored: Scored, market: &MarketState) -> SignalSummary {
+    let Scored { prob_yes, trend, rsi, rsi_signal, ema_diff_pct, imbalance, detail } = scored;
+
     // Clamp to [5, 95]
-    prob_yes = prob_yes.clamp(5.0, 95.0);
+    let prob_yes = prob_yes.clamp(5.0, 95.0);
 
     // Compute edge vs market price for both sides
     let yes_ask = market.yes_ask.unwrap_or(99) as f64;
@@ -210,13 +396,14 @@ pub fn compute_signal_summary(
         None => "NONE",
     };
     let narrative = format!(
-        "Trend: {} | RSI(9): {:.1} ({}) | EMA(9) gap: {:+.3}% | OB imbalance: {:.2} | \
+        "Strategy: {} | Trend: {} | RSI(9): {:.1} ({}) | EMA(9) gap: {:+.3}% | OB imbalance: {:.2}{} | \
          Est. prob YES: {:.0}% | Best side: {} edge {:.1}pt | Kelly: {} shares",
-        trend, rsi, rsi_signal, ema_diff_pct, imbalance,
+        strategy, trend, rsi, rsi_signal, ema_diff_pct, imbalance, detail,
         prob_yes, side_label, best_edge, kelly_shares
     );
 
     SignalSummary {
+        strategy,
         trend,
         rsi_signal,
         orderbook_imbalance: imbalance,
@@ -225,10 +412,25 @@ pub fn compute_signal_summary(
         kelly_shares,
         estimated_probability: prob_yes,
         narrative,
+        trade_flow: None,
     }
 }
 
-pub fn compute(candles_1m: &[Candle], candles_5m: &[Candle], spot: f64) -> PriceIndicators {
+/// Indicators from the series' short (`candles_1m`) and long (`candles_5m`)
+/// candle windows. The `_15m`/`_1h`/`_5m` names match the default windows
+/// (1m x 15, 5m x 12); with others they cover the short span, the long span,
+/// and the last long candle. Nothing is rescaled to the window: the scoring
+/// thresholds (±0.15%/±0.30% moves) stay tuned for the default spans, and
+/// `volatility_1m` is the std-dev of returns per short candle, whatever its
+/// interval.
+pub fn compute(
+    candles_1m: &[Candle],
+    candles_5m: &[Candle],
+    spot: f64,
+    realized_vol_5s: Option<f64>,
+    depth_imbalance: Option<f64>,
+    order_flow: Option<OrderFlow>,
+) -> PriceIndicators {
     let pct_change_15m = if !candles_1m.is_empty() {
         let first_open = candles_1m.first().unwrap().open;
         ((spot - first_open) / first_open) * 100.0
@@ -328,5 +530,8 @@ pub fn compute(candles_1m: &[Candle], candles_5m: &[Candle], spot: f64) -> Price
         rsi_9,
         ema_9,
         price_vs_ema,
+        realized_vol_5s,
+        depth_imbalance,
+        order_flow,
     }
 }
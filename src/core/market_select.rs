@@ -0,0 +1,76 @@
+use crate::core::types::*;
+
+/// Pick the market to trade from a series' open markets (soonest expiry
+/// first), applying the configured pins and exclusions. Pure — no IO.
+pub fn select(
+    candidates: Vec<MarketState>,
+    series: &str,
+    overrides: &MarketOverrides,
+) -> Option<MarketState> {
+    let pinned: Vec<&String> = overrides
+        .pinned
+        .iter()
+        .filter(|t| ticker_series(t) == series)
+        .collect();
+
+    let top_strikes: Vec<(String, f64)> = if overrides.exclude_top_strike {
+        top_strike_per_event(&candidates)
+    } else {
+        Vec::new()
+    };
+
+    candidates.into_iter().find(|m| {
+        if !pinned.is_empty() && !pinned.contains(&&m.ticker) {
+            return false;
+        }
+        if let Some(reason) = exclusion(m, overrides, &top_strikes) {
+            tracing::info!("Market {} excluded: {}", m.ticker, reason);
+            return false;
+        }
+        true
+    })
+}
+
+fn exclusion(
+    market: &MarketState,
+    overrides: &MarketOverrides,
+    top_strikes: &[(String, f64)],
+) -> Option<String> {
+    if let Some(prefix) = overrides.excluded.iter().find(|p| market.ticker.starts_with(p.as_str())) {
+        return Some(format!("matches {}", prefix));
+    }
+
+    let event = ticker_event(&market.ticker);
+    let date = event.split_once('-').map(|(_, d)| d).unwrap_or("");
+    if let Some(day) = overrides.excluded_dates.iter().find(|d| date.starts_with(d.as_str())) {
+        return Some(format!("date {} excluded", day));
+    }
+
+    let is_top = top_strikes
+        .iter()
+        .any(|(e, top)| e == event && market.strike == Some(*top));
+    if is_top {
+        return Some("top strike of event".into());
+    }
+    None
+}
+
+/// Highest strike per event, for events listing more than one strike.
+fn top_strike_per_event(markets: &[MarketState]) -> Vec<(String, f64)> {
+    let mut tops: Vec<(String, f64, usize)> = Vec::new();
+    for m in markets {
+        let Some(strike) = m.strike else { continue };
+        let event = ticker_event(&m.ticker);
+        match tops.iter_mut().find(|(e, _, _)| e == event) {
+            Some((_, top, count)) => {
+                *top = top.max(strike);
+                *count += 1;
+            }
+            None => tops.push((event.to_string(), strike, 1)),
+        }
+    }
+    tops.into_iter()
+        .filter(|(_, _, count)| *count > 1)
+        .map(|(e, top, _)| (e, top))
+        .collect()
+}
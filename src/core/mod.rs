@@ -1,6 +1,22 @@
+pub mod book_cache;
+pub mod candle_builder;
+pub mod depth_imbalance;
 pub mod engine;
+pub mod entry_queue;
+pub mod fill_model;
 pub mod indicators;
+pub mod logistic;
+pub mod market_select;
 pub mod position_manager;
+pub mod price_aggregate;
+pub mod price_failover;
+pub mod price_ticks;
+pub mod realized_vol;
 pub mod risk;
+pub mod rollover;
+pub mod routing;
+pub mod settlement;
+pub mod spot_cache;
+pub mod spot_flow;
 pub mod stats;
 pub mod types;
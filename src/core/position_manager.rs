@@ -8,6 +8,7 @@ pub struct PositionManager {
     orderbooks: HashMap<String, OrderbookUpdate>,
     tp_cents: u32,
     sl_cents: u32,
+    market_exit_spread_cents: u32,
 }
 
 impl PositionManager {
@@ -17,11 +18,17 @@ impl PositionManager {
             orderbooks: HashMap::new(),
             tp_cents: config.tp_cents_per_share,
             sl_cents: config.sl_cents_per_share,
+            market_exit_spread_cents: config.market_exit_spread_cents,
         }
     }
 
+    /// Positions the bot manages; track-only ones don't use its capacity.
     pub fn position_count(&self) -> usize {
-        self.positions.len()
+        self.positions.values().filter(|p| !p.track_only).count()
+    }
+
+    pub fn holds(&self, ticker: &str) -> bool {
+        self.positions.contains_key(ticker)
     }
 
     /// Check if we hold any position whose market ticker starts with the given series.
@@ -39,6 +46,22 @@ impl PositionManager {
         self.positions.iter()
     }
 
+    /// Open positions as a snapshot for handoff.
+    pub fn snapshot(&self) -> Vec<OpenPosition> {
+        self.all_positions().map(|(_, p)| p.clone()).collect()
+    }
+
+    /// Restore positions handed over from a previous instance.
+    pub fn restore(&mut self, positions: Vec<OpenPosition>) {
+        for pos in positions {
+            tracing::info!(
+                "Position restored: {:?} {}x @ {}¢ on {}",
+                pos.side, pos.shares, pos.entry_price_cents, pos.ticker
+            );
+            self.positions.insert(pos.ticker.clone(), pos);
+        }
+    }
+
     /// All market tickers with open positions.
     pub fn position_tickers(&self) -> Vec<String> {
         self.positions.keys().cloned().collect()
@@ -51,7 +74,12 @@ impl PositionManager {
             shares: fill.shares,
             entry_price_cents: fill.price_cents,
             order_id: fill.order_id.clone(),
-            entered_at: chrono::Utc::now().to_rfc3339(),
+            // Exchange fill time, so backlogged fills after a reconnect keep their real time
+            entered_at: fill
+                .exchange_ts_ms
+                .and_then(millis_to_rfc3339)
+                .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+            track_only: false,
         };
         tracing::info!(
             "Position opened: {:?} {}x @ {}¢ on {} [{} total positions]",
@@ -61,10 +89,82 @@ impl PositionManager {
         self.positions.insert(fill.ticker.clone(), pos);
     }
 
+    /// Reconcile with the exchange's view of a position: resize or flip a
+    /// held one, drop one closed elsewhere, and pick up one not held yet.
+    /// `entry_order_id` is the bot's own entry for the ticker (from the
+    /// ledger): with it the position is managed, without it (manual trade,
+    /// other series, another instance) it's track-only and never exited.
+    /// Returns true when a position was picked up.
+    pub fn on_position_update(&mut self, update: &PositionUpdate, entry_order_id: Option<&str>) -> bool {
+        let side = if update.position > 0 { Side::Yes } else { Side::No };
+        let shares = update.position.unsigned_abs() as u32;
+        match self.positions.get_mut(&update.ticker) {
+            None if shares == 0 => false,
+            Some(_) if shares == 0 => {
+                tracing::info!("Position on {} closed on the exchange", update.ticker);
+                self.clear_position(&update.ticker);
+                false
+            }
+            Some(pos) => {
+                if pos.side != side || pos.shares != shares {
+                    tracing::info!(
+                        "Position on {} resynced: {:?} {}x → {:?} {}x",
+                        update.ticker, pos.side, pos.shares, side, shares
+                    );
+                    pos.side = side;
+                    pos.shares = shares;
+                }
+                false
+            }
+            None => {
+                let Some(entry_price_cents) = update.avg_price_cents else {
+                    tracing::warn!(
+                        "Exchange reports {:?} {}x on {} with no cost basis — not tracking it",
+                        side, shares, update.ticker
+                    );
+                    return false;
+                };
+                let track_only = entry_order_id.is_none();
+                tracing::info!(
+                    "Position {} from the exchange: {:?} {}x @ {}¢ on {}",
+                    if track_only { "tracked (not ours, never exited)" } else { "adopted" },
+                    side, shares, entry_price_cents, update.ticker
+                );
+                self.positions.insert(update.ticker.clone(), OpenPosition {
+                    ticker: update.ticker.clone(),
+                    side,
+                    shares,
+                    entry_price_cents,
+                    order_id: entry_order_id.unwrap_or_default().to_string(),
+                    entered_at: update
+                        .exchange_ts_ms
+                        .and_then(millis_to_rfc3339)
+                        .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+                    track_only,
+                });
+                true
+            }
+        }
+    }
+
+    /// Keep the newest book per ticker by exchange time; a backlogged update
+    /// older than the one held is dropped so exits never price off it.
     pub fn on_orderbook_update(&mut self, update: OrderbookUpdate) {
+        if let Some(current) = self.orderbooks.get(&update.ticker) {
+            if update.is_older_than(current) {
+                tracing::debug!("Dropping out-of-order book update for {}", update.ticker);
+                return;
+            }
+        }
         self.orderbooks.insert(update.ticker.clone(), update);
     }
 
+    /// Forget a ticker's book until a fresh one arrives, so exits never
+    /// price off a book known to be wrong.
+    pub fn drop_orderbook(&mut self, ticker: &str) {
+        self.orderbooks.remove(ticker);
+    }
+
     /// Returns the unrealized P&L per share for a specific position.
     pub fn unrealized_pnl_per_share(&self, ticker: &str) -> Option<i32> {
         let pos = self.positions.get(ticker)?;
@@ -73,10 +173,26 @@ impl PositionManager {
         Some(exit_price as i32 - pos.entry_price_cents as i32)
     }
 
-    /// Check all positions for TP/SL exits. Returns list of (ticker, reason).
+    /// Open positions as (cost basis, marked value) in cents, marking each at
+    /// its best exit price, or at cost until an orderbook has arrived.
+    pub fn mark_to_market(&self) -> (i64, i64) {
+        self.positions.values().fold((0, 0), |(cost, value), pos| {
+            let mark = self
+                .orderbooks
+                .get(&pos.ticker)
+                .and_then(|ob| best_exit_price(pos, ob))
+                .unwrap_or(pos.entry_price_cents);
+            (
+                cost + pos.entry_price_cents as i64 * pos.shares as i64,
+                value + mark as i64 * pos.shares as i64,
+            )
+        })
+    }
+
+    /// Check managed positions for TP/SL exits. Returns list of (ticker, reason).
     pub fn check_exits(&self) -> Vec<(String, ExitReason)> {
         let mut exits = Vec::new();
-        for (ticker, _pos) in &self.positions {
+        for (ticker, _pos) in self.positions.iter().filter(|(_, p)| !p.track_only) {
             if let Some(pnl) = self.unrealized_pnl_per_share(ticker) {
                 if pnl >= self.tp_cents as i32 {
                     exits.push((ticker.clone(), ExitReason::TakeProfit));
@@ -88,18 +204,20 @@ impl PositionManager {
         exits
     }
 
-    /// Build an exit order for a specific position.
-    pub fn build_exit_order(&self, ticker: &str) -> Option<OrderRequest> {
-        let pos = self.positions.get(ticker)?;
+    /// Build an exit order for a specific position. A stop-loss into a wide
+    /// spread goes out as a market order: getting out beats price there.
+    pub fn build_exit_order(&self, ticker: &str, reason: &ExitReason) -> Option<OrderRequest> {
+        let pos = self.positions.get(ticker).filter(|p| !p.track_only)?;
         let ob = self.orderbooks.get(ticker)?;
         let exit_price = best_exit_price(pos, ob)?;
 
-        Some(OrderRequest {
-            ticker: pos.ticker.clone(),
-            side: pos.side.clone(),
-            shares: pos.shares,
-            price_cents: exit_price,
-        })
+        let mut order = OrderRequest::new(pos.ticker.clone(), pos.side.clone(), pos.shares, exit_price);
+        let wide = exit_spread(pos, ob).is_none_or(|s| s >= self.market_exit_spread_cents);
+        if matches!(reason, ExitReason::StopLoss) && wide {
+            order.order_type = OrderType::Market;
+            order.price_cents = 1;
+        }
+        Some(order)
     }
 
     /// Build an ExitEvent for ledger recording.
@@ -130,6 +248,16 @@ impl PositionManager {
     }
 }
 
+/// Ask minus bid on the position's side; None without an opposite-side bid.
+fn exit_spread(pos: &OpenPosition, ob: &OrderbookUpdate) -> Option<u32> {
+    let opposite = match pos.side {
+        Side::Yes => &ob.no,
+        Side::No => &ob.yes,
+    };
+    let ask = 100 - opposite.iter().map(|(price, _qty)| *price).max()?;
+    Some(ask.saturating_sub(best_exit_price(pos, ob)?))
+}
+
 fn best_exit_price(pos: &OpenPosition, ob: &OrderbookUpdate) -> Option<u32> {
     let bids = match pos.side {
         Side::Yes => &ob.yes,
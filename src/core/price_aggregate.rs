@@ -0,0 +1,198 @@
+use crate::core::types::{Candle, PerpQuote, PriceFeedError};
+use crate::ports::price_feed::PriceFeed;
+use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use std::collections::BTreeMap;
+
+/// `PriceFeed` over several sources queried concurrently: the median spot,
+/// and candles merged per open time (median prices, summed volume). A source
+/// that errors or returns nothing is left out; a spread between sources
+/// wider than `divergence_pct` of the median is logged, so one exchange's
+/// bad print can't reach the brain unnoticed or unaveraged. With fewer than
+/// three answers there is no majority to outvote a bad print, so a diverged
+/// read returns nothing instead.
+pub struct AggregatedPriceFeed {
+    sources: Vec<(&'static str, Box<dyn PriceFeed>)>,
+    divergence_pct: f64,
+}
+
+impl AggregatedPriceFeed {
+    pub fn new(sources: Vec<(&'static str, Box<dyn PriceFeed>)>, divergence_pct: f64) -> Self {
+        Self { sources, divergence_pct }
+    }
+
+    /// Whether the named quotes spread wider than the threshold around `mid`
+    /// with too few of them for the median to outvote the outlier; warns on
+    /// any divergence.
+    fn unresolved_divergence(&self, what: &str, quotes: &[(&'static str, f64)], mid: f64) -> bool {
+        let (Some(lo), Some(hi)) = (
+            quotes.iter().min_by(|a, b| a.1.total_cmp(&b.1)),
+            quotes.iter().max_by(|a, b| a.1.total_cmp(&b.1)),
+        ) else {
+            return false;
+        };
+        if mid <= 0.0 {
+            return false;
+        }
+        let spread_pct = (hi.1 - lo.1) / mid * 100.0;
+        if spread_pct <= self.divergence_pct {
+            return false;
+        }
+        if quotes.len() < 3 {
+            tracing::warn!(
+                "Price sources diverge on {} by {:.2}%: {} {:.2} vs {} {:.2} — no majority, skipping",
+                what, spread_pct, lo.0, lo.1, hi.0, hi.1
+            );
+            return true;
+        }
+        tracing::warn!(
+            "Price sources diverge on {} by {:.2}%: {} {:.2} vs {} {:.2} (using median {:.2})",
+            what, spread_pct, lo.0, lo.1, hi.0, hi.1, mid
+        );
+        false
+    }
+}
+
+/// Median of a non-empty list (mean of the middle two for an even count).
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// With no answers: down (the first source's error) only if every source
+/// is, else "no data".
+fn all_down<T>(mut down: Vec<anyhow::Error>, sources: usize) -> Result<Option<T>> {
+    if !down.is_empty() && down.len() == sources {
+        return Err(down.swap_remove(0));
+    }
+    Ok(None)
+}
+
+/// Merge each source's candles by open time, keeping the newest `limit`.
+fn merge_candles(series: Vec<Vec<Candle>>, limit: usize) -> Vec<Candle> {
+    let mut by_open: BTreeMap<i64, Vec<Candle>> = BTreeMap::new();
+    for candle in series.into_iter().flatten() {
+        by_open.entry(candle.open_time).or_default().push(candle);
+    }
+    let mut merged: Vec<Candle> = by_open
+        .into_iter()
+        .map(|(open_time, group)| {
+            let field = |f: fn(&Candle) -> f64| median(&mut group.iter().map(f).collect::<Vec<_>>());
+            Candle {
+                open_time,
+                open: field(|c| c.open),
+                high: field(|c| c.high),
+                low: field(|c| c.low),
+                close: field(|c| c.close),
+                volume: group.iter().map(|c| c.volume).sum(),
+                close_time: group.iter().map(|c| c.close_time).max().unwrap_or(open_time),
+            }
+        })
+        .collect();
+    let excess = merged.len().saturating_sub(limit);
+    merged.drain(..excess);
+    merged
+}
+
+#[async_trait]
+impl PriceFeed for AggregatedPriceFeed {
+    async fn candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: u32,
+    ) -> Result<Option<Vec<Candle>>> {
+        let results = join_all(
+            self.sources.iter().map(|(_, feed)| feed.candles(symbol, interval, limit)),
+        )
+        .await;
+        let mut series = Vec::new();
+        let mut closes = Vec::new();
+        let mut down = Vec::new();
+        for ((name, _), result) in self.sources.iter().zip(results) {
+            match result {
+                Ok(Some(candles)) if !candles.is_empty() => {
+                    closes.extend(candles.last().map(|c| (*name, c.close)));
+                    series.push(candles);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("{} candles failed: {}", name, e);
+                    if PriceFeedError::is_down(&e) {
+                        down.push(e);
+                    }
+                }
+            }
+        }
+        if series.is_empty() {
+            return all_down(down, self.sources.len());
+        }
+        let merged = merge_candles(series, limit as usize);
+        if let Some(last) = merged.last() {
+            if self.unresolved_divergence(&format!("{} {} close", symbol, interval), &closes, last.close) {
+                return Ok(None);
+            }
+        }
+        Ok(Some(merged))
+    }
+
+    async fn spot_price(&self, symbol: &str) -> Result<Option<f64>> {
+        let results = join_all(self.sources.iter().map(|(_, feed)| feed.spot_price(symbol))).await;
+        let mut quotes = Vec::new();
+        let mut down = Vec::new();
+        for ((name, _), result) in self.sources.iter().zip(results) {
+            match result {
+                Ok(Some(price)) => quotes.push((*name, price)),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("{} spot price failed: {}", name, e);
+                    if PriceFeedError::is_down(&e) {
+                        down.push(e);
+                    }
+                }
+            }
+        }
+        if quotes.is_empty() {
+            return all_down(down, self.sources.len());
+        }
+        let mid = median(&mut quotes.iter().map(|q| q.1).collect::<Vec<_>>());
+        if self.unresolved_divergence(&format!("{} spot", symbol), &quotes, mid) {
+            return Ok(None);
+        }
+        Ok(Some(mid))
+    }
+
+    /// From the first source that has one; only some exchanges list perps.
+    async fn perp(&self, symbol: &str) -> Result<Option<PerpQuote>> {
+        for (name, feed) in &self.sources {
+            match feed.perp(symbol).await {
+                Ok(Some(quote)) => return Ok(Some(quote)),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("{} perp quote failed: {}", name, e),
+            }
+        }
+        Ok(None)
+    }
+
+    fn record_price(&self, symbol: &str, price: f64, ts_ms: i64) {
+        for (_, feed) in &self.sources {
+            feed.record_price(symbol, price, ts_ms);
+        }
+    }
+
+    fn record_kline(&self, symbol: &str, candle: &Candle, closed: bool) {
+        for (_, feed) in &self.sources {
+            feed.record_kline(symbol, candle, closed);
+        }
+    }
+
+    fn realized_vol(&self, symbol: &str) -> Option<f64> {
+        self.sources.iter().find_map(|(_, feed)| feed.realized_vol(symbol))
+    }
+}
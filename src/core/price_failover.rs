@@ -0,0 +1,151 @@
+use crate::core::types::{Candle, PerpQuote, PriceFeedError};
+use crate::ports::price_feed::PriceFeed;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// How often a failed-over feed retries the primary.
+const PROBE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `PriceFeed` that serves from `primary` until it misses (errors or returns
+/// nothing) `fail_after` times in a row or reports itself down (e.g. a
+/// rate-limit ban), then from `secondary`, probing the
+/// primary once a minute and switching back when a probe succeeds. Each
+/// switch sends a message on `alerts` for the daemon to forward.
+pub struct FailoverPriceFeed {
+    primary: Box<dyn PriceFeed>,
+    secondary: Box<dyn PriceFeed>,
+    secondary_name: &'static str,
+    fail_after: u32,
+    alerts: mpsc::UnboundedSender<String>,
+    state: Mutex<FailoverState>,
+}
+
+struct FailoverState {
+    misses: u32,
+    on_secondary: bool,
+    last_probe: Instant,
+}
+
+impl FailoverPriceFeed {
+    pub fn new(
+        primary: Box<dyn PriceFeed>,
+        secondary: Box<dyn PriceFeed>,
+        secondary_name: &'static str,
+        fail_after: u32,
+        alerts: mpsc::UnboundedSender<String>,
+    ) -> Self {
+        Self {
+            primary,
+            secondary,
+            secondary_name,
+            fail_after: fail_after.max(1),
+            alerts,
+            state: Mutex::new(FailoverState { misses: 0, on_secondary: false, last_probe: Instant::now() }),
+        }
+    }
+
+    /// Whether this call goes to the primary: always while it's active,
+    /// else once per probe interval.
+    fn try_primary(&self) -> bool {
+        let mut state = self.state.lock().expect("failover state poisoned");
+        if !state.on_secondary {
+            return true;
+        }
+        if state.last_probe.elapsed() < PROBE_INTERVAL {
+            return false;
+        }
+        state.last_probe = Instant::now();
+        true
+    }
+
+    /// Count a primary hit or miss, switching feeds at the thresholds.
+    /// Returns whether the primary's answer should be used.
+    fn on_primary<T>(&self, result: &Result<Option<T>>) -> bool {
+        let hit = matches!(result, Ok(Some(_)));
+        let mut state = self.state.lock().expect("failover state poisoned");
+        if hit {
+            state.misses = 0;
+            if state.on_secondary {
+                state.on_secondary = false;
+                self.alert("Price feed: primary recovered — switched back from fallback".into());
+            }
+            return true;
+        }
+        state.misses += 1;
+        let down = result.as_ref().err().filter(|e| PriceFeedError::is_down(e));
+        if !state.on_secondary && (down.is_some() || state.misses >= self.fail_after) {
+            state.on_secondary = true;
+            state.last_probe = Instant::now();
+            self.alert(match down {
+                Some(e) => format!("Price feed: primary down ({}) — failing over to {}", e, self.secondary_name),
+                None => format!(
+                    "Price feed: primary missed {} requests in a row — failing over to {}",
+                    state.misses, self.secondary_name
+                ),
+            });
+        }
+        !state.on_secondary
+    }
+
+    fn alert(&self, msg: String) {
+        tracing::warn!("{}", msg);
+        let _ = self.alerts.send(msg);
+    }
+
+    fn active(&self) -> &dyn PriceFeed {
+        if self.state.lock().expect("failover state poisoned").on_secondary {
+            self.secondary.as_ref()
+        } else {
+            self.primary.as_ref()
+        }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for FailoverPriceFeed {
+    async fn candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: u32,
+    ) -> Result<Option<Vec<Candle>>> {
+        if self.try_primary() {
+            let result = self.primary.candles(symbol, interval, limit).await;
+            if self.on_primary(&result) {
+                return result;
+            }
+        }
+        self.secondary.candles(symbol, interval, limit).await
+    }
+
+    async fn spot_price(&self, symbol: &str) -> Result<Option<f64>> {
+        if self.try_primary() {
+            let result = self.primary.spot_price(symbol).await;
+            if self.on_primary(&result) {
+                return result;
+            }
+        }
+        self.secondary.spot_price(symbol).await
+    }
+
+    async fn perp(&self, symbol: &str) -> Result<Option<PerpQuote>> {
+        self.active().perp(symbol).await
+    }
+
+    fn record_price(&self, symbol: &str, price: f64, ts_ms: i64) {
+        self.primary.record_price(symbol, price, ts_ms);
+        self.secondary.record_price(symbol, price, ts_ms);
+    }
+
+    fn record_kline(&self, symbol: &str, candle: &Candle, closed: bool) {
+        self.primary.record_kline(symbol, candle, closed);
+        self.secondary.record_kline(symbol, candle, closed);
+    }
+
+    fn realized_vol(&self, symbol: &str) -> Option<f64> {
+        self.active().realized_vol(symbol)
+    }
+}
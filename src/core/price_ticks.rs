@@ -0,0 +1,90 @@
+use crate::core::types::{Candle, PerpQuote, PriceTick};
+use crate::ports::price_feed::PriceFeed;
+use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+/// Ticks buffered per subscriber before a slow one starts skipping.
+const TICK_BUFFER: usize = 256;
+
+/// `PriceFeed` that publishes every recorded price as a `PriceTick` to its
+/// subscribers and keeps the latest per symbol. Wraps the whole feed stack,
+/// since it's where the daemon records streamed prices.
+pub struct PriceTicks {
+    inner: Box<dyn PriceFeed>,
+    ticks: broadcast::Sender<PriceTick>,
+    latest: Mutex<HashMap<String, PriceTick>>,
+}
+
+impl PriceTicks {
+    pub fn new(inner: Box<dyn PriceFeed>) -> Self {
+        Self { inner, ticks: broadcast::channel(TICK_BUFFER).0, latest: Mutex::default() }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for PriceTicks {
+    async fn candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: u32,
+    ) -> Result<Option<Vec<Candle>>> {
+        self.inner.candles(symbol, interval, limit).await
+    }
+
+    async fn spot_price(&self, symbol: &str) -> Result<Option<f64>> {
+        self.inner.spot_price(symbol).await
+    }
+
+    async fn perp(&self, symbol: &str) -> Result<Option<PerpQuote>> {
+        self.inner.perp(symbol).await
+    }
+
+    fn record_price(&self, symbol: &str, price: f64, ts_ms: i64) {
+        self.inner.record_price(symbol, price, ts_ms);
+        let tick = PriceTick { symbol: symbol.to_string(), price };
+        if let Ok(mut latest) = self.latest.lock() {
+            latest.insert(tick.symbol.clone(), tick.clone());
+        }
+        // No subscribers is fine
+        let _ = self.ticks.send(tick);
+    }
+
+    fn record_kline(&self, symbol: &str, candle: &Candle, closed: bool) {
+        self.inner.record_kline(symbol, candle, closed);
+    }
+
+    fn realized_vol(&self, symbol: &str) -> Option<f64> {
+        self.inner.realized_vol(symbol)
+    }
+
+    /// A subscriber that falls more than `TICK_BUFFER` ticks behind skips
+    /// to the newest; the stream ends when the feed is dropped.
+    fn subscribe(&self, symbol: &str) -> BoxStream<'static, PriceTick> {
+        // Subscribe before reading the latest so no tick falls in between
+        let rx = self.ticks.subscribe();
+        let first = self.last_tick(symbol);
+        let symbol = symbol.to_string();
+        let live = stream::unfold((rx, symbol), |(mut rx, symbol)| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(tick) if tick.symbol == symbol => return Some((tick, (rx, symbol))),
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!("{} tick subscriber lagged, skipped {}", symbol, skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+        stream::iter(first).chain(live).boxed()
+    }
+
+    fn last_tick(&self, symbol: &str) -> Option<PriceTick> {
+        self.latest.lock().ok()?.get(symbol).cloned()
+    }
+}
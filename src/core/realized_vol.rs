@@ -0,0 +1,68 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Minimum returns in the window before an estimate is reported.
+const MIN_RETURNS: usize = 12;
+
+/// Rolling realized-volatility estimator per symbol, sampled from streamed
+/// prices into fixed buckets (e.g. 5s returns over the last 10 minutes).
+#[derive(Debug)]
+pub struct RealizedVol {
+    /// (bucket index, last price in bucket), oldest first.
+    samples: HashMap<String, VecDeque<(i64, f64)>>,
+    bucket_ms: i64,
+    window_buckets: i64,
+}
+
+impl RealizedVol {
+    pub fn new(bucket_secs: u64, window_secs: u64) -> Self {
+        let bucket_secs = bucket_secs.max(1);
+        Self {
+            samples: HashMap::new(),
+            bucket_ms: bucket_secs as i64 * 1000,
+            window_buckets: (window_secs / bucket_secs).max(2) as i64,
+        }
+    }
+
+    /// Record a streamed price observed at `ts_ms` (unix millis).
+    pub fn record(&mut self, symbol: &str, price: f64, ts_ms: i64) {
+        if price <= 0.0 {
+            return;
+        }
+        let bucket = ts_ms / self.bucket_ms;
+        let q = self.samples.entry(symbol.to_string()).or_default();
+
+        match q.back_mut() {
+            Some((b, p)) if *b == bucket => *p = price,
+            Some((b, _)) if *b > bucket => return, // out-of-order tick
+            _ => q.push_back((bucket, price)),
+        }
+
+        while q.front().is_some_and(|(b, _)| *b <= bucket - self.window_buckets) {
+            q.pop_front();
+        }
+    }
+
+    /// Standard deviation of per-bucket log returns, in percent. Returns that
+    /// span missing buckets are scaled down by √gap so quiet periods don't
+    /// inflate the estimate. None until the window has enough samples.
+    pub fn realized_vol_pct(&self, symbol: &str) -> Option<f64> {
+        let q = self.samples.get(symbol)?;
+        let returns: Vec<f64> = q
+            .iter()
+            .zip(q.iter().skip(1))
+            .map(|((b0, p0), (b1, p1))| {
+                let gap = (b1 - b0).max(1) as f64;
+                (p1 / p0).ln() / gap.sqrt()
+            })
+            .collect();
+
+        if returns.len() < MIN_RETURNS {
+            return None;
+        }
+
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance =
+            returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+        Some(variance.sqrt() * 100.0)
+    }
+}
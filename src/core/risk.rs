@@ -1,7 +1,8 @@
-use crate::core::types::{Config, Stats};
+use crate::core::stats::CounterSnapshot;
+use crate::core::types::{ticker_event, ticker_series, Config, LedgerRow};
 
 pub fn check(
-    stats: &Stats,
+    stats: &CounterSnapshot,
     balance_cents: u64,
     config: &Config,
 ) -> Option<String> {
@@ -23,6 +24,42 @@ pub fn check(
     None
 }
 
+/// Correlated-exposure limits. Sums the cost basis of the pending ledger rows
+/// (open positions, resting orders, paper trades) in the same event and the
+/// same underlying asset, plus the proposed order. Returns a veto reason.
+pub fn check_exposure(
+    pending: &[LedgerRow],
+    ticker: &str,
+    order_cost_cents: i64,
+    config: &Config,
+) -> Option<String> {
+    let event = ticker_event(ticker);
+    let asset = config.asset_label(ticker_series(ticker));
+    let pending = pending.iter().filter(|r| r.result == "pending");
+
+    let (event_cents, asset_cents) = pending.fold((0, 0), |(e, a), r| {
+        let cost = r.cost_cents();
+        (
+            e + if ticker_event(&r.ticker) == event { cost } else { 0 },
+            a + if config.asset_label(r.series()) == asset { cost } else { 0 },
+        )
+    });
+
+    if event_cents + order_cost_cents > config.max_event_notional_cents {
+        return Some(format!(
+            "Event {} exposure {}¢ + {}¢ > {}¢ cap",
+            event, event_cents, order_cost_cents, config.max_event_notional_cents
+        ));
+    }
+    if asset_cents + order_cost_cents > config.max_asset_notional_cents {
+        return Some(format!(
+            "{} exposure {}¢ + {}¢ > {}¢ cap",
+            asset, asset_cents, order_cost_cents, config.max_asset_notional_cents
+        ));
+    }
+    None
+}
+
 /// Half-Kelly position sizing.
 /// Returns number of shares (1..=max_shares), or 0 if Kelly says no bet.
 pub fn kelly_shares(win_prob: f64, price_cents: u32, max_shares: u32) -> u32 {
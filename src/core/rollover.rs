@@ -0,0 +1,69 @@
+use crate::core::types::MarketState;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// A series' next market, lined up before the current one expires.
+#[derive(Debug, Clone)]
+pub struct Rollover {
+    pub ticker: String,
+    /// When to run the entry cycle against it.
+    pub due: DateTime<Utc>,
+}
+
+/// Per-series rollover schedule: shortly before the traded market expires,
+/// its successor is pre-fetched and queued so the entry cycle can target it
+/// the moment it opens instead of waiting for the next timer tick. Pure — no IO.
+#[derive(Debug)]
+pub struct Rollovers {
+    lead_secs: f64,
+    pending: HashMap<String, Rollover>,
+}
+
+fn parse_time(t: &str) -> Option<DateTime<Utc>> {
+    Some(DateTime::parse_from_rfc3339(t).ok()?.with_timezone(&Utc))
+}
+
+impl Rollovers {
+    pub fn new(lead_secs: u64) -> Self {
+        Self { lead_secs: lead_secs as f64, pending: HashMap::new() }
+    }
+
+    /// Whether `current` is within the lead time of expiry and its series
+    /// has no successor queued yet.
+    pub fn wants_prefetch(&self, series: &str, current: &MarketState) -> bool {
+        !self.pending.contains_key(series) && current.minutes_to_expiry * 60.0 <= self.lead_secs
+    }
+
+    /// Queue `next` to be entered when it opens, or when `current` expires if
+    /// that is later. Returns false if neither time parses.
+    pub fn schedule(&mut self, series: &str, current: &MarketState, next: &MarketState) -> bool {
+        let due = match (parse_time(&next.open_time), parse_time(&current.expiration_time)) {
+            (Some(open), Some(exp)) => open.max(exp),
+            (Some(t), None) | (None, Some(t)) => t,
+            (None, None) => return false,
+        };
+        self.pending.insert(
+            series.to_string(),
+            Rollover { ticker: next.ticker.clone(), due },
+        );
+        true
+    }
+
+    /// Earliest queued rollover time.
+    pub fn next_due(&self) -> Option<DateTime<Utc>> {
+        self.pending.values().map(|r| r.due).min()
+    }
+
+    /// Remove and return the (series, rollover) pairs due at `now`.
+    pub fn take_due(&mut self, now: DateTime<Utc>) -> Vec<(String, Rollover)> {
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, r)| r.due <= now)
+            .map(|(series, _)| series.clone())
+            .collect();
+        due.into_iter()
+            .filter_map(|series| self.pending.remove_entry(&series))
+            .collect()
+    }
+}
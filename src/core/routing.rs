@@ -0,0 +1,140 @@
+use crate::core::types::*;
+use crate::ports::brain::Brain;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Pick a tier for this context. Calm, far-from-strike markets with a
+/// consistent signal go to the cheap tier; everything else (and any context
+/// missing price data) goes to the expensive model.
+pub fn route(context: &DecisionContext, policy: &ModelRouting) -> (ModelTier, String) {
+    let (Some(price), Some(summary)) = (&context.crypto_price, &context.signal_summary) else {
+        return (ModelTier::Expensive, "no price data".into());
+    };
+    let ind = &price.indicators;
+
+    if ind.volatility_1m >= policy.high_vol_pct {
+        return (ModelTier::Expensive, format!("high vol ({:.3}%)", ind.volatility_1m));
+    }
+
+    let strike_gap_pct = context
+        .market
+        .strike
+        .filter(|k| *k > 0.0)
+        .map(|k| (ind.spot_price - k) / k * 100.0);
+    if let Some(gap) = strike_gap_pct {
+        if gap.abs() <= policy.near_strike_pct {
+            return (ModelTier::Expensive, format!("near strike ({:+.2}%)", gap));
+        }
+    }
+
+    if summary.trend == TrendAlignment::Mixed {
+        return (ModelTier::Expensive, "mixed trend".into());
+    }
+    // Book leaning against the recommended side (same thresholds as the
+    // imbalance adjustment in compute_signal_summary)
+    let book_conflict = match summary.recommended_side {
+        Some(Side::Yes) => summary.orderbook_imbalance < 0.5,
+        Some(Side::No) => summary.orderbook_imbalance > 2.0,
+        None => false,
+    };
+    if book_conflict {
+        return (
+            ModelTier::Expensive,
+            format!("book imbalance {:.2} against signal", summary.orderbook_imbalance),
+        );
+    }
+
+    let tier = if policy.cheap_model.is_some() { ModelTier::Cheap } else { ModelTier::Quant };
+    let reason = match strike_gap_pct {
+        Some(gap) => format!("calm, {:+.2}% from strike", gap),
+        None => "calm".into(),
+    };
+    (tier, reason)
+}
+
+/// Decision straight from the signal summary, for the quant-only tier.
+pub fn quant_decision(context: &DecisionContext) -> TradeDecision {
+    let pass = |reasoning: String| TradeDecision {
+        action: Action::Pass,
+        side: None,
+        shares: None,
+        max_price_cents: None,
+        reasoning,
+        estimated_probability: None,
+        estimated_edge: None,
+        prompt: String::new(),
+        raw_response: String::new(),
+        route: None,
+    };
+    let Some(summary) = &context.signal_summary else {
+        return pass("No signal summary".into());
+    };
+    let (side, ask) = match summary.recommended_side {
+        Some(Side::Yes) => (Side::Yes, context.market.yes_ask),
+        Some(Side::No) => (Side::No, context.market.no_ask),
+        None => return pass(format!("Quant: no side ({})", summary.narrative)),
+    };
+    if summary.kelly_shares == 0 {
+        return pass(format!("Quant: edge {:.1}pt too small to size", summary.estimated_edge));
+    }
+    let Some(ask) = ask else {
+        return pass(format!("Quant: no {} ask", side));
+    };
+    let probability = match side {
+        Side::Yes => summary.estimated_probability,
+        Side::No => 100.0 - summary.estimated_probability,
+    };
+    TradeDecision {
+        action: Action::Buy,
+        side: Some(side),
+        shares: Some(summary.kelly_shares),
+        max_price_cents: Some(ask),
+        reasoning: format!("Quant: {}", summary.narrative),
+        estimated_probability: Some(probability),
+        estimated_edge: Some(summary.estimated_edge),
+        prompt: String::new(),
+        raw_response: String::new(),
+        route: None,
+    }
+}
+
+/// Routing policy in front of the Brain port: sends each decision to the
+/// quant-only, cheap, or expensive tier and tags it with the route taken.
+pub struct RoutedBrain {
+    policy: ModelRouting,
+    expensive: Box<dyn Brain>,
+    expensive_model: String,
+    /// None when the cheap tier is quant-only.
+    cheap: Option<Box<dyn Brain>>,
+}
+
+impl RoutedBrain {
+    pub fn new(
+        policy: ModelRouting,
+        expensive: Box<dyn Brain>,
+        expensive_model: String,
+        cheap: Option<Box<dyn Brain>>,
+    ) -> Self {
+        Self { policy, expensive, expensive_model, cheap }
+    }
+}
+
+#[async_trait]
+impl Brain for RoutedBrain {
+    async fn decide(&self, context: &DecisionContext) -> Result<TradeDecision> {
+        let (tier, reason) = route(context, &self.policy);
+        let (mut decision, model) = match (tier, &self.cheap) {
+            (ModelTier::Cheap, Some(cheap)) => (
+                cheap.decide(context).await?,
+                self.policy.cheap_model.clone().unwrap_or_default(),
+            ),
+            (ModelTier::Expensive, _) | (ModelTier::Cheap, None) => {
+                (self.expensive.decide(context).await?, self.expensive_model.clone())
+            }
+            (ModelTier::Quant, _) => (quant_decision(context), "quant".to_string()),
+        };
+        tracing::info!("[{}] Routed to {} ({}): {}", context.market.ticker, tier, model, reason);
+        decision.route = Some(Route { tier, model, reason });
+        Ok(decision)
+    }
+}
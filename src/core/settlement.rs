@@ -0,0 +1,48 @@
+use crate::core::types::{LedgerRow, Settlement};
+
+/// Cross-check a portfolio settlement against the market's final `result`
+/// and the ledger row it would settle. Returns a reason to hold the ledger
+/// update if the two sources disagree or the revenue doesn't add up.
+pub fn verify(
+    settlement: &Settlement,
+    market_result: Option<&str>,
+    row: &LedgerRow,
+) -> Result<(), String> {
+    let market_result = match market_result {
+        Some(r) if r == "yes" || r == "no" => r,
+        Some(r) => return Err(format!("market result {:?} is not yes/no", r)),
+        None => return Err("market has no final result yet".into()),
+    };
+
+    if settlement.market_result != market_result {
+        return Err(format!(
+            "settlement says {:?}, market says {:?}",
+            settlement.market_result, market_result
+        ));
+    }
+
+    // Zero count means the API didn't itemize the position; revenue still checks it
+    if settlement.count > 0
+        && (settlement.side.to_string() != row.side || settlement.count != row.shares)
+    {
+        return Err(format!(
+            "settlement holds {} {}x, ledger has {} {}x",
+            settlement.side, settlement.count, row.side, row.shares
+        ));
+    }
+
+    // Each winning contract pays 100¢; a losing side pays nothing.
+    let expected_revenue = if row.side == market_result {
+        row.shares as i64 * 100
+    } else {
+        0
+    };
+    if settlement.pnl_cents != expected_revenue {
+        return Err(format!(
+            "revenue {}¢ inconsistent with {} {}x on a {:?} result (expected {}¢)",
+            settlement.pnl_cents, row.side, row.shares, market_result, expected_revenue
+        ));
+    }
+
+    Ok(())
+}
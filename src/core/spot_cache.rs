@@ -0,0 +1,40 @@
+use crate::core::depth_imbalance::DepthImbalance;
+use crate::core::spot_flow::SpotFlow;
+use crate::core::types::OrderFlow;
+
+/// Streamed spot-market state beside the price feed — the Binance depth
+/// stream's book imbalance and the aggTrade stream's taker flow per symbol.
+/// Fed by main from the Binance WS and read by the entry path, as
+/// `BookCache` is for Kalshi books. Pure — no IO.
+#[derive(Debug)]
+pub struct SpotCache {
+    depth: DepthImbalance,
+    flow: SpotFlow,
+}
+
+impl SpotCache {
+    pub fn new(flow_window_secs: u64) -> Self {
+        Self { depth: DepthImbalance::default(), flow: SpotFlow::new(flow_window_secs) }
+    }
+
+    /// Record a streamed spot order book, best levels first.
+    pub fn on_depth(&mut self, symbol: &str, bids: &[(f64, f64)], asks: &[(f64, f64)], ts_ms: i64) {
+        self.depth.record(symbol, bids, asks, ts_ms);
+    }
+
+    /// Record a streamed spot trade.
+    pub fn on_trade(&mut self, symbol: &str, qty: f64, taker_buy: bool, ts_ms: i64) {
+        self.flow.record(symbol, qty, taker_buy, ts_ms);
+    }
+
+    /// Latest bid/ask imbalance of the streamed spot book (> 1.0 bid-heavy),
+    /// unless it's stale at `now_ms`.
+    pub fn depth_imbalance(&self, symbol: &str, now_ms: i64) -> Option<f64> {
+        self.depth.get(symbol, now_ms)
+    }
+
+    /// Taker buy vs sell volume over the flow window ending at `now_ms`.
+    pub fn order_flow(&self, symbol: &str, now_ms: i64) -> Option<OrderFlow> {
+        self.flow.flow(symbol, now_ms)
+    }
+}
@@ -0,0 +1,38 @@
+use crate::core::types::OrderFlow;
+use std::collections::{HashMap, VecDeque};
+
+/// Rolling taker buy/sell volume per symbol from the spot trade stream. No IO.
+#[derive(Debug)]
+pub struct SpotFlow {
+    window_ms: i64,
+    /// (trade time in unix millis, quantity, taker bought), oldest first.
+    trades: HashMap<String, VecDeque<(i64, f64, bool)>>,
+}
+
+impl SpotFlow {
+    pub fn new(window_secs: u64) -> Self {
+        Self { window_ms: window_secs.max(1) as i64 * 1000, trades: HashMap::new() }
+    }
+
+    pub fn record(&mut self, symbol: &str, qty: f64, taker_buy: bool, ts_ms: i64) {
+        let q = self.trades.entry(symbol.to_string()).or_default();
+        q.push_back((ts_ms, qty, taker_buy));
+        while q.front().is_some_and(|(t, _, _)| *t <= ts_ms - self.window_ms) {
+            q.pop_front();
+        }
+    }
+
+    /// Taker volume over the window ending at `now_ms`, or None with no trades in it.
+    pub fn flow(&self, symbol: &str, now_ms: i64) -> Option<OrderFlow> {
+        let cutoff = now_ms - self.window_ms;
+        let mut flow = OrderFlow { window_secs: (self.window_ms / 1000) as u64, ..OrderFlow::default() };
+        for (_, qty, taker_buy) in self.trades.get(symbol)?.iter().filter(|(t, _, _)| *t > cutoff) {
+            if *taker_buy {
+                flow.buy_volume += qty;
+            } else {
+                flow.sell_volume += qty;
+            }
+        }
+        (flow.buy_volume + flow.sell_volume > 0.0).then_some(flow)
+    }
+}
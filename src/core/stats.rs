@@ -1,9 +1,10 @@
-use crate::core::types::{LedgerRow, Stats};
+use crate::core::types::{LedgerRow, ResultFilter, Stats};
+use std::sync::Mutex;
 
 pub fn compute(ledger: &[LedgerRow]) -> Stats {
     let done: Vec<&LedgerRow> = ledger
         .iter()
-        .filter(|r| r.result == "win" || r.result == "loss")
+        .filter(|r| ResultFilter::Settled.matches(&r.result))
         .collect();
 
     let wins = done.iter().filter(|r| r.result == "win").count() as u32;
@@ -83,3 +84,103 @@ fn max_drawdown(trades: &[&LedgerRow]) -> i64 {
     }
     worst
 }
+
+/// Counters the risk check needs, kept current incrementally as trades settle
+/// so it doesn't need a full ledger parse.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CounterSnapshot {
+    pub trades_today: u32,
+    pub losses_today: u32,
+    pub today_pnl_cents: i64,
+    /// Positive = consecutive wins, negative = consecutive losses.
+    pub current_streak: i32,
+}
+
+#[derive(Debug)]
+struct CounterState {
+    day: String,
+    counts: CounterSnapshot,
+}
+
+/// Concurrent-safe stat counters shared by all series' cycles.
+#[derive(Debug)]
+pub struct StatCounters {
+    state: Mutex<CounterState>,
+}
+
+fn today() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+impl StatCounters {
+    /// Seed from the ledger, using the same win/loss semantics as `compute`.
+    pub fn from_ledger(ledger: &[LedgerRow]) -> Self {
+        let stats = compute(ledger);
+        let today = today();
+        let done_today = ledger
+            .iter()
+            .filter(|r| ResultFilter::Settled.matches(&r.result) && r.timestamp.starts_with(&today));
+        let (trades_today, losses_today) = done_today.fold((0, 0), |(t, l), r| {
+            (t + 1, l + (r.result == "loss") as u32)
+        });
+
+        Self {
+            state: Mutex::new(CounterState {
+                day: today,
+                counts: CounterSnapshot {
+                    trades_today,
+                    losses_today,
+                    today_pnl_cents: stats.today_pnl_cents,
+                    current_streak: stats.current_streak,
+                },
+            }),
+        }
+    }
+
+    /// Record a settled trade. Results other than win/loss (cancelled,
+    /// unknown, early exits) don't count, matching `compute`.
+    pub fn record_result(&self, result: &str, pnl_cents: i64) {
+        let is_win = match result {
+            "win" => true,
+            "loss" => false,
+            _ => return,
+        };
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        roll_day(&mut state);
+
+        let c = &mut state.counts;
+        c.trades_today += 1;
+        c.today_pnl_cents += pnl_cents;
+        if is_win {
+            c.current_streak = if c.current_streak > 0 { c.current_streak + 1 } else { 1 };
+        } else {
+            c.losses_today += 1;
+            c.current_streak = if c.current_streak < 0 { c.current_streak - 1 } else { -1 };
+        }
+    }
+
+    /// Replace the counters with a fresh count of `ledger`: after an amended
+    /// row, or on shared backends where other instances settle trades too.
+    pub fn reseed(&self, ledger: &[LedgerRow]) {
+        let fresh = Self::from_ledger(ledger).state.into_inner().unwrap_or_else(|e| e.into_inner());
+        *self.state.lock().unwrap_or_else(|e| e.into_inner()) = fresh;
+    }
+
+    pub fn snapshot(&self) -> CounterSnapshot {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        roll_day(&mut state);
+        state.counts
+    }
+}
+
+/// Reset the daily counters at UTC midnight; the streak carries over.
+fn roll_day(state: &mut CounterState) {
+    let today = today();
+    if state.day != today {
+        state.day = today;
+        state.counts = CounterSnapshot {
+            current_streak: state.counts.current_streak,
+            ..CounterSnapshot::default()
+        };
+    }
+}
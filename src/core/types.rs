@@ -1,9 +1,11 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 
 // ── Signal Analysis ──
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TrendAlignment {
     AllUp,
     AllDown,
@@ -22,8 +24,41 @@ impl fmt::Display for TrendAlignment {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Built-in quant strategy that produces the signal summary for a series.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Strategy {
+    /// Follow the 15-minute move (trend, EMA, RSI continuation).
+    #[default]
+    Momentum,
+    /// Fade extreme 15-minute moves when RSI and distance-to-strike favor reversion.
+    MeanReversion,
+}
+
+impl Strategy {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "momentum" => Some(Strategy::Momentum),
+            "mean-reversion" | "mean_reversion" | "reversion" => Some(Strategy::MeanReversion),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Momentum => write!(f, "momentum"),
+            Strategy::MeanReversion => write!(f, "mean-reversion"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignalSummary {
+    /// Decisions recorded before strategies existed were all momentum.
+    #[serde(default)]
+    pub strategy: Strategy,
     pub trend: TrendAlignment,
     pub rsi_signal: String,
     pub orderbook_imbalance: f64,
@@ -32,11 +67,25 @@ pub struct SignalSummary {
     pub kelly_shares: u32,
     pub estimated_probability: f64,
     pub narrative: String,
+    /// Taker imbalance on the contract's own trades, -1 (all NO) to +1 (all
+    /// YES); None without recent trades.
+    #[serde(default)]
+    pub trade_flow: Option<f64>,
+}
+
+impl SignalSummary {
+    /// One-line version for notifications: trend, probability, edge, Kelly.
+    pub fn condensed(&self) -> String {
+        format!(
+            "{} | P(YES) {:.0}% | edge {:.1}pt | Kelly {}",
+            self.trend, self.estimated_probability, self.estimated_edge, self.kelly_shares
+        )
+    }
 }
 
 // ── AI Decision ──
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TradeDecision {
     pub action: Action,
     pub side: Option<Side>,
@@ -47,25 +96,71 @@ pub struct TradeDecision {
     pub estimated_probability: Option<f64>,
     #[serde(default)]
     pub estimated_edge: Option<f64>,
+    /// Full prompt sent to the model (set by the Brain adapter).
+    #[serde(skip)]
+    pub prompt: String,
+    /// Unparsed model response (set by the Brain adapter).
+    #[serde(skip)]
+    pub raw_response: String,
+    /// Which model tier answered (set by the routing brain).
+    #[serde(default)]
+    pub route: Option<Route>,
+}
+
+/// Decision-maker tier picked by the model router.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelTier {
+    /// No LLM call — act on the signal summary alone.
+    Quant,
+    Cheap,
+    Expensive,
+}
+
+impl fmt::Display for ModelTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelTier::Quant => write!(f, "quant"),
+            ModelTier::Cheap => write!(f, "cheap"),
+            ModelTier::Expensive => write!(f, "expensive"),
+        }
+    }
+}
+
+/// Where a decision was routed and why, persisted with the decision record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Route {
+    pub tier: ModelTier,
+    pub model: String,
+    pub reason: String,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Action {
     Buy,
     Pass,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum Side {
     Yes,
     No,
 }
 
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Yes => write!(f, "yes"),
+            Side::No => write!(f, "no"),
+        }
+    }
+}
+
 // ── Market Data ──
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketState {
     pub ticker: String,
     pub event_ticker: String,
@@ -78,11 +173,83 @@ pub struct MarketState {
     pub volume: u64,
     pub volume_24h: u64,
     pub open_interest: u64,
+    /// RFC 3339; empty for contexts recorded before it was captured.
+    #[serde(default)]
+    pub open_time: String,
     pub expiration_time: String,
     pub minutes_to_expiry: f64,
+    /// Strike of multi-strike markets (floor, or cap for "below" markets).
+    #[serde(default)]
+    pub strike: Option<f64>,
+    /// How the strike resolves YES; None for contexts recorded before it was captured.
+    #[serde(default)]
+    pub strike_kind: Option<StrikeKind>,
 }
 
-#[derive(Debug)]
+/// A market's YES condition on the underlying at expiry.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StrikeKind {
+    Above(f64),
+    Below(f64),
+    /// Between floor and cap.
+    Between(f64, f64),
+}
+
+impl StrikeKind {
+    /// From Kalshi's floor/cap strikes: floor only is "above", cap only "below".
+    pub fn from_strikes(floor: Option<f64>, cap: Option<f64>) -> Option<Self> {
+        match (floor, cap) {
+            (Some(floor), Some(cap)) => Some(StrikeKind::Between(floor, cap)),
+            (Some(floor), None) => Some(StrikeKind::Above(floor)),
+            (None, Some(cap)) => Some(StrikeKind::Below(cap)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// A listed Kalshi series (`Exchange::series`).
+#[derive(Debug, Clone)]
+pub struct SeriesInfo {
+    pub ticker: String,
+    pub title: String,
+    pub category: String,
+    /// Kalshi's schedule label, e.g. "fifteen_min", "daily".
+    pub frequency: String,
+}
+
+/// One period of a Kalshi contract's own price history, closing values in
+/// cents (`Exchange::market_candles`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractCandle {
+    /// Period end, unix seconds.
+    pub end_ts: i64,
+    pub yes_bid: Option<u32>,
+    pub yes_ask: Option<u32>,
+    pub last_price: Option<u32>,
+    pub volume: u64,
+}
+
+impl ContractCandle {
+    /// Implied P(YES) in percent: the bid/ask mid, else the last trade.
+    pub fn implied_probability(&self) -> Option<f64> {
+        match (self.yes_bid, self.yes_ask) {
+            (Some(bid), Some(ask)) if ask > 0 => Some((bid + ask) as f64 / 2.0),
+            _ => self.last_price.map(f64::from),
+        }
+    }
+}
+
+/// A Kalshi event with its full strike ladder (`Exchange::event`).
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub event_ticker: String,
+    pub series_ticker: String,
+    pub title: String,
+    /// Every market (strike) in the event, lowest strike first.
+    pub markets: Vec<MarketState>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Orderbook {
     pub yes: Vec<(u32, u32)>,
     pub no: Vec<(u32, u32)>,
@@ -90,7 +257,7 @@ pub struct Orderbook {
 
 // ── BTC Price Data ──
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Candle {
     pub open_time: i64,
     pub open: f64,
@@ -101,14 +268,14 @@ pub struct Candle {
     pub close_time: i64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MomentumDirection {
     Up,
     Down,
     Flat,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceIndicators {
     pub spot_price: f64,
     pub pct_change_15m: f64,
@@ -122,221 +289,1637 @@ pub struct PriceIndicators {
     pub rsi_9: f64,
     pub ema_9: f64,
     pub price_vs_ema: String,
+    /// Std-dev of 5s returns over the last 10 minutes (%), from the WS stream.
+    pub realized_vol_5s: Option<f64>,
+    /// Bid/ask imbalance of the spot order book (> 1.0 bid-heavy), from the
+    /// Binance depth stream.
+    pub depth_imbalance: Option<f64>,
+    /// Spot taker buy vs sell volume over the last few seconds, from the
+    /// Binance aggTrade stream.
+    pub order_flow: Option<OrderFlow>,
 }
 
-#[derive(Debug, Clone)]
+/// Spot taker volume, in base units, over a recent window.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderFlow {
+    pub window_secs: u64,
+    pub buy_volume: f64,
+    pub sell_volume: f64,
+}
+
+impl OrderFlow {
+    /// Buy minus sell volume.
+    pub fn delta(&self) -> f64 {
+        self.buy_volume - self.sell_volume
+    }
+
+    /// Delta as a share of total volume, in [-1, 1].
+    pub fn imbalance(&self) -> f64 {
+        let total = self.buy_volume + self.sell_volume;
+        if total > 0.0 { self.delta() / total } else { 0.0 }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceSnapshot {
+    /// The series' short candle window (1m candles by default).
     pub candles_1m: Vec<Candle>,
+    /// The series' long candle window (5m candles by default).
     pub candles_5m: Vec<Candle>,
+    /// The windows the candles were fetched with, for labelling them.
+    #[serde(default)]
+    pub candle_windows: CandleWindows,
     pub spot_price: f64,
     pub indicators: PriceIndicators,
+    /// Perpetual futures mark and funding, when the feed has them.
+    pub perp: Option<PerpQuote>,
+    /// (perp mark − spot) / spot, in percent.
+    pub basis_pct: Option<f64>,
+}
+
+/// Spot candle history fetched for a series: a short window for the recent
+/// move and a long one for the trend, sized to the market's horizon. The
+/// default suits 15-minute markets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CandleWindows {
+    pub short_interval: String,
+    pub short_limit: u32,
+    pub long_interval: String,
+    pub long_limit: u32,
+}
+
+impl Default for CandleWindows {
+    fn default() -> Self {
+        Self { short_interval: "1m".into(), short_limit: 15, long_interval: "5m".into(), long_limit: 12 }
+    }
+}
+
+impl CandleWindows {
+    /// Time covered by the short window, as a label ("15m").
+    pub fn short_span(&self) -> String {
+        span_label(&self.short_interval, self.short_limit)
+    }
+
+    /// Time covered by the long window, as a label ("1h").
+    pub fn long_span(&self) -> String {
+        span_label(&self.long_interval, self.long_limit)
+    }
+}
+
+/// Minutes in a Binance-style candle interval ("1m", "4h", "1d", "1w").
+pub fn interval_minutes(interval: &str) -> Option<u64> {
+    let unit = match interval.chars().last()? {
+        'm' => 1,
+        'h' => 60,
+        'd' => 1440,
+        'w' => 10080,
+        _ => return None,
+    };
+    let n: u64 = interval[..interval.len() - 1].parse().ok()?;
+    (n > 0).then_some(n * unit)
+}
+
+/// `limit` candles of `interval` as the largest whole unit ("12 x 5m" → "1h").
+fn span_label(interval: &str, limit: u32) -> String {
+    let Some(minutes) = interval_minutes(interval).map(|m| m * limit as u64) else {
+        return format!("{}x{}", limit, interval);
+    };
+    if minutes.is_multiple_of(1440) {
+        format!("{}d", minutes / 1440)
+    } else if minutes.is_multiple_of(60) {
+        format!("{}h", minutes / 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Perpetual futures state for a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerpQuote {
+    pub mark_price: f64,
+    /// Last funding rate, in percent per funding interval (positive: longs pay).
+    pub funding_rate_pct: f64,
+    /// Next funding time (unix millis).
+    pub next_funding_ms: i64,
+}
+
+/// A streamed spot price, as pushed to `PriceFeed::subscribe` consumers.
+#[derive(Debug, Clone)]
+pub struct PriceTick {
+    pub symbol: String,
+    pub price: f64,
 }
 
 // ── Orders & Positions ──
 
-#[derive(Debug)]
-pub struct OrderResult {
-    pub order_id: String,
-    pub status: String,
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderStatus {
+    Resting,
+    Executed,
+    Canceled,
+    Pending,
+    Rejected,
+    Unknown(String),
 }
 
-#[derive(Debug)]
-pub struct OrderRequest {
-    pub ticker: String,
-    pub side: Side,
-    pub shares: u32,
-    pub price_cents: u32,
+impl OrderStatus {
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "resting" => OrderStatus::Resting,
+            "executed" => OrderStatus::Executed,
+            "canceled" | "cancelled" => OrderStatus::Canceled,
+            "pending" => OrderStatus::Pending,
+            "rejected" => OrderStatus::Rejected,
+            other => OrderStatus::Unknown(other.to_string()),
+        }
+    }
 }
 
-#[derive(Debug)]
-pub struct RestingOrder {
-    pub order_id: String,
-    pub ticker: String,
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderStatus::Resting => write!(f, "resting"),
+            OrderStatus::Executed => write!(f, "executed"),
+            OrderStatus::Canceled => write!(f, "canceled"),
+            OrderStatus::Pending => write!(f, "pending"),
+            OrderStatus::Rejected => write!(f, "rejected"),
+            OrderStatus::Unknown(s) => write!(f, "unknown({})", s),
+        }
+    }
 }
 
 #[derive(Debug)]
-pub struct Position {
-    pub ticker: String,
-    pub side: Side,
-    pub count: u32,
+pub struct OrderResult {
+    pub order_id: String,
+    pub status: OrderStatus,
+    /// Exchange creation time (unix millis), when the response carries it.
+    pub created_ms: Option<i64>,
 }
 
+/// Outcome of a reduce-only sell (`Exchange::sell_order`).
 #[derive(Debug)]
-pub struct Settlement {
-    pub ticker: String,
-    pub side: Side,
-    pub count: u32,
-    pub price_cents: u32,
-    pub result: String,
-    pub pnl_cents: i64,
-    pub settled_time: String,
-    pub market_result: String,
+pub struct SellResult {
+    pub order: OrderResult,
+    /// Shares held on the order's side when the sell was placed.
+    pub held: u32,
+    /// Shares still open once the sell fills completely.
+    pub remaining: u32,
 }
 
-// ── WebSocket Events ──
-
+/// Exchange trading state and scheduled maintenance (`Exchange::exchange_status`).
 #[derive(Debug, Clone)]
-pub struct OrderbookUpdate {
-    pub ticker: String,
-    pub yes: Vec<(u32, u32)>,
-    pub no: Vec<(u32, u32)>,
+pub struct ExchangeStatus {
+    pub exchange_active: bool,
+    pub trading_active: bool,
+    /// Exchange's estimate of when trading resumes, while halted.
+    pub resume_time: Option<String>,
+    /// Scheduled maintenance windows, (start, end) in RFC 3339.
+    pub maintenance_windows: Vec<(String, String)>,
+}
+
+impl ExchangeStatus {
+    /// Why entries should wait at `now`, or None if trading is open.
+    pub fn entry_block(&self, now: chrono::DateTime<chrono::Utc>) -> Option<String> {
+        if !self.exchange_active || !self.trading_active {
+            let what = if self.exchange_active { "trading halted" } else { "exchange inactive" };
+            return Some(match &self.resume_time {
+                Some(t) => format!("{} (resumes ~{})", what, t),
+                None => what.to_string(),
+            });
+        }
+        let parse = |t: &str| chrono::DateTime::parse_from_rfc3339(t).ok();
+        self.maintenance_windows.iter().find_map(|(start, end)| {
+            let (s, e) = (parse(start)?, parse(end)?);
+            (s <= now && now < e).then(|| format!("maintenance until {}", end))
+        })
+    }
 }
 
+/// Exchange-side state of one order (`Exchange::get_order`, or pushed on
+/// the `user_orders` channel on every acknowledgement, fill, cancel, or expiry).
 #[derive(Debug, Clone)]
-pub struct FillEvent {
+pub struct OrderState {
     pub order_id: String,
     pub ticker: String,
+    pub status: OrderStatus,
     pub side: Side,
-    pub shares: u32,
+    /// Contracts filled so far.
+    pub filled: u32,
+    /// Contracts still resting.
+    pub remaining: u32,
+    /// Limit price on the order's own side.
     pub price_cents: u32,
 }
 
+/// Estimated place of a resting buy in its side's bid queue
+/// (`Exchange::queue_position`).
 #[derive(Debug, Clone)]
-pub struct MarketLifecycleEvent {
+pub struct QueuePosition {
+    pub order_id: String,
     pub ticker: String,
-    pub status: String,
-    pub result: Option<String>,
+    pub side: Side,
+    pub price_cents: u32,
+    pub filled: u32,
+    pub remaining: u32,
+    /// Contracts that fill before ours: better-priced bids plus the rest of
+    /// our level, assuming we're at its back — an upper bound.
+    pub ahead: u32,
+    /// Best bid and ask on the order's side, if the book has them.
+    pub best_bid: Option<u32>,
+    pub best_ask: Option<u32>,
 }
 
-// ── Position Management (TP/SL) ──
+impl QueuePosition {
+    /// Kalshi books list bids only; the ask on one side is 100 minus the
+    /// best bid on the other.
+    pub fn estimate(order: &OrderState, book: &Orderbook) -> Self {
+        let (bids, opposite) = match order.side {
+            Side::Yes => (&book.yes, &book.no),
+            Side::No => (&book.no, &book.yes),
+        };
+        let better: u32 = bids.iter().filter(|(p, _)| *p > order.price_cents).map(|(_, q)| q).sum();
+        let level: u32 = bids.iter().filter(|(p, _)| *p == order.price_cents).map(|(_, q)| q).sum();
+        QueuePosition {
+            order_id: order.order_id.clone(),
+            ticker: order.ticker.clone(),
+            side: order.side.clone(),
+            price_cents: order.price_cents,
+            filled: order.filled,
+            remaining: order.remaining,
+            ahead: better + level.saturating_sub(order.remaining),
+            best_bid: bids.iter().filter(|(_, q)| *q > 0).map(|(p, _)| *p).max(),
+            best_ask: opposite.iter().filter(|(_, q)| *q > 0).map(|(p, _)| 100 - p).min(),
+        }
+    }
+}
 
-#[derive(Debug, Clone)]
-pub struct OpenPosition {
-    pub ticker: String,
-    pub side: Side,
-    pub shares: u32,
-    pub entry_price_cents: u32,
+/// The exchange accepted the request but rejected the order.
+#[derive(Debug)]
+pub struct OrderRejected {
     pub order_id: String,
-    pub entered_at: String,
+    pub ticker: String,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum ExitReason {
-    TakeProfit,
-    StopLoss,
-    Settlement,
+impl fmt::Display for OrderRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "order {} on {} rejected by exchange", self.order_id, self.ticker)
+    }
 }
 
-impl fmt::Display for ExitReason {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl std::error::Error for OrderRejected {}
+
+/// A Kalshi REST call the exchange answered with an error, classified from
+/// the status and error code so callers can branch without string matching.
+/// Each variant carries the full request/response detail for logging.
+#[derive(Debug)]
+pub enum KalshiError {
+    /// 429 after all retries.
+    RateLimited(String),
+    /// Bad or expired key, bad signature, or missing permission (401/403).
+    Unauthorized(String),
+    InsufficientBalance(String),
+    /// The market or exchange isn't accepting orders right now.
+    MarketClosed(String),
+    /// The exchange refused the order as submitted (bad price, size, …).
+    InvalidOrder(String),
+    /// An order with this `client_order_id` already exists.
+    DuplicateOrder(String),
+    /// A post-only order would have crossed the spread.
+    PostOnlyCross(String),
+    NotFound(String),
+    /// 5xx after all retries.
+    Server(String),
+    Other(String),
+}
+
+impl KalshiError {
+    fn detail(&self) -> &str {
         match self {
-            ExitReason::TakeProfit => write!(f, "take_profit"),
-            ExitReason::StopLoss => write!(f, "stop_loss"),
-            ExitReason::Settlement => write!(f, "settlement"),
+            KalshiError::RateLimited(d)
+            | KalshiError::Unauthorized(d)
+            | KalshiError::InsufficientBalance(d)
+            | KalshiError::MarketClosed(d)
+            | KalshiError::InvalidOrder(d)
+            | KalshiError::DuplicateOrder(d)
+            | KalshiError::PostOnlyCross(d)
+            | KalshiError::NotFound(d)
+            | KalshiError::Server(d)
+            | KalshiError::Other(d) => d,
         }
     }
+
+    /// The order definitely wasn't placed and retrying it as-is won't help.
+    pub fn is_order_refusal(&self) -> bool {
+        matches!(
+            self,
+            KalshiError::InsufficientBalance(_)
+                | KalshiError::MarketClosed(_)
+                | KalshiError::InvalidOrder(_)
+                | KalshiError::PostOnlyCross(_)
+        )
+    }
 }
 
-#[derive(Debug, Clone)]
-pub struct ExitEvent {
-    pub ticker: String,
-    pub reason: ExitReason,
-    pub entry_price_cents: u32,
-    pub exit_price_cents: u32,
-    pub shares: u32,
-    pub pnl_cents: i64,
-    pub order_id: String,
+impl fmt::Display for KalshiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.detail())
+    }
 }
 
-// ── Stats ──
+impl std::error::Error for KalshiError {}
 
+/// A price feed request that failed, split so callers can tell a blip from
+/// an outage. A feed's `Ok(None)` still means "no data", not a failure.
 #[derive(Debug)]
-pub struct Stats {
-    pub total_trades: u32,
-    pub wins: u32,
-    pub losses: u32,
-    pub win_rate: f64,
-    pub total_pnl_cents: i64,
-    pub today_pnl_cents: i64,
-    pub current_streak: i32,
-    pub max_drawdown_cents: i64,
-    pub avg_win_cents: f64,
-    pub avg_loss_cents: f64,
+pub enum PriceFeedError {
+    /// Failed after all retries; the next cycle may well succeed.
+    Transient(String),
+    /// The source is refusing us (rate-limit or IP ban) for `retry_in_secs`;
+    /// cycles that need it should be skipped until then.
+    Down { detail: String, retry_in_secs: u64 },
 }
 
-// ── Prompt Context ──
+impl PriceFeedError {
+    /// Whether `err` is a feed reporting itself down.
+    pub fn is_down(err: &anyhow::Error) -> bool {
+        matches!(err.downcast_ref::<PriceFeedError>(), Some(PriceFeedError::Down { .. }))
+    }
+}
+
+impl fmt::Display for PriceFeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PriceFeedError::Transient(detail) => write!(f, "{}", detail),
+            PriceFeedError::Down { detail, retry_in_secs } => {
+                write!(f, "{} — feed down for {}s", detail, retry_in_secs)
+            }
+        }
+    }
+}
 
+impl std::error::Error for PriceFeedError {}
+
+/// A sell found no position held on that side, so it was refused before
+/// reaching the exchange.
 #[derive(Debug)]
-pub struct DecisionContext {
-    pub prompt_md: String,
-    pub stats: Stats,
-    pub last_n_trades: Vec<LedgerRow>,
-    pub market: MarketState,
-    pub orderbook: Orderbook,
-    pub crypto_price: Option<PriceSnapshot>,
-    pub crypto_label: String,
-    pub signal_summary: Option<SignalSummary>,
+pub struct SellExceedsPosition {
+    pub ticker: String,
+    pub side: Side,
+    pub requested: u32,
+    pub held: u32,
 }
 
-/// Map a Kalshi series ticker to its Binance symbol.
-pub fn series_to_binance_symbol(series: &str) -> &str {
-    match series {
-        "KXBTC15M" => "BTCUSDT",
-        "KXETH15M" => "ETHUSDT",
-        "KXSOL15M" => "SOLUSDT",
-        _ => "BTCUSDT",
+impl fmt::Display for SellExceedsPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sell of {} {} on {} exceeds the {} held",
+            self.requested, self.side, self.ticker, self.held
+        )
     }
 }
 
-/// Map a Kalshi series ticker to a short asset label.
-pub fn series_to_asset_label(series: &str) -> &str {
-    match series {
-        "KXBTC15M" => "BTC",
-        "KXETH15M" => "ETH",
-        "KXSOL15M" => "SOL",
-        _ => "UNKNOWN",
+impl std::error::Error for SellExceedsPosition {}
+
+/// Shutdown was requested before the cycle reached order placement.
+#[derive(Debug)]
+pub struct ShutdownRequested;
+
+impl fmt::Display for ShutdownRequested {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "shutdown requested before order placement")
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct LedgerRow {
-    pub timestamp: String,
-    pub ticker: String,
-    pub side: String,
-    pub shares: u32,
-    pub price: u32,
-    pub result: String,
-    pub pnl_cents: i64,
-    pub cumulative_cents: i64,
-    pub order_id: String,
+impl std::error::Error for ShutdownRequested {}
+
+/// What an entry cycle did, so the daemon can track the order.
+#[derive(Debug)]
+pub enum EntryOutcome {
+    NoTrade,
+    /// Paper trade recorded in the ledger; nothing to track on the exchange.
+    Paper,
+    /// Filled on placement — open the position without waiting for the WS fill.
+    Filled(FillEvent),
+    /// Resting on the book — cancel if not filled by the fill timeout.
+    Resting { order_id: String, ticker: String },
+    /// BUY held back by the open-position cap — queue it for a free slot.
+    Queued(Box<QueuedEntry>),
 }
 
-// ── Config ──
+/// A validated BUY waiting for a position slot. Only the limit price and the
+/// brain's probability are kept; the entry is re-planned against fresh
+/// quotes before placement.
+#[derive(Debug)]
+pub struct QueuedEntry {
+    pub series: String,
+    pub side: Side,
+    pub shares: u32,
+    pub limit_price_cents: u32,
+    pub win_prob: f64,
+    pub strategy: Strategy,
+    pub expires: chrono::DateTime<chrono::Utc>,
+    pub context: DecisionContext,
+    pub decision: TradeDecision,
+}
 
-pub struct Config {
-    pub max_shares: u32,
-    pub max_daily_loss_cents: i64,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderRequest {
+    pub ticker: String,
+    pub side: Side,
+    pub shares: u32,
+    pub price_cents: u32,
+    /// Idempotency key sent to the exchange; lets the journal find an order
+    /// whose placement response was lost.
+    pub client_order_id: String,
+    #[serde(default)]
+    pub order_type: OrderType,
+    #[serde(default)]
+    pub time_in_force: TimeInForce,
+    /// Only ever add liquidity: the exchange refuses the order rather than
+    /// let it cross the spread.
+    #[serde(default)]
+    pub post_only: bool,
+}
+
+impl OrderRequest {
+    pub fn new(ticker: String, side: Side, shares: u32, price_cents: u32) -> Self {
+        Self {
+            ticker,
+            side,
+            shares,
+            price_cents,
+            client_order_id: uuid::Uuid::new_v4().to_string(),
+            order_type: OrderType::Limit,
+            time_in_force: TimeInForce::Gtc,
+            post_only: false,
+        }
+    }
+}
+
+/// How long an order may rest before the exchange cancels what's unfilled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeInForce {
+    /// Rest until filled or canceled.
+    #[default]
+    Gtc,
+    /// Fill what's available immediately, cancel the rest.
+    Ioc,
+    /// Fill completely immediately or not at all.
+    Fok,
+    /// Rest until this unix time (seconds), then expire.
+    GoodTill(i64),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderType {
+    /// Rest at `price_cents` until filled or canceled.
+    #[default]
+    Limit,
+    /// Take whatever the book offers; `price_cents` is only a worst-price floor.
+    Market,
+}
+
+impl fmt::Display for OrderType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderType::Limit => write!(f, "limit"),
+            OrderType::Market => write!(f, "market"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RestingOrder {
+    pub order_id: String,
+    pub ticker: String,
+    pub side: Side,
+    /// "buy" or "sell".
+    pub action: String,
+    /// Good-till expiry (unix millis); None for orders resting until canceled.
+    pub expires_ms: Option<i64>,
+}
+
+#[derive(Debug)]
+pub struct Position {
+    pub ticker: String,
+    pub side: Side,
+    pub count: u32,
+}
+
+/// Balance, open positions, and resting orders fetched together
+/// (`Exchange::portfolio_snapshot`).
+#[derive(Debug)]
+pub struct PortfolioSnapshot {
+    pub balance_cents: u64,
+    pub positions: Vec<Position>,
+    pub resting_orders: Vec<RestingOrder>,
+}
+
+#[derive(Debug)]
+pub struct Settlement {
+    pub ticker: String,
+    /// Side held into settlement, with its contract count and average cost.
+    pub side: Side,
+    pub count: u32,
+    pub price_cents: u32,
+    pub result: String,
+    /// Gross payout (Kalshi's `revenue`), before cost and fees.
+    pub pnl_cents: i64,
+    pub fee_cents: i64,
+    pub settled_time: String,
+    pub market_result: String,
+}
+
+// ── Venue Quality ──
+
+/// Top-of-book sample for one market, taken each entry cycle.
+#[derive(Debug, Clone)]
+pub struct BookSample {
+    pub timestamp: String,
+    pub ticker: String,
+    /// Yes ask − yes bid in cents; None without a two-sided book.
+    pub spread_cents: Option<u32>,
+    /// Contracts at the best yes bid and best no bid (the yes ask).
+    pub bid_depth: u32,
+    pub ask_depth: u32,
+}
+
+impl BookSample {
+    pub fn from_orderbook(ticker: &str, orderbook: &Orderbook) -> Self {
+        let best = |levels: &[(u32, u32)]| levels.iter().copied().max_by_key(|(p, _)| *p);
+        let yes = best(&orderbook.yes);
+        let no = best(&orderbook.no);
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            ticker: ticker.to_string(),
+            spread_cents: match (yes, no) {
+                (Some((bid, _)), Some((no_bid, _))) => Some((100 - no_bid).saturating_sub(bid)),
+                _ => None,
+            },
+            bid_depth: yes.map(|(_, q)| q).unwrap_or(0),
+            ask_depth: no.map(|(_, q)| q).unwrap_or(0),
+        }
+    }
+
+    pub fn series(&self) -> &str {
+        ticker_series(&self.ticker)
+    }
+}
+
+// ── Equity Curve ──
+
+/// Account equity at a point in time: cash balance plus open positions
+/// marked at what they would sell for now.
+#[derive(Debug, Clone)]
+pub struct EquitySample {
+    pub timestamp: String,
+    pub balance_cents: u64,
+    pub open_positions: u32,
+    /// Open positions at cost.
+    pub cost_basis_cents: i64,
+    /// Open positions at the best bid for their side (cost when no book yet).
+    pub marked_value_cents: i64,
+}
+
+impl EquitySample {
+    pub fn equity_cents(&self) -> i64 {
+        self.balance_cents as i64 + self.marked_value_cents
+    }
+}
+
+/// One push of gauges to the shared metrics collector, labelled with the
+/// instance that produced them (`instance`, `mode`, `series`).
+#[derive(Debug, Clone)]
+pub struct MetricsSample {
+    pub labels: Vec<(&'static str, String)>,
+    pub gauges: Vec<(&'static str, f64)>,
+    /// Gauges with their own labels on top of the instance ones, e.g. one
+    /// series per Kalshi endpoint.
+    pub labelled: Vec<LabelledGauge>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LabelledGauge {
+    pub name: &'static str,
+    pub labels: Vec<(&'static str, String)>,
+    pub value: f64,
+}
+
+// ── Order Journal ──
+
+/// One line of the write-ahead order journal, keyed by client order id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum JournalEvent {
+    /// Written before the order REST call.
+    Submitted { order: OrderRequest },
+    /// The exchange acknowledged the order.
+    Placed {
+        client_order_id: String,
+        order_id: String,
+        /// Exchange creation time (unix millis).
+        #[serde(default)]
+        created_ms: Option<i64>,
+    },
+    /// The ledger row exists — nothing left to recover.
+    Recorded { client_order_id: String },
+    /// The order never reached the book (rejected, canceled, or not found).
+    Abandoned { client_order_id: String },
+    /// Audited manual correction of a ledger row (`settle`/`amend` commands).
+    Amended {
+        order_id: String,
+        by: String,
+        reason: String,
+        // Boxed: two full rows would dwarf the other variants
+        before: Box<LedgerRow>,
+        after: Box<LedgerRow>,
+    },
+}
+
+/// A submitted order with no `Recorded`/`Abandoned` entry — the process
+/// died somewhere between the REST call and the ledger write.
+#[derive(Debug, Clone)]
+pub struct UnresolvedOrder {
+    pub order: OrderRequest,
+    pub order_id: Option<String>,
+}
+
+/// Unix millis as an RFC 3339 timestamp, the format used in ledger and state files.
+pub fn millis_to_rfc3339(ms: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp_millis(ms).map(|t| t.to_rfc3339())
+}
+
+// ── WebSocket Events ──
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderbookUpdate {
+    pub ticker: String,
+    pub yes: Vec<(u32, u32)>,
+    pub no: Vec<(u32, u32)>,
+    /// Exchange timestamp (unix millis); None if the message had none.
+    #[serde(default)]
+    pub exchange_ts_ms: Option<i64>,
+}
+
+impl OrderbookUpdate {
+    /// True if this update is older than `other` by exchange time — a
+    /// backlogged message delivered after a newer one.
+    pub fn is_older_than(&self, other: &OrderbookUpdate) -> bool {
+        matches!((self.exchange_ts_ms, other.exchange_ts_ms), (Some(a), Some(b)) if a < b)
+    }
+}
+
+/// Top-of-book quote from the `ticker_v2` channel. Kalshi sends only what
+/// changed, so every field is optional.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TickerUpdate {
+    pub ticker: String,
+    pub yes_bid: Option<u32>,
+    pub yes_ask: Option<u32>,
+    pub last_price: Option<u32>,
+    /// Total contracts traded, when sent in full.
+    pub volume: Option<u64>,
+    /// Contracts traded since the previous update.
+    pub volume_delta: Option<i64>,
+    pub exchange_ts_ms: Option<i64>,
+}
+
+/// The account's position on a market as the exchange sees it
+/// (`market_positions` channel), after any change — ours or a manual trade.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionUpdate {
+    pub ticker: String,
+    /// Contracts held: positive YES, negative NO, 0 flat.
+    pub position: i64,
+    /// Average cost per contract in cents, when the message carries a cost basis.
+    pub avg_price_cents: Option<u32>,
+    pub exchange_ts_ms: Option<i64>,
+}
+
+/// A public trade on a market (`trade` channel).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractTrade {
+    pub ticker: String,
+    pub yes_price: u32,
+    pub count: u32,
+    /// Side that crossed the spread: YES takers are buying pressure.
+    pub taker_side: Side,
+    pub exchange_ts_ms: Option<i64>,
+}
+
+/// Taker volume on one contract over a recent window.
+#[derive(Debug, Clone, Default)]
+pub struct TradeFlow {
+    pub trades: u32,
+    pub yes_taker_contracts: u32,
+    pub no_taker_contracts: u32,
+}
+
+impl TradeFlow {
+    pub fn from_trades<'a>(trades: impl IntoIterator<Item = &'a ContractTrade>) -> Self {
+        trades.into_iter().fold(TradeFlow::default(), |mut flow, t| {
+            flow.trades += 1;
+            match t.taker_side {
+                Side::Yes => flow.yes_taker_contracts += t.count,
+                Side::No => flow.no_taker_contracts += t.count,
+            }
+            flow
+        })
+    }
+
+    /// (YES − NO) / total taker contracts, or None with no volume.
+    pub fn imbalance(&self) -> Option<f64> {
+        let total = self.yes_taker_contracts + self.no_taker_contracts;
+        (total > 0).then(|| {
+            (self.yes_taker_contracts as f64 - self.no_taker_contracts as f64) / total as f64
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FillEvent {
+    pub order_id: String,
+    pub ticker: String,
+    pub side: Side,
+    pub shares: u32,
+    pub price_cents: u32,
+    /// Exchange fill time (unix millis); None if the message had none.
+    pub exchange_ts_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketLifecycleEvent {
+    pub ticker: String,
+    pub status: String,
+    pub result: Option<String>,
+    /// Exchange timestamp (unix millis); None if the message had none.
+    #[serde(default)]
+    pub exchange_ts_ms: Option<i64>,
+}
+
+// ── Market Data Recording ──
+
+/// One line of a market-data recording, in the order it was received.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Local receive time (unix millis).
+    pub recv_ms: i64,
+    #[serde(flatten)]
+    pub event: MarketDataEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MarketDataEvent {
+    /// Kalshi orderbook levels for one market.
+    Orderbook(OrderbookUpdate),
+    /// Kalshi market status change (open, closed, settled, …).
+    Lifecycle(MarketLifecycleEvent),
+    /// Binance 1m kline; `closed` marks the final update for the candle.
+    Kline {
+        symbol: String,
+        event_time_ms: i64,
+        closed: bool,
+        candle: Candle,
+    },
+    /// A WS frame as received (`RECORD_RAW_WS`), from "kalshi" or "binance".
+    Raw { source: String, frame: String },
+}
+
+// ── Position Management (TP/SL) ──
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenPosition {
+    pub ticker: String,
+    pub side: Side,
+    pub shares: u32,
+    pub entry_price_cents: u32,
+    pub order_id: String,
+    pub entered_at: String,
+    /// Held on the account but not opened by this bot (manual trade, other
+    /// series, another instance): tracked, never exited.
+    #[serde(default)]
+    pub track_only: bool,
+}
+
+/// What a handing-off instance passes its standby (`brain/positions.json`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HandoffSnapshot {
+    pub positions: Vec<OpenPosition>,
+    /// Resting entry orders and the seconds left on each one's fill timeout.
+    #[serde(default)]
+    pub resting_orders: Vec<(String, u64)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExitReason {
+    TakeProfit,
+    StopLoss,
+    Settlement,
+}
+
+impl fmt::Display for ExitReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExitReason::TakeProfit => write!(f, "take_profit"),
+            ExitReason::StopLoss => write!(f, "stop_loss"),
+            ExitReason::Settlement => write!(f, "settlement"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExitEvent {
+    pub ticker: String,
+    pub reason: ExitReason,
+    pub entry_price_cents: u32,
+    pub exit_price_cents: u32,
+    pub shares: u32,
+    pub pnl_cents: i64,
+    pub order_id: String,
+}
+
+// ── Decision Audit ──
+
+/// Everything that went into an entry decision, persisted per order.
+#[derive(Debug, Serialize)]
+pub struct DecisionRecord<'a> {
+    pub timestamp: String,
+    pub order_id: &'a str,
+    pub ticker: &'a str,
+    pub strategy: Strategy,
+    pub prompt: &'a str,
+    pub raw_response: &'a str,
+    pub decision: &'a TradeDecision,
+    pub signal_summary: Option<&'a SignalSummary>,
+    pub orderbook: &'a Orderbook,
+    /// Full context snapshot, so the decision can be replayed (`redecide`).
+    pub context: &'a DecisionContext,
+    pub entry_style: String,
+    pub entry_price_cents: u32,
+    pub shares: u32,
+}
+
+// ── Stats ──
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Stats {
+    pub total_trades: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub win_rate: f64,
+    pub total_pnl_cents: i64,
+    pub today_pnl_cents: i64,
+    pub current_streak: i32,
+    pub max_drawdown_cents: i64,
+    pub avg_win_cents: f64,
+    pub avg_loss_cents: f64,
+}
+
+// ── Prompt Context ──
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DecisionContext {
+    pub prompt_md: String,
+    pub stats: Stats,
+    pub last_n_trades: Vec<LedgerRow>,
+    pub market: MarketState,
+    /// Every strike in the market's event, lowest first; empty if unavailable.
+    #[serde(default)]
+    pub strikes: Vec<MarketState>,
+    /// The market's own 1m candles over the last 15 minutes, oldest first.
+    #[serde(default)]
+    pub contract_candles: Vec<ContractCandle>,
+    pub orderbook: Orderbook,
+    pub crypto_price: Option<PriceSnapshot>,
+    /// Annualized implied vol (%) of the underlying, e.g. Deribit DVOL.
+    #[serde(default)]
+    pub implied_vol: Option<f64>,
+    pub crypto_label: String,
+    pub signal_summary: Option<SignalSummary>,
+}
+
+/// Series prefix of a market ticker ("KXBTC15M-26FEB121300-00" → "KXBTC15M").
+pub fn ticker_series(ticker: &str) -> &str {
+    ticker.split('-').next().unwrap_or(ticker)
+}
+
+/// Price feed symbol and display label for the Kalshi series whose ticker
+/// starts with `prefix`, so every horizon of an underlying (KXBTC15M,
+/// KXBTCD, …) shares one symbol and label.
+#[derive(Debug, Clone)]
+pub struct SeriesSymbol {
+    pub prefix: String,
+    /// Binance-style spot symbol, e.g. `BTCUSDT`.
+    pub symbol: String,
+    pub label: String,
+}
+
+impl SeriesSymbol {
+    fn new(prefix: &str, symbol: &str, label: &str) -> Self {
+        Self { prefix: prefix.into(), symbol: symbol.into(), label: label.into() }
+    }
+}
+
+/// Built-in series → symbol table, extended or overridden by SERIES_SYMBOLS.
+fn default_series_symbols() -> Vec<SeriesSymbol> {
+    vec![
+        SeriesSymbol::new("KXBTC", "BTCUSDT", "BTC"),
+        SeriesSymbol::new("KXETH", "ETHUSDT", "ETH"),
+        SeriesSymbol::new("KXSOL", "SOLUSDT", "SOL"),
+    ]
+}
+
+/// Event prefix of a market ticker ("KXBTC15M-26FEB121300-00" → "KXBTC15M-26FEB121300").
+/// All strikes of one event share it.
+pub fn ticker_event(ticker: &str) -> &str {
+    match ticker.rsplit_once('-') {
+        Some((event, _)) if event.contains('-') => event,
+        _ => ticker,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerRow {
+    pub timestamp: String,
+    pub ticker: String,
+    pub side: String,
+    pub shares: u32,
+    pub price: u32,
+    pub result: String,
+    pub pnl_cents: i64,
+    pub cumulative_cents: i64,
+    pub order_id: String,
+    /// Signal summary narrative at entry (empty for older rows).
+    #[serde(default)]
+    pub signal: String,
+}
+
+impl LedgerRow {
+    /// Series this trade belongs to, derived from the market ticker.
+    pub fn series(&self) -> &str {
+        ticker_series(&self.ticker)
+    }
+
+    /// Cost basis of the trade in cents.
+    pub fn cost_cents(&self) -> i64 {
+        self.price as i64 * self.shares as i64
+    }
+}
+
+/// A manual correction to one ledger row; unset fields are left unchanged.
+/// Cumulative P&L is never set directly — it follows from `pnl_cents`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LedgerAmendment {
+    pub side: Option<String>,
+    pub shares: Option<u32>,
+    pub price: Option<u32>,
+    pub result: Option<String>,
+    pub pnl_cents: Option<i64>,
+    /// New order id, when an amend replaced the order.
+    pub order_id: Option<String>,
+}
+
+impl LedgerAmendment {
+    pub fn is_empty(&self) -> bool {
+        self.side.is_none()
+            && self.shares.is_none()
+            && self.price.is_none()
+            && self.result.is_none()
+            && self.pnl_cents.is_none()
+            && self.order_id.is_none()
+    }
+
+    /// `row` with the amendment applied, its cumulative P&L shifted by the
+    /// change in `pnl_cents`.
+    pub fn apply(&self, row: &LedgerRow) -> LedgerRow {
+        let pnl_cents = self.pnl_cents.unwrap_or(row.pnl_cents);
+        LedgerRow {
+            side: self.side.clone().unwrap_or_else(|| row.side.clone()),
+            shares: self.shares.unwrap_or(row.shares),
+            price: self.price.unwrap_or(row.price),
+            result: self.result.clone().unwrap_or_else(|| row.result.clone()),
+            pnl_cents,
+            cumulative_cents: row.cumulative_cents + pnl_cents - row.pnl_cents,
+            order_id: self.order_id.clone().unwrap_or_else(|| row.order_id.clone()),
+            ..row.clone()
+        }
+    }
+}
+
+/// Ledger `result` classes for `QueryFilter`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResultFilter {
+    Pending,
+    Win,
+    Loss,
+    /// Settled at expiry: win or loss.
+    Settled,
+    /// Any early exit (`exit_take_profit`, `exit_stop_loss`, …).
+    Exit,
+    Cancelled,
+}
+
+impl ResultFilter {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(ResultFilter::Pending),
+            "win" => Some(ResultFilter::Win),
+            "loss" => Some(ResultFilter::Loss),
+            "settled" => Some(ResultFilter::Settled),
+            "exit" => Some(ResultFilter::Exit),
+            "cancelled" => Some(ResultFilter::Cancelled),
+            _ => None,
+        }
+    }
+
+    pub fn matches(&self, result: &str) -> bool {
+        match self {
+            ResultFilter::Pending => result == "pending",
+            ResultFilter::Win => result == "win",
+            ResultFilter::Loss => result == "loss",
+            ResultFilter::Settled => result == "win" || result == "loss",
+            ResultFilter::Exit => result.starts_with("exit_"),
+            ResultFilter::Cancelled => result == "cancelled",
+        }
+    }
+}
+
+/// Ledger query; unset fields match everything. `from` is inclusive, `to`
+/// exclusive.
+#[derive(Debug, Clone, Default)]
+pub struct QueryFilter {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub ticker: Option<String>,
+    pub series: Option<String>,
+    pub result: Option<ResultFilter>,
+    pub side: Option<Side>,
+}
+
+impl QueryFilter {
+    pub fn matches(&self, row: &LedgerRow) -> bool {
+        if self.ticker.as_ref().is_some_and(|t| *t != row.ticker)
+            || self.series.as_ref().is_some_and(|s| s != row.series())
+            || self.result.is_some_and(|r| !r.matches(&row.result))
+            || self.side.as_ref().is_some_and(|s| s.to_string() != row.side)
+        {
+            return false;
+        }
+        if self.from.is_none() && self.to.is_none() {
+            return true;
+        }
+        // Rows without a parseable timestamp can't be placed in a range
+        let Ok(ts) = chrono::DateTime::parse_from_rfc3339(&row.timestamp) else {
+            return false;
+        };
+        self.from.is_none_or(|from| ts >= from) && self.to.is_none_or(|to| ts < to)
+    }
+}
+
+// ── Config ──
+
+pub struct Config {
+    pub max_shares: u32,
+    pub max_daily_loss_cents: i64,
     pub max_consecutive_losses: u32,
+    /// Cap on open cost basis per event (all strikes, both sides).
+    pub max_event_notional_cents: i64,
+    /// Cap on open cost basis per underlying asset across series.
+    pub max_asset_notional_cents: i64,
+    /// Open positions plus resting entries across all series (0 = no cap).
+    /// BUYs past the cap wait in the entry queue for a free slot.
+    pub max_open_positions: usize,
+    /// How long a queued BUY stays valid.
+    pub entry_queue_ttl_secs: u64,
     pub min_balance_cents: u64,
     pub min_minutes_to_expiry: f64,
     pub paper_trade: bool,
     pub confirm_live: bool,
     pub series_tickers: Vec<String>,
+    /// Series discovery when `series_tickers` is empty: Kalshi category
+    /// and frequency to pick series from.
+    pub series_category: String,
+    pub series_frequency: String,
+    /// Selects the default hosts and which key pair is loaded.
+    pub kalshi_env: KalshiEnv,
     pub kalshi_base_url: String,
     pub openrouter_api_key: String,
+    pub openrouter_model: String,
     pub kalshi_key_id: String,
     pub kalshi_private_key_pem: String,
+    pub kalshi_auth_mode: KalshiAuthMode,
+    pub kalshi_api_token: String,
+    pub kalshi_email: String,
+    pub kalshi_password: String,
+    /// The other environment's key pair, if configured — only loaded so
+    /// startup can refuse to run when the two are the same.
+    pub kalshi_other_env_key_id: String,
+    pub kalshi_other_env_key_pem: String,
+    // Kalshi REST rate limits (token buckets shared by all requests)
+    pub kalshi_reads_per_sec: f64,
+    pub kalshi_writes_per_sec: f64,
+    // Kalshi REST retries: exponential backoff with jitter
+    pub kalshi_max_retries: u32,
+    pub kalshi_retry_base_ms: u64,
+    pub kalshi_retry_max_ms: u64,
+    /// Retries per Binance REST request on 429/5xx/connect errors (backoff as Kalshi's).
+    pub binance_max_retries: u32,
+    /// How long a series' open-market listing (tickers, expiries, titles) is
+    /// reused before re-listing; in between only quotes are refreshed. 0 = off.
+    pub kalshi_market_cache_secs: u64,
+    /// How often request signing re-measures the offset to Kalshi's clock.
+    pub kalshi_clock_sync_secs: u64,
+    // Kalshi REST connection pool: reuse connections across concurrent cycles
+    pub kalshi_pool_max_idle: usize,
+    pub kalshi_pool_idle_secs: u64,
+    /// TCP (and HTTP/2 ping) keepalive interval. 0 = off.
+    pub kalshi_keepalive_secs: u64,
+    /// Offer HTTP/2 so concurrent requests multiplex over one connection.
+    pub kalshi_http2: bool,
     // v2: TP/SL
     pub tp_cents_per_share: u32,
     pub sl_cents_per_share: u32,
+    /// Stop-loss exits go out as market orders when the exit spread is at least this wide.
+    pub market_exit_spread_cents: u32,
     // v2: WebSocket URLs
     pub kalshi_ws_url: String,
-    pub binance_ws_url: String,
+    /// Binance kline stream URLs, tried in order: the next is used when one
+    /// can't be reached (geo-block, timeout).
+    pub binance_ws_urls: Vec<String>,
+    /// Binance spot REST roots, with the same fallback.
+    pub binance_rest_urls: Vec<String>,
+    /// Sources of spot prices and candles (`PRICE_FEED`); with more than
+    /// one, the median across them is used.
+    pub price_feeds: Vec<PriceSource>,
+    /// Spread between sources, as % of the median, that gets logged.
+    pub price_divergence_pct: f64,
+    /// Feed to fail over to when the primary keeps missing (`PRICE_FEED_FALLBACK`).
+    pub price_feed_fallback: Option<PriceSource>,
+    /// Consecutive primary misses before failing over.
+    pub price_failover_after: u32,
+    /// Directory for the on-disk candle cache; None disables.
+    pub candle_cache_dir: Option<String>,
+    /// Days of closed candles kept in the cache.
+    pub candle_cache_days: u64,
+    /// Window for the spot buy/sell volume delta (`SPOT_FLOW_WINDOW_SECS`).
+    pub spot_flow_window_secs: u64,
+    /// Binance USDⓈ-M futures REST root for funding and mark price; None disables.
+    pub binance_futures_url: Option<String>,
+    /// Deribit REST root for DVOL implied vol; None disables.
+    pub deribit_url: Option<String>,
+    /// Client ping interval on the Kalshi WS.
+    pub kalshi_ws_ping_secs: u64,
+    /// Reconnect when the Kalshi WS has been silent this long — no data,
+    /// pings, or pongs — instead of waiting on a dead TCP connection.
+    pub kalshi_ws_idle_secs: u64,
+    /// Backoff between WS reconnect attempts (both feeds): the first delay
+    /// ceiling, doubling up to the max, jittered.
+    pub ws_reconnect_initial_ms: u64,
+    pub ws_reconnect_max_secs: u64,
+    /// Halt entries after this many failed reconnects in a row on either
+    /// feed, until it reconnects (0 = never).
+    pub ws_reconnect_halt_after: u32,
+    /// Halt a series' entries and reconnect the Binance WS when no price for
+    /// its symbol has arrived in this long (0 = off).
+    pub price_stale_secs: u64,
     // v2: Daemon intervals
     pub entry_cycle_interval_secs: u64,
     pub position_check_interval_secs: u64,
+    /// Pre-fetch the next market this long before the current one expires (0 = off).
+    pub rollover_lead_secs: u64,
+    // Maker/taker entry selection
+    pub min_fill_probability: f64,
+    pub maker_horizon_secs: u64,
+    pub fill_timeout_secs: u64,
+    /// Halfway to the fill timeout, reprice a resting entry with more than
+    /// this many contracts estimated ahead of it. 0 = off.
+    pub reprice_queue_ahead: u32,
+    /// Most a reprice raises an entry's limit price.
+    pub reprice_max_cents: u32,
+    // Alerts
+    pub telegram_bot_token: String,
+    pub telegram_chat_id: String,
+    // Market selection overrides
+    pub market_overrides: MarketOverrides,
+    // Quant strategy per series (default momentum)
+    pub series_strategies: HashMap<String, Strategy>,
+    // Spot candle windows per series (default 1m x 15 and 5m x 12)
+    pub series_candles: HashMap<String, CandleWindows>,
+    // Series ticker prefix → price feed symbol and label
+    pub series_symbols: Vec<SeriesSymbol>,
+    // Brain routing by market complexity (off when unset)
+    pub model_routing: Option<ModelRouting>,
+    // Off-box backups of brain/
+    pub backup_target: Option<BackupTarget>,
+    pub backup_interval_secs: u64,
+    // Equity curve sampling
+    pub equity_interval_secs: u64,
+    // Metrics push to a shared collector (off when unset)
+    pub metrics_target: Option<MetricsTarget>,
+    pub metrics_instance: String,
+    pub metrics_interval_secs: u64,
+    // Market data recording for replay (off when unset)
+    pub record_dir: Option<String>,
+    /// Tap raw WS frames: into the recording when there is one, else the
+    /// `ws_raw` debug log.
+    pub record_raw_ws: bool,
+    // Shared storage (requires the `postgres` feature)
+    pub database_url: Option<String>,
+}
+
+/// Config-driven steering of the market-selection step.
+#[derive(Debug, Clone, Default)]
+pub struct MarketOverrides {
+    /// If any pinned ticker belongs to a series, only those are traded there.
+    pub pinned: Vec<String>,
+    /// Ticker prefixes to skip: a full ticker, an event, or a partial event
+    /// (e.g. `KXBTC15M-26FEB12` skips that day of one series).
+    pub excluded: Vec<String>,
+    /// Event dates to skip in every series, as `YYMMMDD` (e.g. `26FEB12`).
+    pub excluded_dates: Vec<String>,
+    /// Never trade the highest strike of a multi-strike event.
+    pub exclude_top_strike: bool,
+}
+
+/// Thresholds for sending a decision to the cheap tier instead of
+/// `openrouter_model`. Anything volatile, near the strike, or with
+/// conflicting signals still goes to the expensive model.
+#[derive(Debug, Clone)]
+pub struct ModelRouting {
+    /// OpenRouter model for calm markets; None = quant-only (no LLM call).
+    pub cheap_model: Option<String>,
+    /// Spot within this % of the strike counts as near-strike.
+    pub near_strike_pct: f64,
+    /// 1m return std-dev (%) at or above this counts as high volatility.
+    pub high_vol_pct: f64,
+}
+
+impl ModelRouting {
+    /// Enabled by OPENROUTER_CHEAP_MODEL (a model id, or `quant`).
+    fn from_env() -> Option<Self> {
+        let cheap = std::env::var("OPENROUTER_CHEAP_MODEL").ok().filter(|v| !v.is_empty())?;
+        Some(Self {
+            cheap_model: (cheap != "quant").then_some(cheap),
+            near_strike_pct: std::env::var("ROUTE_NEAR_STRIKE_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.15),
+            high_vol_pct: std::env::var("ROUTE_HIGH_VOL_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.10),
+        })
+    }
+}
+
+/// How requests authenticate (`KALSHI_AUTH`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KalshiAuthMode {
+    /// RSA-signed requests with an API key pair (default).
+    Rsa,
+    /// Bearer token: `KALSHI_API_TOKEN`, or a session from
+    /// `KALSHI_EMAIL`/`KALSHI_PASSWORD`.
+    Token,
+}
+
+/// Which Kalshi environment to trade against (`KALSHI_ENV`). Each has its
+/// own hosts and its own key pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KalshiEnv {
+    Prod,
+    Demo,
+}
+
+impl KalshiEnv {
+    fn from_env() -> Self {
+        match std::env::var("KALSHI_ENV").as_deref() {
+            Ok("demo") => KalshiEnv::Demo,
+            _ => KalshiEnv::Prod,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            KalshiEnv::Prod => "prod",
+            KalshiEnv::Demo => "demo",
+        }
+    }
+
+    pub fn other(self) -> Self {
+        match self {
+            KalshiEnv::Prod => KalshiEnv::Demo,
+            KalshiEnv::Demo => KalshiEnv::Prod,
+        }
+    }
+
+    fn default_base_url(self) -> &'static str {
+        match self {
+            KalshiEnv::Prod => "https://api.elections.kalshi.com",
+            KalshiEnv::Demo => "https://demo-api.kalshi.co",
+        }
+    }
+
+    fn default_ws_url(self) -> &'static str {
+        match self {
+            KalshiEnv::Prod => "wss://api.elections.kalshi.com/trade-api/ws/v2",
+            KalshiEnv::Demo => "wss://demo-api.kalshi.co/trade-api/ws/v2",
+        }
+    }
+
+    /// Env var holding this environment's API key id.
+    pub fn key_id_var(self) -> &'static str {
+        match self {
+            KalshiEnv::Prod => "KALSHI_API_KEY_ID",
+            KalshiEnv::Demo => "KALSHI_DEMO_API_KEY_ID",
+        }
+    }
+
+    /// Env var holding the path to this environment's private key.
+    pub fn key_path_var(self) -> &'static str {
+        match self {
+            KalshiEnv::Prod => "KALSHI_PRIVATE_KEY_PATH",
+            KalshiEnv::Demo => "KALSHI_DEMO_PRIVATE_KEY_PATH",
+        }
+    }
+
+    fn default_key_path(self) -> &'static str {
+        match self {
+            KalshiEnv::Prod => "./kalshi_private_key.pem",
+            KalshiEnv::Demo => "./kalshi_demo_private_key.pem",
+        }
+    }
+
+    /// Key id and PEM contents for this environment (empty when unset).
+    fn key_pair(self) -> (String, String) {
+        let path = std::env::var(self.key_path_var()).unwrap_or_else(|_| self.default_key_path().into());
+        (
+            std::env::var(self.key_id_var()).unwrap_or_default(),
+            std::fs::read_to_string(path).unwrap_or_default(),
+        )
+    }
+
+    /// Whether `url` points at this environment's hosts.
+    pub fn owns_url(self, url: &str) -> bool {
+        url.contains("demo") == (self == KalshiEnv::Demo)
+    }
+}
+
+/// Where periodic backups are uploaded.
+#[derive(Debug, Clone)]
+pub enum BackupTarget {
+    WebDav {
+        url: String,
+        user: String,
+        password: String,
+    },
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+impl BackupTarget {
+    /// S3 if BACKUP_S3_BUCKET is set, else WebDAV if BACKUP_WEBDAV_URL is set.
+    fn from_env() -> Option<Self> {
+        let var = |k: &str| std::env::var(k).unwrap_or_default();
+        if !var("BACKUP_S3_BUCKET").is_empty() {
+            let region = std::env::var("BACKUP_S3_REGION").unwrap_or_else(|_| "us-east-1".into());
+            return Some(BackupTarget::S3 {
+                endpoint: std::env::var("BACKUP_S3_ENDPOINT")
+                    .unwrap_or_else(|_| format!("https://s3.{}.amazonaws.com", region)),
+                bucket: var("BACKUP_S3_BUCKET"),
+                region,
+                access_key: var("BACKUP_S3_ACCESS_KEY"),
+                secret_key: var("BACKUP_S3_SECRET_KEY"),
+            });
+        }
+        if !var("BACKUP_WEBDAV_URL").is_empty() {
+            return Some(BackupTarget::WebDav {
+                url: var("BACKUP_WEBDAV_URL"),
+                user: var("BACKUP_WEBDAV_USER"),
+                password: var("BACKUP_WEBDAV_PASSWORD"),
+            });
+        }
+        None
+    }
+}
+
+/// Which exchange's REST API serves spot prices and candles (`PRICE_FEED`).
+/// The Binance WS stream still drives realized vol either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    Binance,
+    Kraken,
+}
+
+impl PriceSource {
+    /// Comma-separated PRICE_FEED, e.g. `binance,kraken`; Binance when unset.
+    fn list_from_env() -> anyhow::Result<Vec<Self>> {
+        let mut sources = Vec::new();
+        for name in std::env::var("PRICE_FEED").unwrap_or_default().split(',').map(str::trim) {
+            if name.is_empty() {
+                continue;
+            }
+            let Some(source) = PriceSource::parse(name) else {
+                anyhow::bail!("Unknown PRICE_FEED {:?} (expected binance or kraken)", name);
+            };
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        if sources.is_empty() {
+            sources.push(PriceSource::Binance);
+        }
+        Ok(sources)
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "binance" => Some(PriceSource::Binance),
+            "kraken" => Some(PriceSource::Kraken),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PriceSource::Binance => "binance",
+            PriceSource::Kraken => "kraken",
+        }
+    }
+}
+
+/// Where periodic metrics are pushed.
+#[derive(Debug, Clone)]
+pub enum MetricsTarget {
+    Pushgateway { url: String },
+    Otlp { url: String },
+}
+
+impl MetricsTarget {
+    /// Pushgateway if METRICS_PUSHGATEWAY_URL is set, else OTLP/HTTP if
+    /// METRICS_OTLP_URL is set.
+    fn from_env() -> Option<Self> {
+        let var = |k: &str| std::env::var(k).ok().filter(|v| !v.is_empty());
+        if let Some(url) = var("METRICS_PUSHGATEWAY_URL") {
+            return Some(MetricsTarget::Pushgateway { url });
+        }
+        var("METRICS_OTLP_URL").map(|url| MetricsTarget::Otlp { url })
+    }
+}
+
+fn env_list(key: &str) -> Vec<String> {
+    std::env::var(key)
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_uppercase())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Comma-separated URLs from `key`, trailing slashes trimmed; `default` when
+/// unset or empty.
+fn url_list(key: &str, default: &str) -> Vec<String> {
+    let urls: Vec<String> = std::env::var(key)
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().trim_end_matches('/').to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if urls.is_empty() {
+        vec![default.to_string()]
+    } else {
+        urls
+    }
+}
+
+/// Parse SERIES_STRATEGIES, e.g. `KXBTC15M=momentum,KXETH15M=mean-reversion`.
+fn series_strategies_from_env() -> anyhow::Result<HashMap<String, Strategy>> {
+    let mut out = HashMap::new();
+    for entry in env_list("SERIES_STRATEGIES") {
+        let Some((series, name)) = entry.split_once('=') else {
+            anyhow::bail!("SERIES_STRATEGIES entry {:?} is not SERIES=strategy", entry);
+        };
+        let strategy = Strategy::parse(name.trim())
+            .ok_or_else(|| anyhow::anyhow!("Unknown strategy {:?} for {}", name, series))?;
+        out.insert(series.trim().to_string(), strategy);
+    }
+    Ok(out)
+}
+
+/// Parse SERIES_CANDLES, e.g. `KXBTCD=15m:16/1h:24` (short window, then
+/// long, each INTERVAL:COUNT).
+fn series_candles_from_env() -> anyhow::Result<HashMap<String, CandleWindows>> {
+    fn window(spec: &str, entry: &str) -> anyhow::Result<(String, u32)> {
+        // env_list uppercases; Binance intervals are lowercase
+        let (interval, limit) = spec.split_once(':').unwrap_or((spec, ""));
+        let interval = interval.trim().to_lowercase();
+        match limit.trim().parse::<u32>() {
+            Ok(limit) if interval_minutes(&interval).is_some() && (2..=1000).contains(&limit) => Ok((interval, limit)),
+            _ => anyhow::bail!("SERIES_CANDLES window {:?} in {:?} is not INTERVAL:COUNT (count 2-1000)", spec, entry),
+        }
+    }
+
+    let mut out = HashMap::new();
+    for entry in env_list("SERIES_CANDLES") {
+        let Some((series, windows)) = entry.split_once('=') else {
+            anyhow::bail!("SERIES_CANDLES entry {:?} is not SERIES=SHORT/LONG", entry);
+        };
+        let Some((short, long)) = windows.split_once('/') else {
+            anyhow::bail!("SERIES_CANDLES entry {:?} is not SERIES=SHORT/LONG", entry);
+        };
+        let (short_interval, short_limit) = window(short, &entry)?;
+        let (long_interval, long_limit) = window(long, &entry)?;
+        out.insert(
+            series.trim().to_string(),
+            CandleWindows { short_interval, short_limit, long_interval, long_limit },
+        );
+    }
+    Ok(out)
+}
+
+/// The built-in series symbols plus SERIES_SYMBOLS, e.g.
+/// `KXXRP=XRPUSDT:XRP,KXDOGE=DOGEUSDT` (label defaults to the prefix without
+/// `KX`). Sorted longest prefix first, so the most specific entry matches.
+fn series_symbols_from_env() -> anyhow::Result<Vec<SeriesSymbol>> {
+    let mut out = default_series_symbols();
+    for entry in env_list("SERIES_SYMBOLS") {
+        let Some((prefix, target)) = entry.split_once('=') else {
+            anyhow::bail!("SERIES_SYMBOLS entry {:?} is not SERIES=SYMBOL[:LABEL]", entry);
+        };
+        let prefix = prefix.trim();
+        let (symbol, label) = match target.split_once(':') {
+            Some((symbol, label)) => (symbol.trim(), label.trim()),
+            None => (target.trim(), prefix.strip_prefix("KX").unwrap_or(prefix)),
+        };
+        if prefix.is_empty() || symbol.is_empty() || label.is_empty() {
+            anyhow::bail!("SERIES_SYMBOLS entry {:?} is not SERIES=SYMBOL[:LABEL]", entry);
+        }
+        out.retain(|s| s.prefix != prefix);
+        out.push(SeriesSymbol::new(prefix, symbol, label));
+    }
+    out.sort_by_key(|s| std::cmp::Reverse(s.prefix.len()));
+    Ok(out)
 }
 
 impl Config {
+    /// Price symbol and label for a series, by longest matching prefix.
+    pub fn series_symbol(&self, series: &str) -> Option<&SeriesSymbol> {
+        self.series_symbols.iter().find(|s| series.starts_with(&s.prefix))
+    }
+
+    /// Price feed symbol for a series, or None if it has no mapping.
+    pub fn price_symbol(&self, series: &str) -> Option<&str> {
+        self.series_symbol(series).map(|s| s.symbol.as_str())
+    }
+
+    /// Short asset label for a series ("BTC"), or "UNKNOWN".
+    pub fn asset_label(&self, series: &str) -> &str {
+        self.series_symbol(series).map_or("UNKNOWN", |s| s.label.as_str())
+    }
+
+    /// Spot candle windows configured for a series.
+    pub fn candles_for(&self, series: &str) -> CandleWindows {
+        self.series_candles.get(series).cloned().unwrap_or_default()
+    }
+
+    /// Quant strategy configured for a series.
+    pub fn strategy_for(&self, series: &str) -> Strategy {
+        self.series_strategies.get(series).copied().unwrap_or_default()
+    }
+
     pub fn from_env() -> anyhow::Result<Self> {
-        let pem_path = std::env::var("KALSHI_PRIVATE_KEY_PATH")
-            .unwrap_or_else(|_| "./kalshi_private_key.pem".into());
-        let pem = std::fs::read_to_string(&pem_path).unwrap_or_default();
+        let kalshi_env = KalshiEnv::from_env();
+        let (key_id, pem) = kalshi_env.key_pair();
+        let (other_key_id, other_pem) = kalshi_env.other().key_pair();
 
         Ok(Self {
             max_shares: 5,
             max_daily_loss_cents: 1000,
             max_consecutive_losses: 7,
+            max_event_notional_cents: std::env::var("MAX_EVENT_NOTIONAL_CENTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000),
+            max_asset_notional_cents: std::env::var("MAX_ASSET_NOTIONAL_CENTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2000),
+            max_open_positions: std::env::var("MAX_OPEN_POSITIONS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            entry_queue_ttl_secs: std::env::var("ENTRY_QUEUE_TTL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(120),
             min_balance_cents: 500,
             min_minutes_to_expiry: 2.0,
             paper_trade: std::env::var("PAPER_TRADE")
@@ -352,11 +1935,74 @@ impl Config {
                 .map(|s| s.trim().to_string())
                 .filter(|s| !s.is_empty())
                 .collect(),
+            series_category: std::env::var("KALSHI_SERIES_CATEGORY")
+                .unwrap_or_else(|_| "Crypto".into()),
+            series_frequency: std::env::var("KALSHI_SERIES_FREQUENCY")
+                .unwrap_or_else(|_| "fifteen_min".into()),
+            kalshi_env,
             kalshi_base_url: std::env::var("KALSHI_BASE_URL")
-                .unwrap_or_else(|_| "https://api.elections.kalshi.com".into()),
+                .unwrap_or_else(|_| kalshi_env.default_base_url().into()),
             openrouter_api_key: std::env::var("OPENROUTER_API_KEY").unwrap_or_default(),
-            kalshi_key_id: std::env::var("KALSHI_API_KEY_ID").unwrap_or_default(),
+            openrouter_model: std::env::var("OPENROUTER_MODEL")
+                .unwrap_or_else(|_| "anthropic/claude-opus-4-6".into()),
+            kalshi_key_id: key_id,
             kalshi_private_key_pem: pem,
+            kalshi_auth_mode: match std::env::var("KALSHI_AUTH").as_deref() {
+                Ok("token") => KalshiAuthMode::Token,
+                _ => KalshiAuthMode::Rsa,
+            },
+            kalshi_api_token: std::env::var("KALSHI_API_TOKEN").unwrap_or_default(),
+            kalshi_email: std::env::var("KALSHI_EMAIL").unwrap_or_default(),
+            kalshi_password: std::env::var("KALSHI_PASSWORD").unwrap_or_default(),
+            kalshi_other_env_key_id: other_key_id,
+            kalshi_other_env_key_pem: other_pem,
+            kalshi_reads_per_sec: std::env::var("KALSHI_READS_PER_SEC")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10.0),
+            kalshi_writes_per_sec: std::env::var("KALSHI_WRITES_PER_SEC")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5.0),
+            kalshi_max_retries: std::env::var("KALSHI_MAX_RETRIES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3),
+            kalshi_retry_base_ms: std::env::var("KALSHI_RETRY_BASE_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(250),
+            kalshi_retry_max_ms: std::env::var("KALSHI_RETRY_MAX_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(8000),
+            binance_max_retries: std::env::var("BINANCE_MAX_RETRIES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2),
+            kalshi_market_cache_secs: std::env::var("KALSHI_MARKET_CACHE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            kalshi_clock_sync_secs: std::env::var("KALSHI_CLOCK_SYNC_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(900),
+            kalshi_pool_max_idle: std::env::var("KALSHI_POOL_MAX_IDLE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(16),
+            kalshi_pool_idle_secs: std::env::var("KALSHI_POOL_IDLE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(90),
+            kalshi_keepalive_secs: std::env::var("KALSHI_KEEPALIVE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            kalshi_http2: std::env::var("KALSHI_HTTP2")
+                .map(|v| v != "false")
+                .unwrap_or(true),
             tp_cents_per_share: std::env::var("TP_CENTS")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -365,10 +2011,77 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(15),
+            market_exit_spread_cents: std::env::var("MARKET_EXIT_SPREAD_CENTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
             kalshi_ws_url: std::env::var("KALSHI_WS_URL")
-                .unwrap_or_else(|_| "wss://api.elections.kalshi.com/trade-api/ws/v2".into()),
-            binance_ws_url: std::env::var("BINANCE_WS_URL")
-                .unwrap_or_else(|_| "wss://stream.binance.us:9443/stream?streams=btcusdt@kline_1m/ethusdt@kline_1m/solusdt@kline_1m".into()),
+                .unwrap_or_else(|_| kalshi_env.default_ws_url().into()),
+            price_feeds: PriceSource::list_from_env()?,
+            price_divergence_pct: std::env::var("PRICE_DIVERGENCE_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.5),
+            // Opt-in: fapi.binance.com refuses US IPs
+            binance_futures_url: std::env::var("BINANCE_FUTURES_URL").ok().filter(|v| !v.is_empty()),
+            deribit_url: match std::env::var("DERIBIT_URL") {
+                Ok(url) if url.is_empty() => None,
+                Ok(url) => Some(url),
+                Err(_) => Some("https://www.deribit.com".into()),
+            },
+            price_feed_fallback: match std::env::var("PRICE_FEED_FALLBACK").unwrap_or_default().trim() {
+                "" => None,
+                name => Some(PriceSource::parse(name).ok_or_else(|| {
+                    anyhow::anyhow!("Unknown PRICE_FEED_FALLBACK {:?} (expected binance or kraken)", name)
+                })?),
+            },
+            price_failover_after: std::env::var("PRICE_FAILOVER_AFTER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3),
+            candle_cache_dir: match std::env::var("CANDLE_CACHE_DIR") {
+                Ok(dir) if dir.is_empty() => None,
+                Ok(dir) => Some(dir),
+                Err(_) => Some("brain/candles".into()),
+            },
+            candle_cache_days: std::env::var("CANDLE_CACHE_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&d| d > 0)
+                .unwrap_or(30),
+            spot_flow_window_secs: std::env::var("SPOT_FLOW_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            binance_ws_urls: url_list(
+                "BINANCE_WS_URL",
+                "wss://stream.binance.us:9443/stream",
+            ),
+            binance_rest_urls: url_list("BINANCE_REST_URL", "https://api.binance.us"),
+            kalshi_ws_ping_secs: std::env::var("KALSHI_WS_PING_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            kalshi_ws_idle_secs: std::env::var("KALSHI_WS_IDLE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            ws_reconnect_initial_ms: std::env::var("WS_RECONNECT_INITIAL_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000),
+            ws_reconnect_max_secs: std::env::var("WS_RECONNECT_MAX_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            ws_reconnect_halt_after: std::env::var("WS_RECONNECT_HALT_AFTER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            price_stale_secs: std::env::var("PRICE_STALE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
             entry_cycle_interval_secs: std::env::var("ENTRY_CYCLE_INTERVAL_SECS")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -377,6 +2090,64 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(30),
+            rollover_lead_secs: std::env::var("ROLLOVER_LEAD_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(180),
+            min_fill_probability: std::env::var("MIN_FILL_PROBABILITY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.6),
+            maker_horizon_secs: std::env::var("MAKER_HORIZON_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(180),
+            fill_timeout_secs: std::env::var("FILL_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(180),
+            reprice_queue_ahead: std::env::var("REPRICE_QUEUE_AHEAD")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            reprice_max_cents: std::env::var("REPRICE_MAX_CENTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2),
+            telegram_bot_token: std::env::var("TELEGRAM_BOT_TOKEN").unwrap_or_default(),
+            telegram_chat_id: std::env::var("TELEGRAM_CHAT_ID").unwrap_or_default(),
+            market_overrides: MarketOverrides {
+                pinned: env_list("PINNED_MARKETS"),
+                excluded: env_list("EXCLUDED_MARKETS"),
+                excluded_dates: env_list("EXCLUDED_DATES"),
+                exclude_top_strike: std::env::var("EXCLUDE_TOP_STRIKE")
+                    .map(|v| v == "true")
+                    .unwrap_or(false),
+            },
+            series_strategies: series_strategies_from_env()?,
+            series_symbols: series_symbols_from_env()?,
+            series_candles: series_candles_from_env()?,
+            model_routing: ModelRouting::from_env(),
+            backup_target: BackupTarget::from_env(),
+            backup_interval_secs: std::env::var("BACKUP_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3600),
+            equity_interval_secs: std::env::var("EQUITY_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            metrics_target: MetricsTarget::from_env(),
+            metrics_instance: std::env::var("METRICS_INSTANCE")
+                .or_else(|_| std::env::var("HOSTNAME"))
+                .unwrap_or_else(|_| "kalshi-bot".into()),
+            metrics_interval_secs: std::env::var("METRICS_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            record_dir: std::env::var("RECORD_DIR").ok().filter(|v| !v.is_empty()),
+            record_raw_ws: std::env::var("RECORD_RAW_WS").map(|v| v == "true").unwrap_or(false),
+            database_url: std::env::var("DATABASE_URL").ok().filter(|v| !v.is_empty()),
         })
     }
 }
@@ -0,0 +1,230 @@
+use crate::adapters::kalshi::client::KalshiClient;
+use crate::adapters::kalshi::event_bus::KalshiEventBus;
+use crate::adapters::kalshi::websocket::{self as kalshi_ws, KalshiWsEvent, KalshiWsSender};
+use crate::core::types::{Config, KalshiEnv, LedgerRow};
+use crate::core::{engine, market_select};
+use crate::ports::exchange::Exchange;
+use crate::ports::storage::Storage;
+use std::collections::HashSet;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// How long to wait for books to arrive after (re)subscribing.
+const BOOK_WAIT: Duration = Duration::from_secs(60);
+/// How long the severed socket may take to report the disconnect.
+const DISCONNECT_WAIT: Duration = Duration::from_secs(10);
+
+struct Check {
+    name: &'static str,
+    passed: bool,
+    detail: String,
+}
+
+/// Rehearse a Kalshi outage end-to-end: sever the WS feed and REST for
+/// `minutes`, restore them, and verify resubscription, fill backfill,
+/// reconciliation, and ledger integrity recovered. Paper or demo only —
+/// nothing is traded. Prints a pass/fail report; errors if any check failed.
+pub async fn outage(config: &Config, storage: &dyn Storage, minutes: u64) -> anyhow::Result<()> {
+    if !config.paper_trade && config.kalshi_env != KalshiEnv::Demo {
+        anyhow::bail!("Outage drills run only with PAPER_TRADE=true or KALSHI_ENV=demo");
+    }
+    let outage = Duration::from_secs(minutes * 60);
+    let mut checks = Vec::new();
+
+    let exchange = KalshiClient::new(config)?;
+    let ledger_before = storage.read_ledger().await?;
+    let positions_before = position_keys(&exchange).await?;
+
+    // Baseline: books for each series' active market
+    let mut tickers = Vec::new();
+    for series in &config.series_tickers {
+        let open = exchange.open_markets(series).await?;
+        if let Some(m) = market_select::select(open, series, &config.market_overrides) {
+            tickers.push(m.ticker);
+        }
+    }
+    if tickers.is_empty() {
+        anyhow::bail!("No open markets in {:?} to drill against", config.series_tickers);
+    }
+    let bus = KalshiEventBus::default();
+    let mut rx = bus.subscribe_all("drill", 256);
+    let ws = kalshi_ws::connect(config, exchange.auth(), bus, None).await?;
+    subscribe_all(&ws, &tickers).await;
+    let missing = await_books(&mut rx, &tickers).await;
+    checks.push(Check {
+        name: "baseline books",
+        passed: missing.is_empty(),
+        detail: missing_detail(&tickers, &missing),
+    });
+
+    // Sever
+    println!("Severing Kalshi WS and REST for {}min", minutes);
+    let severed_at = chrono::Utc::now();
+    ws.sever(outage).await;
+    exchange.sever(outage);
+    let disconnected = tokio::time::timeout(DISCONNECT_WAIT, async {
+        while let Some(event) = rx.recv().await {
+            if matches!(event, KalshiWsEvent::Disconnected) {
+                return true;
+            }
+        }
+        false
+    })
+    .await
+    .unwrap_or(false);
+    checks.push(Check {
+        name: "disconnect detected",
+        passed: disconnected,
+        detail: if disconnected { "Disconnected event".into() } else { "no event within 10s".into() },
+    });
+
+    let rest_down = exchange.balance().await;
+    checks.push(Check {
+        name: "REST outage surfaces errors",
+        passed: rest_down.is_err(),
+        detail: match rest_down {
+            Ok(b) => format!("unexpected balance {}¢", b),
+            Err(e) => format!("{}", e),
+        },
+    });
+
+    // The adapter replays the baseline subscriptions on reconnect
+    let restore_at = Instant::now() + outage;
+    let mut leaked = 0;
+    while let Ok(Some(event)) = tokio::time::timeout_at(restore_at, rx.recv()).await {
+        if matches!(event, KalshiWsEvent::Orderbook(_)) {
+            leaked += 1;
+        }
+    }
+    checks.push(Check {
+        name: "no data while severed",
+        passed: leaked == 0,
+        detail: format!("{} book updates during the outage", leaked),
+    });
+
+    // Restore
+    println!("Restoring — waiting for resubscription");
+    let missing = await_books(&mut rx, &tickers).await;
+    checks.push(Check {
+        name: "resubscription",
+        passed: missing.is_empty(),
+        detail: missing_detail(&tickers, &missing),
+    });
+    let rest_up = exchange.balance().await;
+    checks.push(Check {
+        name: "REST recovered",
+        passed: rest_up.is_ok(),
+        detail: match rest_up {
+            Ok(b) => format!("balance {}¢", b),
+            Err(e) => format!("{}", e),
+        },
+    });
+
+    let resting: Vec<String> = match exchange.resting_orders().await {
+        Ok(orders) => orders.into_iter().map(|o| o.order_id).collect(),
+        Err(e) => {
+            checks.push(Check { name: "fill backfill", passed: false, detail: e.to_string() });
+            Vec::new()
+        }
+    };
+    match engine::reconcile_fills(&exchange, &resting, severed_at - chrono::Duration::seconds(60)).await {
+        Ok(fills) => checks.push(Check {
+            name: "fill backfill",
+            passed: true,
+            detail: format!("{} resting orders, {} missed fills recovered", resting.len(), fills.len()),
+        }),
+        Err(e) => checks.push(Check { name: "fill backfill", passed: false, detail: e.to_string() }),
+    }
+
+    let positions_after = position_keys(&exchange).await?;
+    let unresolved = storage.unresolved_orders().await?;
+    checks.push(Check {
+        name: "reconciliation",
+        passed: positions_after == positions_before && unresolved.is_empty(),
+        detail: format!(
+            "positions {} before / {} after, {} unresolved journal orders",
+            positions_before.len(), positions_after.len(), unresolved.len()
+        ),
+    });
+
+    let ledger_after = storage.read_ledger().await?;
+    let ledger_issue = ledger_problem(&ledger_before, &ledger_after);
+    checks.push(Check {
+        name: "ledger integrity",
+        passed: ledger_issue.is_none(),
+        detail: ledger_issue.unwrap_or_else(|| format!("{} rows unchanged", ledger_after.len())),
+    });
+
+    println!("\nOutage drill ({}min, {} markets)", minutes, tickers.len());
+    for c in &checks {
+        println!("  [{}] {:<28} {}", if c.passed { "PASS" } else { "FAIL" }, c.name, c.detail);
+    }
+    let failed = checks.iter().filter(|c| !c.passed).count();
+    if failed > 0 {
+        anyhow::bail!("Outage drill failed: {} of {} checks", failed, checks.len());
+    }
+    println!("Outage drill passed ({} checks)", checks.len());
+    Ok(())
+}
+
+async fn subscribe_all(ws: &KalshiWsSender, tickers: &[String]) {
+    ws.subscribe_markets(vec!["orderbook_delta".into(), "market_lifecycle_v2".into()], tickers.to_vec()).await;
+}
+
+/// Wait for a book on every ticker; returns the ones that never arrived.
+async fn await_books(rx: &mut mpsc::Receiver<KalshiWsEvent>, tickers: &[String]) -> HashSet<String> {
+    let mut missing: HashSet<String> = tickers.iter().cloned().collect();
+    let deadline = Instant::now() + BOOK_WAIT;
+    while !missing.is_empty() {
+        match tokio::time::timeout_at(deadline, rx.recv()).await {
+            Ok(Some(KalshiWsEvent::Orderbook(update))) => {
+                missing.remove(&update.ticker);
+            }
+            Ok(Some(_)) => {}
+            Ok(None) | Err(_) => break,
+        }
+    }
+    missing
+}
+
+fn missing_detail(tickers: &[String], missing: &HashSet<String>) -> String {
+    if missing.is_empty() {
+        format!("{} of {} books", tickers.len(), tickers.len())
+    } else {
+        format!("no book for {}", missing.iter().cloned().collect::<Vec<_>>().join(", "))
+    }
+}
+
+async fn position_keys(exchange: &dyn Exchange) -> anyhow::Result<Vec<(String, String, u32)>> {
+    let mut keys: Vec<(String, String, u32)> = exchange
+        .positions()
+        .await?
+        .into_iter()
+        .map(|p| (p.ticker, p.side.to_string(), p.count))
+        .collect();
+    keys.sort();
+    Ok(keys)
+}
+
+/// Rows must be unchanged (nothing traded) and cumulative P&L a running sum.
+fn ledger_problem(before: &[LedgerRow], after: &[LedgerRow]) -> Option<String> {
+    if before.len() != after.len() {
+        return Some(format!("{} rows before, {} after", before.len(), after.len()));
+    }
+    if let Some((b, _)) = before
+        .iter()
+        .zip(after)
+        .find(|(b, a)| b.order_id != a.order_id || b.result != a.result || b.pnl_cents != a.pnl_cents)
+    {
+        return Some(format!("row {} changed", b.order_id));
+    }
+    let mut running = 0;
+    for row in after {
+        running += row.pnl_cents;
+        if row.cumulative_cents != running {
+            return Some(format!("cumulative breaks at {} ({}¢ vs {}¢)", row.order_id, row.cumulative_cents, running));
+        }
+    }
+    None
+}
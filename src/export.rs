@@ -0,0 +1,194 @@
+use crate::storage;
+use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use std::path::Path;
+use std::sync::Arc;
+
+const LEDGER_SCHEMA: &str = "
+message ledger {
+    REQUIRED BYTE_ARRAY timestamp (UTF8);
+    REQUIRED BYTE_ARRAY series (UTF8);
+    REQUIRED BYTE_ARRAY ticker (UTF8);
+    REQUIRED BYTE_ARRAY side (UTF8);
+    REQUIRED INT64 shares;
+    REQUIRED INT64 price_cents;
+    REQUIRED BYTE_ARRAY result (UTF8);
+    REQUIRED INT64 pnl_cents;
+    REQUIRED INT64 cumulative_cents;
+    REQUIRED BYTE_ARRAY order_id (UTF8);
+}
+";
+
+const DECISIONS_SCHEMA: &str = "
+message decisions {
+    REQUIRED BYTE_ARRAY timestamp (UTF8);
+    REQUIRED BYTE_ARRAY order_id (UTF8);
+    REQUIRED BYTE_ARRAY ticker (UTF8);
+    REQUIRED BYTE_ARRAY action (UTF8);
+    OPTIONAL BYTE_ARRAY side (UTF8);
+    OPTIONAL INT64 llm_shares;
+    OPTIONAL INT64 llm_max_price_cents;
+    OPTIONAL DOUBLE llm_probability;
+    OPTIONAL DOUBLE llm_edge;
+    REQUIRED BYTE_ARRAY reasoning (UTF8);
+    OPTIONAL DOUBLE signal_probability_yes;
+    OPTIONAL DOUBLE signal_edge;
+    OPTIONAL DOUBLE orderbook_imbalance;
+    REQUIRED BYTE_ARRAY entry_style (UTF8);
+    REQUIRED INT64 entry_price_cents;
+    REQUIRED INT64 shares;
+    REQUIRED BYTE_ARRAY prompt (UTF8);
+    REQUIRED BYTE_ARRAY raw_response (UTF8);
+}
+";
+
+/// One Parquet column, in schema order.
+enum Column {
+    Str(Vec<ByteArray>),
+    Int(Vec<i64>),
+    OptStr(Vec<Option<ByteArray>>),
+    OptInt(Vec<Option<i64>>),
+    OptDouble(Vec<Option<f64>>),
+}
+
+fn str_col<'a>(values: impl Iterator<Item = &'a str>) -> Column {
+    Column::Str(values.map(ByteArray::from).collect())
+}
+
+/// Export the ledger and persisted decision contexts to
+/// `<out_dir>/{ledger,decisions}.parquet` for research tooling (pandas/polars).
+pub fn export_parquet(out_dir: &str) -> anyhow::Result<()> {
+    std::fs::create_dir_all(out_dir)?;
+    export_ledger(out_dir)?;
+    export_decisions(out_dir)?;
+    Ok(())
+}
+
+fn export_ledger(out_dir: &str) -> anyhow::Result<()> {
+    let ledger = storage::read_ledger()?;
+
+    let columns = vec![
+        str_col(ledger.iter().map(|r| r.timestamp.as_str())),
+        str_col(ledger.iter().map(|r| r.series())),
+        str_col(ledger.iter().map(|r| r.ticker.as_str())),
+        str_col(ledger.iter().map(|r| r.side.as_str())),
+        Column::Int(ledger.iter().map(|r| r.shares as i64).collect()),
+        Column::Int(ledger.iter().map(|r| r.price as i64).collect()),
+        str_col(ledger.iter().map(|r| r.result.as_str())),
+        Column::Int(ledger.iter().map(|r| r.pnl_cents).collect()),
+        Column::Int(ledger.iter().map(|r| r.cumulative_cents).collect()),
+        str_col(ledger.iter().map(|r| r.order_id.as_str())),
+    ];
+
+    let path = Path::new(out_dir).join("ledger.parquet");
+    write_parquet(&path, LEDGER_SCHEMA, columns)?;
+    tracing::info!("Exported {} ledger rows to {}", ledger.len(), path.display());
+    Ok(())
+}
+
+fn export_decisions(out_dir: &str) -> anyhow::Result<()> {
+    let records = storage::read_decisions()?;
+
+    let s = |ptr: &str| {
+        Column::Str(
+            records
+                .iter()
+                .map(|r| ByteArray::from(r.pointer(ptr).and_then(|v| v.as_str()).unwrap_or("")))
+                .collect(),
+        )
+    };
+    let opt_s = |ptr: &str| {
+        Column::OptStr(
+            records
+                .iter()
+                .map(|r| r.pointer(ptr).and_then(|v| v.as_str()).map(ByteArray::from))
+                .collect(),
+        )
+    };
+    let i = |ptr: &str| {
+        Column::Int(records.iter().map(|r| r.pointer(ptr).and_then(|v| v.as_i64()).unwrap_or(0)).collect())
+    };
+    let opt_i = |ptr: &str| {
+        Column::OptInt(records.iter().map(|r| r.pointer(ptr).and_then(|v| v.as_i64())).collect())
+    };
+    let opt_d = |ptr: &str| {
+        Column::OptDouble(records.iter().map(|r| r.pointer(ptr).and_then(|v| v.as_f64())).collect())
+    };
+
+    let columns = vec![
+        s("/timestamp"),
+        s("/order_id"),
+        s("/ticker"),
+        s("/decision/action"),
+        opt_s("/decision/side"),
+        opt_i("/decision/shares"),
+        opt_i("/decision/max_price_cents"),
+        opt_d("/decision/estimated_probability"),
+        opt_d("/decision/estimated_edge"),
+        s("/decision/reasoning"),
+        opt_d("/signal_summary/estimated_probability"),
+        opt_d("/signal_summary/estimated_edge"),
+        opt_d("/signal_summary/orderbook_imbalance"),
+        s("/entry_style"),
+        i("/entry_price_cents"),
+        i("/shares"),
+        s("/prompt"),
+        s("/raw_response"),
+    ];
+
+    let path = Path::new(out_dir).join("decisions.parquet");
+    write_parquet(&path, DECISIONS_SCHEMA, columns)?;
+    tracing::info!("Exported {} decisions to {}", records.len(), path.display());
+    Ok(())
+}
+
+/// Values plus definition levels for an OPTIONAL column.
+fn split_optional<T: Clone>(values: &[Option<T>]) -> (Vec<T>, Vec<i16>) {
+    let defs = values.iter().map(|v| v.is_some() as i16).collect();
+    let present = values.iter().flatten().cloned().collect();
+    (present, defs)
+}
+
+fn write_parquet(path: &Path, schema: &str, columns: Vec<Column>) -> anyhow::Result<()> {
+    let schema = Arc::new(parse_message_type(schema)?);
+    let props = Arc::new(WriterProperties::builder().build());
+
+    // Write to .tmp then rename so readers never see a half-written file
+    let tmp = path.with_extension("parquet.tmp");
+    let file = std::fs::File::create(&tmp)?;
+    let mut writer = SerializedFileWriter::new(file, schema, props)?;
+    let mut row_group = writer.next_row_group()?;
+
+    let mut columns = columns.into_iter();
+    while let Some(mut col_writer) = row_group.next_column()? {
+        match columns.next() {
+            Some(Column::Str(values)) => {
+                col_writer.typed::<ByteArrayType>().write_batch(&values, None, None)?;
+            }
+            Some(Column::Int(values)) => {
+                col_writer.typed::<Int64Type>().write_batch(&values, None, None)?;
+            }
+            Some(Column::OptStr(values)) => {
+                let (present, defs) = split_optional(&values);
+                col_writer.typed::<ByteArrayType>().write_batch(&present, Some(&defs), None)?;
+            }
+            Some(Column::OptInt(values)) => {
+                let (present, defs) = split_optional(&values);
+                col_writer.typed::<Int64Type>().write_batch(&present, Some(&defs), None)?;
+            }
+            Some(Column::OptDouble(values)) => {
+                let (present, defs) = split_optional(&values);
+                col_writer.typed::<DoubleType>().write_batch(&present, Some(&defs), None)?;
+            }
+            None => anyhow::bail!("Parquet schema has more columns than data"),
+        }
+        col_writer.close()?;
+    }
+
+    row_group.close()?;
+    writer.close()?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
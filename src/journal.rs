@@ -0,0 +1,132 @@
+use crate::core::types::{JournalEvent, UnresolvedOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+
+const JOURNAL_PATH: &str = "brain/journal.jsonl";
+
+#[derive(Serialize, Deserialize)]
+struct JournalLine {
+    ts: String,
+    #[serde(flatten)]
+    event: JournalEvent,
+}
+
+/// Append one event and fsync before returning, so a `Submitted` entry is on
+/// disk before the order leaves the process.
+pub fn append(event: &JournalEvent) -> anyhow::Result<()> {
+    let line = JournalLine {
+        ts: chrono::Utc::now().to_rfc3339(),
+        event: event.clone(),
+    };
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(JOURNAL_PATH)?;
+    writeln!(file, "{}", serde_json::to_string(&line)?)?;
+    file.sync_data()?;
+    Ok(())
+}
+
+fn read_lines() -> anyhow::Result<Vec<JournalLine>> {
+    read_lines_from(JOURNAL_PATH)
+}
+
+fn read_lines_from(path: &str) -> anyhow::Result<Vec<JournalLine>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    // A crash mid-write leaves a torn last line — skip anything unparseable.
+    Ok(content
+        .lines()
+        .filter_map(|l| match serde_json::from_str(l) {
+            Ok(line) => Some(line),
+            Err(e) => {
+                tracing::warn!("Skipping malformed journal line: {}", e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Submitted orders with no `Recorded`/`Abandoned` entry, oldest first:
+/// by exchange creation time once placed, else by local submission time.
+pub fn unresolved() -> anyhow::Result<Vec<UnresolvedOrder>> {
+    Ok(unresolved_from(read_lines()?))
+}
+
+/// (submitted, still unresolved) order counts from another instance's
+/// journal, read from its brain directory.
+pub fn order_counts_in(dir: &str) -> anyhow::Result<(usize, usize)> {
+    let lines = read_lines_from(&format!("{}/journal.jsonl", dir.trim_end_matches('/')))?;
+    let submitted = lines
+        .iter()
+        .filter(|l| matches!(l.event, JournalEvent::Submitted { .. }))
+        .count();
+    Ok((submitted, unresolved_from(lines).len()))
+}
+
+fn unresolved_from(lines: Vec<JournalLine>) -> Vec<UnresolvedOrder> {
+    let mut open: Vec<(i64, UnresolvedOrder)> = Vec::new();
+    let mut placed: HashMap<String, (String, Option<i64>)> = HashMap::new();
+
+    for line in lines {
+        match line.event {
+            JournalEvent::Submitted { order } => {
+                let submitted_ms = chrono::DateTime::parse_from_rfc3339(&line.ts)
+                    .map(|t| t.timestamp_millis())
+                    .unwrap_or(0);
+                open.push((submitted_ms, UnresolvedOrder { order, order_id: None }));
+            }
+            JournalEvent::Placed { client_order_id, order_id, created_ms } => {
+                placed.insert(client_order_id, (order_id, created_ms));
+            }
+            JournalEvent::Recorded { client_order_id } | JournalEvent::Abandoned { client_order_id } => {
+                open.retain(|(_, u)| u.order.client_order_id != client_order_id);
+            }
+            JournalEvent::Amended { .. } => {}
+        }
+    }
+
+    for (at_ms, u) in &mut open {
+        if let Some((order_id, created_ms)) = placed.remove(&u.order.client_order_id) {
+            u.order_id = Some(order_id);
+            *at_ms = created_ms.unwrap_or(*at_ms);
+        }
+    }
+    open.sort_by_key(|(at_ms, _)| *at_ms);
+    open.into_iter().map(|(_, u)| u).collect()
+}
+
+/// Rewrite the journal keeping only unresolved orders and the manual
+/// correction audit trail, so it stays small. brain/ is snapshotted first
+/// since resolved history is dropped.
+pub fn compact() -> anyhow::Result<()> {
+    crate::snapshot::take("journal-compact")?;
+    let keep: Vec<String> = unresolved()?
+        .into_iter()
+        .map(|u| u.order.client_order_id)
+        .collect();
+
+    let mut out = String::new();
+    for line in read_lines()? {
+        let keep_line = match &line.event {
+            JournalEvent::Submitted { order } => keep.contains(&order.client_order_id),
+            JournalEvent::Placed { client_order_id, .. }
+            | JournalEvent::Recorded { client_order_id }
+            | JournalEvent::Abandoned { client_order_id } => keep.contains(client_order_id),
+            JournalEvent::Amended { .. } => true,
+        };
+        if keep_line {
+            out.push_str(&serde_json::to_string(&line)?);
+            out.push('\n');
+        }
+    }
+
+    let tmp = format!("{}.tmp", JOURNAL_PATH);
+    std::fs::write(&tmp, out)?;
+    std::fs::rename(&tmp, JOURNAL_PATH)?;
+    Ok(())
+}
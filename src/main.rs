@@ -1,18 +1,203 @@
 mod adapters;
+mod backup;
 mod core;
+mod drill;
+mod export;
+mod journal;
 mod ports;
+mod recorder;
+mod redecide;
+mod report;
 mod safety;
+mod snapshot;
 mod storage;
 
-use adapters::binance::BinanceClient;
 use adapters::binance_ws;
 use adapters::kalshi::client::KalshiClient;
-use adapters::kalshi::websocket::{self as kalshi_ws, KalshiWsEvent};
+use adapters::kalshi::event_bus::KalshiEventBus;
+use adapters::kalshi::retry::RetryPolicy;
+use adapters::kalshi::websocket::{self as kalshi_ws, KalshiWsEvent, WsErrorKind};
 use adapters::openrouter::OpenRouterClient;
+use adapters::telegram::TelegramNotifier;
+use adapters::ws_health::WsHealth;
+use adapters::ws_tap::RawTap;
+use core::book_cache::BookCache;
+use core::candle_builder::StreamedCandles;
 use core::engine;
+use core::entry_queue::EntryQueue;
+use core::fill_model::FillModel;
+use core::market_select;
 use core::position_manager::PositionManager;
-use core::types::Config;
+use core::price_aggregate::AggregatedPriceFeed;
+use core::price_failover::FailoverPriceFeed;
+use core::price_ticks::PriceTicks;
+use core::rollover::Rollovers;
+use core::routing::RoutedBrain;
+use core::spot_cache::SpotCache;
+use core::types::{
+    BackupTarget, Config, EntryOutcome, HandoffSnapshot, JournalEvent, LedgerAmendment, MarketDataEvent, MarketState,
+    MetricsTarget, OrderStatus, PriceSource, QueryFilter, ResultFilter, SeriesInfo, ShutdownRequested, Side,
+    ticker_series,
+};
+use ports::backup::BackupStore;
+use ports::brain::Brain;
+use ports::exchange::Exchange;
+use ports::metrics::MetricsSink;
+use ports::notifier::Notifier;
+use ports::price_feed::PriceFeed;
+use ports::storage::Storage;
+use ports::vol_feed::VolFeed;
+use storage::FileStorage;
+
+use futures_util::StreamExt;
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Postgres when built with the `postgres` feature and DATABASE_URL is set,
+/// otherwise the local markdown ledger.
+async fn open_storage(config: &Config) -> anyhow::Result<Box<dyn Storage>> {
+    match &config.database_url {
+        #[cfg(feature = "postgres")]
+        Some(url) => Ok(Box::new(adapters::postgres::PostgresStorage::connect(url).await?)),
+        #[cfg(not(feature = "postgres"))]
+        Some(_) => anyhow::bail!("DATABASE_URL is set but the bot was built without the `postgres` feature"),
+        None => Ok(Box::new(FileStorage::open()?)),
+    }
+}
+
+/// Copy brain/ledger.md into the Postgres ledger, then read it back and check
+/// row count, order ids, and final cumulative P&L match.
+#[cfg(feature = "postgres")]
+async fn migrate_ledger(config: &Config) -> anyhow::Result<()> {
+    let Some(url) = &config.database_url else {
+        anyhow::bail!("DATABASE_URL not set — nothing to migrate to");
+    };
+    let report = storage::verify_ledger(false)?;
+    if !report.issues.is_empty() {
+        anyhow::bail!(
+            "ledger.md has {} integrity issues — run `ledger-check --repair` first",
+            report.issues.len()
+        );
+    }
+
+    let rows = storage::read_ledger()?;
+    let db = adapters::postgres::PostgresStorage::connect(url).await?;
+    let imported = db.import_ledger(&rows).await?;
+    db.write_stats(&core::stats::compute(&rows)).await?;
+
+    let stored = db.read_ledger().await?;
+    if !stored.iter().map(|r| &r.order_id).eq(rows.iter().map(|r| &r.order_id)) {
+        anyhow::bail!("Postgres order ids do not match ledger.md after import");
+    }
+    let expected = rows.last().map(|r| r.cumulative_cents).unwrap_or(0);
+    let actual = stored.last().map(|r| r.cumulative_cents).unwrap_or(0);
+    if actual != expected {
+        anyhow::bail!(
+            "Cumulative P&L mismatch after import: {}¢ in Postgres vs {}¢ in ledger.md",
+            actual, expected
+        );
+    }
+
+    println!("Imported {} rows (cumulative P&L {}¢)", imported, actual);
+    Ok(())
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn migrate_ledger(_config: &Config) -> anyhow::Result<()> {
+    anyhow::bail!("migrate-ledger needs a build with the `postgres` feature")
+}
+
+fn open_backup_store(config: &Config) -> anyhow::Result<Option<Arc<dyn BackupStore>>> {
+    Ok(match &config.backup_target {
+        Some(BackupTarget::WebDav { url, user, password }) => {
+            Some(Arc::new(adapters::webdav::WebDavStore::new(url, user, password)?))
+        }
+        Some(BackupTarget::S3 { endpoint, bucket, region, access_key, secret_key }) => Some(Arc::new(
+            adapters::s3::S3Store::new(endpoint, bucket, region, access_key, secret_key)?,
+        )),
+        None => None,
+    })
+}
+
+fn open_price_source(config: &Config, source: PriceSource) -> anyhow::Result<Box<dyn PriceFeed>> {
+    Ok(match source {
+        PriceSource::Binance => Box::new(adapters::binance::BinanceClient::new(config)?),
+        PriceSource::Kraken => Box::new(adapters::kraken::KrakenClient::new(config)?),
+    })
+}
+
+/// The configured price feed, behind a failover decorator when a fallback is
+/// set (failover alerts arrive on `alerts`), with the primary source's candles
+/// cached on disk when enabled, publishing streamed prices to `subscribe`rs.
+fn open_price_feed(
+    config: &Config,
+    alerts: tokio::sync::mpsc::UnboundedSender<String>,
+) -> anyhow::Result<Box<dyn PriceFeed>> {
+    let mut sources = Vec::new();
+    for &source in &config.price_feeds {
+        sources.push((source.as_str(), open_price_source(config, source)?));
+    }
+    // Only the primary source's own REST candles are cached on disk
+    if let Some(dir) = &config.candle_cache_dir {
+        let (name, feed) = sources.remove(0);
+        let dir = format!("{}/{}", dir.trim_end_matches('/'), name);
+        let cached = adapters::candle_cache::CandleCache::new(feed, &dir, config.candle_cache_days)?;
+        sources.insert(0, (name, Box::new(cached)));
+    }
+    let primary: Box<dyn PriceFeed> = if sources.len() == 1 {
+        let feed = sources.remove(0).1;
+        // The kline stream is Binance's, so only a Binance-only feed is served from it
+        if config.price_feeds == [PriceSource::Binance] {
+            Box::new(StreamedCandles::new(feed))
+        } else {
+            feed
+        }
+    } else {
+        tracing::info!(
+            "Price feed: median of {}",
+            sources.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ")
+        );
+        Box::new(AggregatedPriceFeed::new(sources, config.price_divergence_pct))
+    };
+    let feed: Box<dyn PriceFeed> = match config.price_feed_fallback {
+        Some(fallback) => Box::new(FailoverPriceFeed::new(
+            primary,
+            open_price_source(config, fallback)?,
+            fallback.as_str(),
+            config.price_failover_after,
+            alerts,
+        )),
+        None => primary,
+    };
+    Ok(Box::new(PriceTicks::new(feed)))
+}
+
+fn open_vol_feed(config: &Config) -> anyhow::Result<Option<Box<dyn VolFeed>>> {
+    Ok(match &config.deribit_url {
+        Some(url) => Some(Box::new(adapters::deribit::DeribitClient::new(url)?)),
+        None => None,
+    })
+}
+
+fn open_metrics_sink(config: &Config) -> anyhow::Result<Option<Box<dyn MetricsSink>>> {
+    Ok(match &config.metrics_target {
+        Some(MetricsTarget::Pushgateway { url }) => {
+            Some(Box::new(adapters::pushgateway::PushgatewaySink::new(url)?))
+        }
+        Some(MetricsTarget::Otlp { url }) => Some(Box::new(adapters::otlp::OtlpSink::new(url)?)),
+        None => None,
+    })
+}
+
+const HANDOFF_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+/// Consecutive failed WS reconnects before alerting (once per outage).
+const WS_ALERT_AFTER_FAILURES: u32 = 5;
+/// How often the freshest Binance price is checked against `PRICE_STALE_SECS`.
+const PRICE_STALE_CHECK: std::time::Duration = std::time::Duration::from_secs(5);
+/// How long shutdown waits for each WS to unsubscribe and close.
+const WS_CLOSE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -21,36 +206,251 @@ async fn main() -> anyhow::Result<()> {
     }
     tracing_subscriber::fmt::init();
 
-    let config = Config::from_env()?;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let handoff = args.first().map(String::as_str) == Some("--handoff");
+    match args.first().map(String::as_str) {
+        Some("export-parquet") => {
+            let out_dir = args.get(1).map(String::as_str).unwrap_or("export");
+            export::export_parquet(out_dir)?;
+            return Ok(());
+        }
+        Some("ledger-check") => {
+            let repair = args.get(1).map(String::as_str) == Some("--repair");
+            if repair {
+                snapshot::take("ledger-repair")?;
+            }
+            let report = storage::verify_ledger(repair)?;
+            for issue in &report.issues {
+                println!("{}", issue);
+            }
+            println!(
+                "{} rows checked, {} issues, {} repaired",
+                report.rows, report.issues.len(), report.repaired
+            );
+            if !report.issues.is_empty() && !repair {
+                anyhow::bail!("ledger.md failed integrity check (rerun with --repair)");
+            }
+            return Ok(());
+        }
+        Some("query-ledger") => {
+            let filter = parse_query_filter(&args[1..])?;
+            let rows = storage::query_ledger(&filter)?;
+            for r in &rows {
+                println!(
+                    "{} | {} | {} | {}x @ {}¢ | {} | {}¢",
+                    r.timestamp, r.ticker, r.side, r.shares, r.price, r.result, r.pnl_cents
+                );
+            }
+            println!(
+                "{} rows, P&L {}¢",
+                rows.len(),
+                rows.iter().map(|r| r.pnl_cents).sum::<i64>()
+            );
+            return Ok(());
+        }
+        Some("snapshot") => {
+            let label = args.get(1).map(String::as_str).unwrap_or("manual");
+            println!("{}", snapshot::take(label)?);
+            return Ok(());
+        }
+        Some("snapshots") => {
+            for id in snapshot::list()? {
+                println!("{}", id);
+            }
+            return Ok(());
+        }
+        Some("restore") => {
+            let Some(id) = args.get(1) else {
+                anyhow::bail!("Usage: restore <snapshot-id> (see `snapshots`)");
+            };
+            // Refuse while a daemon is running and writing brain/
+            safety::acquire_lock()?;
+            let restored = snapshot::restore(id);
+            safety::release_lock();
+            restored?;
+            return Ok(());
+        }
+        Some("backup") => {
+            let config = Config::from_env()?;
+            let Some(store) = open_backup_store(&config)? else {
+                anyhow::bail!("No backup target configured (BACKUP_S3_BUCKET or BACKUP_WEBDAV_URL)");
+            };
+            backup::run(store.as_ref()).await?;
+            return Ok(());
+        }
+        Some("migrate-ledger") => {
+            migrate_ledger(&Config::from_env()?).await?;
+            return Ok(());
+        }
+        Some(cmd @ ("settle" | "amend")) => {
+            let Some(order_id) = args.get(1) else {
+                anyhow::bail!(
+                    "Usage: {} <order_id> [--result R] [--pnl CENTS] [--side S] [--shares N] [--price CENTS] \
+                     --reason TEXT [--by NAME]",
+                    cmd
+                );
+            };
+            let correction = parse_correction(&args[2..])?;
+            if cmd == "settle" {
+                correction.check_settle()?;
+            }
+            correct_ledger(&Config::from_env()?, cmd, order_id, correction).await?;
+            return Ok(());
+        }
+        Some("weekly-report") => {
+            print!("{}", report::weekly_report()?);
+            return Ok(());
+        }
+        Some("aggregate-report") => {
+            if args.len() < 2 {
+                anyhow::bail!("Usage: aggregate-report <name=brain_dir>... (e.g. paper=/srv/paper/brain live=brain)");
+            }
+            print!("{}", report::aggregate_report(&args[1..])?);
+            return Ok(());
+        }
+        Some("feature-report") => {
+            let days = args.get(1).map(|d| d.parse()).transpose()?.unwrap_or(30);
+            print!("{}", report::feature_report(days)?);
+            return Ok(());
+        }
+        Some("redecide") => {
+            let Some(prompt_path) = args.get(1) else {
+                anyhow::bail!("Usage: redecide <prompt.md> [model]");
+            };
+            let prompt_md = std::fs::read_to_string(prompt_path)?;
+            let mut config = Config::from_env()?;
+            if let Some(model) = args.get(2) {
+                config.openrouter_model = model.clone();
+            }
+            let brain = OpenRouterClient::new(&config)?;
+            redecide::redecide(&brain, &prompt_md).await?;
+            return Ok(());
+        }
+        Some("drill") => {
+            if args.get(1).map(String::as_str) != Some("outage") {
+                anyhow::bail!("Usage: drill outage [--minutes N]");
+            }
+            let minutes = match (args.get(2).map(String::as_str), args.get(3)) {
+                (Some("--minutes"), Some(n)) => n.parse()?,
+                (None, _) => 5,
+                _ => anyhow::bail!("Usage: drill outage [--minutes N]"),
+            };
+            let config = Config::from_env()?;
+            let storage = open_storage(&config).await?;
+            drill::outage(&config, storage.as_ref(), minutes).await?;
+            return Ok(());
+        }
+        Some("series") => {
+            let mut config = Config::from_env()?;
+            if let Some(category) = args.get(1) {
+                config.series_category = category.clone();
+            }
+            let found = discover_series(&config).await?;
+            println!(
+                "{} {} series in {}:",
+                found.len(), config.series_frequency, config.series_category
+            );
+            for s in &found {
+                println!("  {:<12} {}", s.ticker, s.title);
+            }
+            return Ok(());
+        }
+        Some("--handoff") | None => {}
+        Some(other) => anyhow::bail!("Unknown command: {}", other),
+    }
+
+    let mut config = Config::from_env()?;
+    if config.series_tickers.is_empty() {
+        match discover_series(&config).await {
+            Ok(found) => config.series_tickers = found.into_iter().map(|s| s.ticker).collect(),
+            Err(e) => tracing::warn!("Series discovery failed: {}", e),
+        }
+    }
     tracing::info!(
-        "kalshi-bot v2 daemon | paper_trade={} confirm_live={} tp={}¢ sl={}¢ assets={:?}",
-        config.paper_trade, config.confirm_live,
+        "kalshi-bot v2 daemon | env={} paper_trade={} confirm_live={} tp={}¢ sl={}¢ assets={:?}",
+        config.kalshi_env.as_str(), config.paper_trade, config.confirm_live,
         config.tp_cents_per_share, config.sl_cents_per_share,
         config.series_tickers
     );
 
     safety::validate_startup(&config)?;
+    if !handoff {
+        safety::acquire_lock()?;
+    }
 
     let exchange = KalshiClient::new(&config)?;
-    let brain = OpenRouterClient::new(&config)?;
-    let price_feed = BinanceClient::new(&config)?;
+    let brain = open_brain(&config)?;
+    let (feed_alert_tx, mut feed_alert_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let price_feed = open_price_feed(&config, feed_alert_tx)?;
+    let vol_feed = open_vol_feed(&config)?;
+    let notifier = TelegramNotifier::new(&config)?;
+    let storage = open_storage(&config).await?;
 
     let mut position_mgr = PositionManager::new(&config);
-    let mut shutdown_rx = safety::setup_signal_handler();
+    let mut fill_model = FillModel::new(&config);
+    let shutdown = safety::setup_signal_handler();
+
+    // Warm standby: take over the running instance's positions before it exits
+    if handoff {
+        safety::request_handoff(HANDOFF_TIMEOUT).await?;
+    }
+    let portfolio = exchange.portfolio_snapshot().await?;
+    tracing::info!(
+        "Portfolio: balance {}¢, {} open positions, {} resting orders",
+        portfolio.balance_cents, portfolio.positions.len(), portfolio.resting_orders.len()
+    );
+    // Resting entries handed over, with the seconds left on their fill timeout
+    let mut handed_over_orders: Vec<(String, u64)> = Vec::new();
+    if handoff {
+        let snapshot = storage::read_handoff_snapshot()?;
+        let (kept, dropped): (Vec<_>, Vec<_>) = snapshot
+            .positions
+            .into_iter()
+            .partition(|p| portfolio.positions.iter().any(|l| l.ticker == p.ticker));
+        for pos in &dropped {
+            tracing::warn!("Handoff: {} not open on exchange — dropping", pos.ticker);
+        }
+        position_mgr.restore(kept);
+        for (order_id, secs_left) in snapshot.resting_orders {
+            if portfolio.resting_orders.iter().any(|o| o.order_id == order_id) {
+                tracing::info!("Handoff: order {} resting — fill timeout in {}s", order_id, secs_left);
+                handed_over_orders.push((order_id, secs_left));
+            } else {
+                tracing::warn!("Handoff: order {} no longer resting — dropping", order_id);
+            }
+        }
+    }
 
     // Kalshi WebSocket
-    let (kalshi_tx, mut kalshi_rx) = tokio::sync::mpsc::channel::<KalshiWsEvent>(256);
-    let kalshi_auth = adapters::kalshi::auth::KalshiAuth::new(
-        config.kalshi_key_id.clone(),
-        &config.kalshi_private_key_pem,
-    )?;
-    let kalshi_ws_sender = kalshi_ws::connect(&config.kalshi_ws_url, &kalshi_auth, kalshi_tx).await?;
-
-    // Binance WebSocket — combined stream for all assets
-    let (binance_tx, mut binance_rx) = tokio::sync::mpsc::channel::<binance_ws::CryptoPriceUpdate>(256);
-    let binance_ws_url = config.binance_ws_url.clone();
-    tokio::spawn(async move {
-        if let Err(e) = binance_ws::connect(&binance_ws_url, binance_tx).await {
+    let kalshi_bus = KalshiEventBus::default();
+    let mut kalshi_rx = kalshi_bus.subscribe_all("daemon", 256);
+    let recorder = config.record_dir.as_deref().map(recorder::Recorder::start).transpose()?;
+    if let Some(rec) = &recorder {
+        rec.follow(&kalshi_bus);
+    }
+    let raw_tap = config.record_raw_ws.then(|| match &recorder {
+        Some(rec) => rec.raw_tap(),
+        None => RawTap::logging(),
+    });
+    let kalshi_ws_sender = kalshi_ws::connect(&config, exchange.auth(), kalshi_bus.clone(), raw_tap.clone()).await?;
+
+    // Binance WebSocket — combined stream, subscribed per traded symbol
+    let (binance_tx, mut binance_rx) = tokio::sync::mpsc::channel::<binance_ws::BinanceWsEvent>(256);
+    let binance_ws_urls = config.binance_ws_urls.clone();
+    let binance_backoff = RetryPolicy::ws_reconnect(&config);
+    let binance_health = std::sync::Arc::new(WsHealth::new("binance"));
+    let binance_closing = tokio_util::sync::CancellationToken::new();
+    let loop_health = binance_health.clone();
+    let loop_closing = binance_closing.clone();
+    let mut binance_symbols: Vec<String> =
+        config.series_tickers.iter().filter_map(|s| config.price_symbol(s)).map(str::to_string).collect();
+    binance_symbols.sort();
+    binance_symbols.dedup();
+    let price_symbols = binance_symbols.clone();
+    let binance_task = tokio::spawn(async move {
+        if let Err(e) = binance_ws::connect(
+            &binance_ws_urls, binance_tx, &loop_health, loop_closing, raw_tap, binance_backoff, &binance_symbols,
+        ).await {
             tracing::error!("Binance WS fatal: {}", e);
         }
     });
@@ -62,19 +462,103 @@ async fn main() -> anyhow::Result<()> {
     let mut position_timer = tokio::time::interval(
         std::time::Duration::from_secs(config.position_check_interval_secs),
     );
+    // Rollover pre-fetch, watchlist upkeep and entry repricing make REST calls,
+    // so they run apart from the exit checks on `position_timer`
+    let mut market_timer = tokio::time::interval(
+        std::time::Duration::from_secs(config.position_check_interval_secs),
+    );
 
-    // Track latest prices per Binance symbol (e.g., "BTCUSDT" → 66322.01)
-    let mut latest_prices: HashMap<String, f64> = HashMap::new();
+    // Pushed spot price ticks, for the staleness check; latest prices are read
+    // back from the feed (`PriceFeed::last_tick`)
+    let mut price_ticks = futures_util::stream::select_all(price_symbols.iter().map(|s| price_feed.subscribe(s)));
+    // Last tick per symbol (startup before the first), and the symbols gone
+    // stale, whose series take no entries
+    let mut last_price_at: HashMap<String, Instant> =
+        price_symbols.iter().map(|s| (s.clone(), Instant::now())).collect();
+    let mut stale_symbols: HashSet<String> = HashSet::new();
+    let mut stale_reconnect_at: Option<Instant> = None;
+    let mut price_stale_timer = tokio::time::interval(PRICE_STALE_CHECK);
     // Track subscribed market tickers for WS
     let mut subscribed_tickers: HashSet<String> = HashSet::new();
+    // Market data recording: open markets subscribed only to be recorded
+    let mut recorded_tickers: HashSet<String> = HashSet::new();
+    // Series rollover: successor markets queued for entry at open, and
+    // subscribed ahead of time so their books are warm
+    let mut rollovers = Rollovers::new(config.rollover_lead_secs);
+    let mut prewarmed_tickers: HashSet<String> = HashSet::new();
+    // Orderbook watchlist: each series' active market (series → ticker, expiry),
+    // its WS book cached for the entry path
+    let mut watchlist: HashMap<String, (String, Instant)> = HashMap::new();
+    let mut book_cache = BookCache::default();
+    // Streamed Binance spot book and trade state, beside the price feed
+    let mut spot_cache = SpotCache::new(config.spot_flow_window_secs);
+    // Open markets in the traded series quoted over `ticker_v2`
+    let mut quoted_tickers: HashSet<String> = HashSet::new();
+    // Pending trades whose settlement failed verification, already alerted
+    let mut held_settlements: HashSet<String> = HashSet::new();
+    // Resting entry orders → fill deadline
+    let mut resting_orders: HashMap<String, Instant> = handed_over_orders
+        .into_iter()
+        .map(|(order_id, secs_left)| (order_id, Instant::now() + Duration::from_secs(secs_left)))
+        .collect();
+    // Resting entries already considered for a reprice
+    let mut reprice_checked: HashSet<String> = HashSet::new();
+    let fill_timeout = Duration::from_secs(config.fill_timeout_secs);
+    // Set while the Kalshi WS is down: fills since then may have been missed
+    let mut fill_gap_since: Option<chrono::DateTime<chrono::Utc>> = None;
+    // Feeds past WS_RECONNECT_HALT_AFTER failed reconnects; entries wait for them
+    let mut ws_down: HashSet<&'static str> = HashSet::new();
+    // BUYs held back by the open-position cap
+    let mut entry_queue = EntryQueue::new(&config);
+
+    // Exchange-side position changes (including manual trades) and our orders' lifecycle
+    kalshi_ws_sender.subscribe_all(vec!["market_positions".into(), "user_orders".into()]).await;
+
+    if handoff {
+        // Subscribe restored positions before signalling readiness
+        let restored = position_mgr.position_tickers();
+        kalshi_ws_sender.subscribe_markets(
+            vec!["orderbook_delta".into(), "fill".into(), "market_lifecycle_v2".into()],
+            restored.clone(),
+        ).await;
+        subscribed_tickers.extend(restored);
+        safety::complete_handoff(HANDOFF_TIMEOUT).await?;
+    }
+    let mut handoff_timer = tokio::time::interval(std::time::Duration::from_secs(1));
+    let backup_store = open_backup_store(&config)?;
+    let mut backup_timer = tokio::time::interval(
+        std::time::Duration::from_secs(config.backup_interval_secs),
+    );
+    let mut equity_timer = tokio::time::interval(
+        std::time::Duration::from_secs(config.equity_interval_secs),
+    );
+    let metrics_sink = open_metrics_sink(&config)?;
+    let mut metrics_timer = tokio::time::interval(
+        std::time::Duration::from_secs(config.metrics_interval_secs),
+    );
+    let mut handing_off = false;
+    // Resume entries if the standby never signals ready (crashed or gave up)
+    let mut handoff_deadline: Option<tokio::time::Instant> = None;
+
+    // Recover orders placed by a previous run that crashed before its ledger write
+    for outcome in engine::reconcile_journal(&exchange, storage.as_ref()).await? {
+        track_entry(outcome, &mut position_mgr, &mut resting_orders, &mut entry_queue, fill_timeout);
+    }
 
     // Run initial entry cycles for all series
     tracing::info!("Running initial entry cycles for {} assets", config.series_tickers.len());
+    let paused = entries_paused(&exchange, &ws_down).await;
     for series in &config.series_tickers {
-        if let Err(e) = engine::entry_cycle(
-            &exchange, &brain, &price_feed, &config, &position_mgr, series
+        if shutdown.is_cancelled() || paused {
+            break;
+        }
+        match engine::entry_cycle(
+            &exchange, brain.as_ref(), price_feed.as_ref(), vol_feed.as_deref(), &notifier, storage.as_ref(), &config, &position_mgr, &fill_model, &book_cache, &spot_cache, &mut held_settlements, &resting_orders, series, None,
+            entry_queue.at_capacity(position_mgr.position_count() + resting_orders.len()), &shutdown
         ).await {
-            tracing::error!("[{}] Initial entry cycle error: {}", series, e);
+            Ok(outcome) => track_entry(outcome, &mut position_mgr, &mut resting_orders, &mut entry_queue, fill_timeout),
+            Err(e) if e.is::<ShutdownRequested>() => tracing::info!("[{}] Initial entry cycle aborted: {}", series, e),
+            Err(e) => tracing::error!("[{}] Initial entry cycle error: {}", series, e),
         }
     }
 
@@ -93,12 +577,44 @@ async fn main() -> anyhow::Result<()> {
 
         tokio::select! {
             Some(event) = kalshi_rx.recv() => {
+                // First event after a reconnect: recover fills missed in the gap
+                if !matches!(event, KalshiWsEvent::Disconnected | KalshiWsEvent::ReconnectFailed { .. }) {
+                    if ws_down.remove("Kalshi") {
+                        tracing::info!("Kalshi WS back — entries resume");
+                    }
+                    if let Some(since) = fill_gap_since.take() {
+                        let resting: Vec<String> = resting_orders.keys().cloned().collect();
+                        match engine::reconcile_fills(&exchange, &resting, since).await {
+                            Ok(fills) => {
+                                for fill in fills {
+                                    resting_orders.remove(&fill.order_id);
+                                    position_mgr.on_fill(&fill);
+                                }
+                            }
+                            Err(e) => {
+                                tracing::warn!("Fill reconciliation failed, retrying: {}", e);
+                                fill_gap_since = Some(since);
+                            }
+                        }
+                    }
+                }
                 match event {
                     KalshiWsEvent::Orderbook(update) => {
+                        book_cache.on_update(&update, chrono::Utc::now().timestamp_millis());
+                        if !subscribed_tickers.contains(&update.ticker) {
+                            // Watched or rollover market not yet traded: only the fill model learns from it
+                            if prewarmed_tickers.contains(&update.ticker) || is_watched(&watchlist, &update.ticker) {
+                                let at_ms = update.exchange_ts_ms.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+                                fill_model.on_orderbook_update(&update, at_ms);
+                            }
+                            continue;
+                        }
                         tracing::debug!(
                             "Orderbook update: {} yes_levels={} no_levels={}",
                             update.ticker, update.yes.len(), update.no.len()
                         );
+                        let at_ms = update.exchange_ts_ms.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+                        fill_model.on_orderbook_update(&update, at_ms);
                         position_mgr.on_orderbook_update(update);
                     }
                     KalshiWsEvent::Fill(fill) => {
@@ -108,6 +624,7 @@ async fn main() -> anyhow::Result<()> {
                             fill.ticker, fill.order_id
                         );
                         let ticker = fill.ticker.clone();
+                        resting_orders.remove(&fill.order_id);
                         position_mgr.on_fill(&fill);
 
                         // Subscribe to orderbook for the filled ticker
@@ -125,6 +642,17 @@ async fn main() -> anyhow::Result<()> {
                             lifecycle.ticker, lifecycle.status, lifecycle.result
                         );
                         if lifecycle.status == "settled" || lifecycle.status == "finalized" {
+                            let recorded = recorded_tickers.remove(&lifecycle.ticker);
+                            let prewarmed = prewarmed_tickers.remove(&lifecycle.ticker);
+                            let watched = is_watched(&watchlist, &lifecycle.ticker);
+                            watchlist.retain(|_, (ticker, _)| *ticker != lifecycle.ticker);
+                            if (recorded || prewarmed || watched) && !subscribed_tickers.contains(&lifecycle.ticker) {
+                                kalshi_ws_sender.unsubscribe(
+                                    vec!["orderbook_delta".into(), "market_lifecycle_v2".into()],
+                                    &lifecycle.ticker,
+                                ).await;
+                            }
+                            book_cache.forget(&lifecycle.ticker);
                             if position_mgr.position_for_ticker(&lifecycle.ticker).is_some() {
                                 tracing::info!("Market settled — clearing position on {}", lifecycle.ticker);
                                 position_mgr.clear_position(&lifecycle.ticker);
@@ -134,30 +662,163 @@ async fn main() -> anyhow::Result<()> {
                                     &lifecycle.ticker,
                                 ).await;
                                 subscribed_tickers.remove(&lifecycle.ticker);
+                                fill_model.forget_ticker(&lifecycle.ticker);
                             }
                         }
                     }
                     KalshiWsEvent::Disconnected => {
                         tracing::warn!("Kalshi WS disconnected — will auto-reconnect");
-                        // Re-subscribe all active tickers after reconnect
-                        for ticker in &subscribed_tickers {
+                        // The socket may have been dead a while before the read failed
+                        fill_gap_since.get_or_insert(chrono::Utc::now() - chrono::Duration::seconds(60));
+                        // Books come back with the resubscription snapshots;
+                        // the adapter replays every subscription on reconnect
+                        book_cache.clear();
+                    }
+                    KalshiWsEvent::Resubscribed { tickers } => {
+                        tracing::info!("Kalshi WS reconnected — {} market subscription(s) replayed", tickers);
+                    }
+                    KalshiWsEvent::Ticker(quote) => book_cache.on_ticker(&quote),
+                    KalshiWsEvent::Trade(trade) => book_cache.on_trade(&trade),
+                    KalshiWsEvent::Position(update) => {
+                        let entry_order_id = if position_mgr.holds(&update.ticker) || update.position == 0 {
+                            None
+                        } else {
+                            engine::own_entry(storage.as_ref(), &config, &update.ticker).await.unwrap_or_else(|e| {
+                                tracing::warn!("Ledger lookup for {} failed: {}", update.ticker, e);
+                                None
+                            })
+                        };
+                        if position_mgr.on_position_update(&update, entry_order_id.as_deref())
+                            && subscribed_tickers.insert(update.ticker.clone())
+                        {
                             kalshi_ws_sender.subscribe(
-                                vec!["orderbook_delta".into(), "fill".into(), "market_lifecycle_v2".into()],
-                                ticker,
+                                vec!["orderbook_delta".into(), "market_lifecycle_v2".into()],
+                                &update.ticker,
                             ).await;
                         }
                     }
+                    KalshiWsEvent::Order(state) => {
+                        tracing::debug!(
+                            "Order {} on {}: {:?}, {} filled / {} remaining",
+                            state.order_id, state.ticker, state.status, state.filled, state.remaining
+                        );
+                        // Canceled or expired before the fill timeout got to it
+                        if state.status == OrderStatus::Canceled && resting_orders.remove(&state.order_id).is_some() {
+                            match engine::on_order_closed(storage.as_ref(), &state).await {
+                                Ok(Some(fill)) => position_mgr.on_fill(&fill),
+                                Ok(None) => {}
+                                Err(e) => tracing::error!("Failed to settle canceled order {}: {}", state.order_id, e),
+                            }
+                        }
+                    }
+                    KalshiWsEvent::CommandFailed { cmd, channels, tickers, .. } if cmd == "subscribe" => {
+                        // Quotes are retried on the next entry tick; a held
+                        // position without its book can't be monitored
+                        for ticker in &tickers {
+                            quoted_tickers.remove(ticker);
+                        }
+                        let unmonitored: Vec<&String> = tickers
+                            .iter()
+                            .filter(|t| position_mgr.position_for_ticker(t).is_some())
+                            .collect();
+                        if channels.iter().any(|c| c == "orderbook_delta") && !unmonitored.is_empty() {
+                            let msg = format!(
+                                "Kalshi WS refused the orderbook subscription for held position(s): {:?}",
+                                unmonitored
+                            );
+                            if let Err(e) = notifier.alert(&msg).await {
+                                tracing::error!("{} (alert failed: {})", msg, e);
+                            }
+                        }
+                    }
+                    KalshiWsEvent::CommandFailed { .. } => {}
+                    KalshiWsEvent::Error { kind: WsErrorKind::Auth, .. } => {
+                        // Reconnecting signs the handshake again
+                        kalshi_ws_sender.sever(std::time::Duration::ZERO).await;
+                    }
+                    KalshiWsEvent::Error { kind: WsErrorKind::TooManySubscriptions, message, .. } => {
+                        let msg = format!(
+                            "Kalshi WS subscription limit hit ({} book, {} quote subscriptions): {}",
+                            subscribed_tickers.len(), quoted_tickers.len(), message
+                        );
+                        if let Err(e) = notifier.alert(&msg).await {
+                            tracing::error!("{} (alert failed: {})", msg, e);
+                        }
+                    }
+                    KalshiWsEvent::Error { .. } => {}
+                    KalshiWsEvent::BookGap { tickers } => {
+                        // The adapter resubscribes; the snapshot repopulates both
+                        for ticker in &tickers {
+                            book_cache.forget(ticker);
+                            position_mgr.drop_orderbook(ticker);
+                        }
+                    }
+                    KalshiWsEvent::ReconnectFailed { consecutive } => {
+                        if consecutive == WS_ALERT_AFTER_FAILURES {
+                            let msg = format!("Kalshi WS: {} reconnect attempts in a row have failed", consecutive);
+                            if let Err(e) = notifier.alert(&msg).await {
+                                tracing::error!("{} (alert failed: {})", msg, e);
+                            }
+                        }
+                        if consecutive == config.ws_reconnect_halt_after && ws_down.insert("Kalshi") {
+                            let msg = format!("Kalshi WS: {} failed reconnects — halting entries until it's back", consecutive);
+                            if let Err(e) = notifier.alert(&msg).await {
+                                tracing::error!("{} (alert failed: {})", msg, e);
+                            }
+                        }
+                    }
                 }
             }
 
-            Some(update) = binance_rx.recv() => {
-                tracing::debug!("{} price: ${:.2}", update.symbol, update.price);
-                latest_prices.insert(update.symbol, update.price);
-            }
+            Some(event) = binance_rx.recv() => match event {
+                binance_ws::BinanceWsEvent::Price(update) => {
+                    tracing::debug!("{} price: ${:.2}", update.symbol, update.price);
+                    if ws_down.remove("Binance") {
+                        tracing::info!("Binance WS back — entries resume");
+                    }
+                    if let Some(rec) = &recorder {
+                        rec.record(MarketDataEvent::Kline {
+                            symbol: update.symbol.clone(),
+                            event_time_ms: update.event_time_ms,
+                            closed: update.closed,
+                            candle: update.candle.clone(),
+                        });
+                    }
+                    price_feed.record_price(&update.symbol, update.price, update.event_time_ms);
+                    price_feed.record_kline(&update.symbol, &update.candle, update.closed);
+                }
+                binance_ws::BinanceWsEvent::Depth(depth) => {
+                    if ws_down.remove("Binance") {
+                        tracing::info!("Binance WS back — entries resume");
+                    }
+                    spot_cache.on_depth(&depth.symbol, &depth.bids, &depth.asks, depth.recv_ms);
+                }
+                binance_ws::BinanceWsEvent::Trade(trade) => {
+                    if ws_down.remove("Binance") {
+                        tracing::info!("Binance WS back — entries resume");
+                    }
+                    spot_cache.on_trade(&trade.symbol, trade.qty, trade.taker_buy, trade.trade_time_ms);
+                }
+                binance_ws::BinanceWsEvent::ReconnectFailed { consecutive } => {
+                    if consecutive == WS_ALERT_AFTER_FAILURES {
+                        let msg = format!("Binance WS: {} reconnect attempts in a row have failed", consecutive);
+                        if let Err(e) = notifier.alert(&msg).await {
+                            tracing::error!("{} (alert failed: {})", msg, e);
+                        }
+                    }
+                    if consecutive == config.ws_reconnect_halt_after && ws_down.insert("Binance") {
+                        let msg = format!("Binance WS: {} failed reconnects — halting entries until it's back", consecutive);
+                        if let Err(e) = notifier.alert(&msg).await {
+                            tracing::error!("{} (alert failed: {})", msg, e);
+                        }
+                    }
+                }
+            },
 
-            _ = entry_timer.tick() => {
-                let price_summary: Vec<String> = latest_prices.iter()
-                    .map(|(s, p)| format!("{}=${:.2}", s, p))
+            _ = entry_timer.tick(), if !handing_off => {
+                let price_summary: Vec<String> = price_symbols.iter()
+                    .filter_map(|s| price_feed.last_tick(s))
+                    .map(|t| format!("{}=${:.2}", t.symbol, t.price))
                     .collect();
                 tracing::info!(
                     "Entry cycle tick | {} positions | prices: {}",
@@ -165,12 +826,179 @@ async fn main() -> anyhow::Result<()> {
                     if price_summary.is_empty() { "none".into() } else { price_summary.join(", ") }
                 );
 
+                // Record every open market in the traded series, not just held ones
+                if recorder.is_some() {
+                    for series in &config.series_tickers {
+                        match exchange.open_markets(series).await {
+                            Ok(markets) => {
+                                let new: Vec<String> = markets
+                                    .into_iter()
+                                    .map(|m| m.ticker)
+                                    .filter(|t| !subscribed_tickers.contains(t) && recorded_tickers.insert(t.clone()))
+                                    .collect();
+                                kalshi_ws_sender.subscribe_markets(
+                                    vec!["orderbook_delta".into(), "market_lifecycle_v2".into()],
+                                    new,
+                                ).await;
+                            }
+                            Err(e) => tracing::warn!("[{}] Recorder market lookup failed: {}", series, e),
+                        }
+                    }
+                }
+
+                // Live quotes and public trades for every candidate market, without full books
+                let mut open_tickers: HashSet<String> = HashSet::new();
+                for series in &config.series_tickers {
+                    match exchange.open_markets(series).await {
+                        Ok(markets) => open_tickers.extend(markets.into_iter().map(|m| m.ticker)),
+                        Err(e) => {
+                            // Keep the series' quotes until a lookup succeeds
+                            tracing::warn!("[{}] Quote market lookup failed: {}", series, e);
+                            open_tickers.extend(
+                                quoted_tickers.iter().filter(|t| ticker_series(t) == series.as_str()).cloned(),
+                            );
+                        }
+                    }
+                }
+                kalshi_ws_sender.subscribe_markets(
+                    vec!["ticker_v2".into(), "trade".into()],
+                    open_tickers.difference(&quoted_tickers).cloned().collect(),
+                ).await;
+                for ticker in quoted_tickers.difference(&open_tickers) {
+                    kalshi_ws_sender.unsubscribe(vec!["ticker_v2".into(), "trade".into()], ticker).await;
+                    book_cache.forget_quote(ticker);
+                }
+                quoted_tickers = open_tickers;
+
                 // Run entry cycle for each series that doesn't have a position
+                let paused = entries_paused(&exchange, &ws_down).await;
                 for series in &config.series_tickers {
-                    if let Err(e) = engine::entry_cycle(
-                        &exchange, &brain, &price_feed, &config, &position_mgr, series
+                    if shutdown.is_cancelled() || paused {
+                        break;
+                    }
+                    if price_stale(&config, &stale_symbols, series) {
+                        tracing::info!("[{}] Skipping entry: spot price stale", series);
+                        continue;
+                    }
+                    match engine::entry_cycle(
+                        &exchange, brain.as_ref(), price_feed.as_ref(), vol_feed.as_deref(), &notifier, storage.as_ref(), &config, &position_mgr, &fill_model, &book_cache, &spot_cache, &mut held_settlements, &resting_orders, series, None,
+                        entry_queue.at_capacity(position_mgr.position_count() + resting_orders.len()), &shutdown
+                    ).await {
+                        Ok(outcome) => track_entry(outcome, &mut position_mgr, &mut resting_orders, &mut entry_queue, fill_timeout),
+                        Err(e) if e.is::<ShutdownRequested>() => tracing::info!("[{}] Entry cycle aborted: {}", series, e),
+                        Err(e) => tracing::error!("[{}] Entry cycle error: {}", series, e),
+                    }
+                }
+            }
+
+            _ = sleep_until_utc(rollovers.next_due()), if rollovers.next_due().is_some() && !handing_off => {
+                let paused = entries_paused(&exchange, &ws_down).await;
+                // Every due rollover is consumed; skipped ones fall back to the
+                // regular entry cycle, which trades the same active market
+                for (series, rollover) in rollovers.take_due(chrono::Utc::now()) {
+                    if shutdown.is_cancelled() || paused || price_stale(&config, &stale_symbols, &series) {
+                        tracing::info!("[{}] Rollover to {} skipped: entries paused or spot price stale", series, rollover.ticker);
+                        continue;
+                    }
+                    tracing::info!("[{}] Rollover: entry cycle on {} at open", series, rollover.ticker);
+                    match engine::entry_cycle(
+                        &exchange, brain.as_ref(), price_feed.as_ref(), vol_feed.as_deref(), &notifier, storage.as_ref(), &config, &position_mgr, &fill_model, &book_cache, &spot_cache, &mut held_settlements, &resting_orders, &series, Some(&rollover.ticker),
+                        entry_queue.at_capacity(position_mgr.position_count() + resting_orders.len()), &shutdown
                     ).await {
-                        tracing::error!("[{}] Entry cycle error: {}", series, e);
+                        Ok(outcome) => track_entry(outcome, &mut position_mgr, &mut resting_orders, &mut entry_queue, fill_timeout),
+                        Err(e) if e.is::<ShutdownRequested>() => tracing::info!("[{}] Rollover entry aborted: {}", series, e),
+                        Err(e) => tracing::error!("[{}] Rollover entry error: {}", series, e),
+                    }
+                }
+            }
+
+            _ = market_timer.tick() => {
+                // Line up each series' next market shortly before the current one expires
+                if config.rollover_lead_secs > 0 && !handing_off {
+                    for series in &config.series_tickers {
+                        match prefetch_rollover(&exchange, series, &mut rollovers).await {
+                            Ok(Some(ticker)) => {
+                                if !subscribed_tickers.contains(&ticker)
+                                    && !recorded_tickers.contains(&ticker)
+                                    && prewarmed_tickers.insert(ticker.clone())
+                                {
+                                    kalshi_ws_sender.subscribe(
+                                        vec!["orderbook_delta".into(), "market_lifecycle_v2".into()],
+                                        &ticker,
+                                    ).await;
+                                }
+                            }
+                            Ok(None) => {}
+                            Err(e) => tracing::warn!("[{}] Rollover pre-fetch failed: {}", series, e),
+                        }
+                    }
+                }
+
+                let now = Instant::now();
+
+                // Keep each series' active market on the orderbook watchlist
+                if !handing_off {
+                    for series in &config.series_tickers {
+                        if watchlist.get(series).is_some_and(|(_, expiry)| *expiry > now) {
+                            continue;
+                        }
+                        let market = match active_market(&exchange, series, &config).await {
+                            Ok(Some(m)) => m,
+                            Ok(None) => continue,
+                            Err(e) => {
+                                tracing::warn!("[{}] Watchlist market lookup failed: {}", series, e);
+                                continue;
+                            }
+                        };
+                        let expiry = now + Duration::from_secs_f64(market.minutes_to_expiry.max(0.0) * 60.0);
+                        let previous = watchlist.insert(series.clone(), (market.ticker.clone(), expiry));
+                        if let Some((old, _)) = previous.filter(|(old, _)| *old != market.ticker) {
+                            if !subscribed_tickers.contains(&old)
+                                && !recorded_tickers.contains(&old)
+                                && !prewarmed_tickers.contains(&old)
+                            {
+                                kalshi_ws_sender.unsubscribe(
+                                    vec!["orderbook_delta".into(), "market_lifecycle_v2".into()],
+                                    &old,
+                                ).await;
+                                book_cache.forget(&old);
+                            }
+                        }
+                        if !subscribed_tickers.contains(&market.ticker)
+                            && !recorded_tickers.contains(&market.ticker)
+                            && !prewarmed_tickers.contains(&market.ticker)
+                        {
+                            tracing::info!("[{}] Watching {}", series, market.ticker);
+                            kalshi_ws_sender.subscribe(
+                                vec!["orderbook_delta".into(), "market_lifecycle_v2".into()],
+                                &market.ticker,
+                            ).await;
+                        }
+                    }
+                }
+
+                // Halfway to the fill timeout, reprice entries stuck deep in the queue
+                if config.reprice_queue_ahead > 0 {
+                    reprice_checked.retain(|id| resting_orders.contains_key(id));
+                    let due: Vec<String> = resting_orders.iter()
+                        .filter(|(id, deadline)| {
+                            deadline.saturating_duration_since(now) <= fill_timeout / 2 && !reprice_checked.contains(*id)
+                        })
+                        .map(|(id, _)| id.clone())
+                        .collect();
+                    for order_id in due {
+                        reprice_checked.insert(order_id.clone());
+                        match engine::reprice_stuck_entry(&exchange, storage.as_ref(), &order_id, &config).await {
+                            // The fill timeout carries over to the amended order
+                            Ok(Some(new_id)) if new_id != order_id => {
+                                if let Some(deadline) = resting_orders.remove(&order_id) {
+                                    resting_orders.insert(new_id.clone(), deadline);
+                                }
+                                reprice_checked.insert(new_id);
+                            }
+                            Ok(_) => {}
+                            Err(e) => tracing::warn!("Failed to reprice order {}: {}", order_id, e),
+                        }
                     }
                 }
             }
@@ -188,30 +1016,487 @@ async fn main() -> anyhow::Result<()> {
                     let exits = position_mgr.check_exits();
                     for (ticker, reason) in exits {
                         tracing::info!("Exit signal: {:?} on {}", reason, ticker);
-                        if let Err(e) = engine::execute_exit(
-                            &exchange, &mut position_mgr, &ticker, reason, &config
+                        match engine::execute_exit(
+                            &exchange, storage.as_ref(), &mut position_mgr, &ticker, reason, &config, &shutdown
                         ).await {
-                            tracing::error!("Exit execution error on {}: {}", ticker, e);
+                            Ok(()) => {}
+                            Err(e) if e.is::<ShutdownRequested>() => {
+                                tracing::info!("Exit on {} aborted: {}", ticker, e);
+                                break;
+                            }
+                            Err(e) => tracing::error!("Exit execution error on {}: {}", ticker, e),
                         }
-                        // Unsubscribe from exited ticker
-                        kalshi_ws_sender.unsubscribe(
-                            vec!["orderbook_delta".into(), "fill".into(), "market_lifecycle_v2".into()],
-                            &ticker,
-                        ).await;
+                        // Unsubscribe from exited ticker; a watched market keeps its book feed
                         subscribed_tickers.remove(&ticker);
+                        if is_watched(&watchlist, &ticker) {
+                            kalshi_ws_sender.unsubscribe(vec!["fill".into()], &ticker).await;
+                        } else {
+                            kalshi_ws_sender.unsubscribe(
+                                vec!["orderbook_delta".into(), "fill".into(), "market_lifecycle_v2".into()],
+                                &ticker,
+                            ).await;
+                            fill_model.forget_ticker(&ticker);
+                            book_cache.forget(&ticker);
+                        }
+                    }
+                }
+
+                // Cancel resting entries that missed their fill timeout
+                let now = Instant::now();
+                let expired: Vec<String> = resting_orders.iter()
+                    .filter(|(_, deadline)| **deadline <= now)
+                    .map(|(id, _)| id.clone())
+                    .collect();
+                for order_id in expired {
+                    resting_orders.remove(&order_id);
+                    match engine::cancel_unfilled(&exchange, storage.as_ref(), &order_id).await {
+                        Ok(Some(fill)) => position_mgr.on_fill(&fill),
+                        Ok(None) => {}
+                        Err(e) => tracing::error!("Failed to cancel unfilled order {}: {}", order_id, e),
+                    }
+                }
+
+                // Hand freed slots to queued entries, oldest signal first
+                let drain = !handing_off && !entry_queue.is_empty() && !entries_paused(&exchange, &ws_down).await;
+                while drain
+                    && !entry_queue.at_capacity(position_mgr.position_count() + resting_orders.len())
+                {
+                    let Some(entry) = entry_queue.pop(chrono::Utc::now(), |e| !price_stale(&config, &stale_symbols, &e.series)) else {
+                        break;
+                    };
+                    let series = entry.series.clone();
+                    match engine::execute_queued(
+                        &exchange, &notifier, storage.as_ref(), &config, &position_mgr, &fill_model, &book_cache, entry, &shutdown
+                    ).await {
+                        Ok(outcome) => track_entry(outcome, &mut position_mgr, &mut resting_orders, &mut entry_queue, fill_timeout),
+                        Err(e) if e.is::<ShutdownRequested>() => {
+                            tracing::info!("[{}] Queued entry aborted: {}", series, e);
+                            break;
+                        }
+                        Err(e) => tracing::error!("[{}] Queued entry error: {}", series, e),
+                    }
+                }
+            }
+
+            _ = handoff_timer.tick() => {
+                match safety::read_handoff() {
+                    Some(safety::HandoffState::Requested) if !handing_off => {
+                        tracing::info!("Handoff requested — pausing entries, publishing snapshot");
+                        handing_off = true;
+                        handoff_deadline = Some(tokio::time::Instant::now() + HANDOFF_TIMEOUT);
+                        let now = Instant::now();
+                        let snapshot = HandoffSnapshot {
+                            positions: position_mgr.snapshot(),
+                            resting_orders: resting_orders
+                                .iter()
+                                .map(|(id, deadline)| (id.clone(), deadline.saturating_duration_since(now).as_secs()))
+                                .collect(),
+                        };
+                        if let Err(e) = storage::write_handoff_snapshot(&snapshot)
+                            .and_then(|_| safety::write_handoff(safety::HandoffState::SnapshotReady))
+                        {
+                            tracing::error!("Handoff snapshot failed: {} — resuming", e);
+                            safety::clear_handoff();
+                            handing_off = false;
+                            handoff_deadline = None;
+                        }
+                    }
+                    Some(safety::HandoffState::Ready) if handing_off => {
+                        tracing::info!("Standby ready — releasing lock and exiting");
+                        break;
+                    }
+                    _ if handoff_deadline.is_some_and(|d| tokio::time::Instant::now() >= d) => {
+                        tracing::warn!("Standby not ready within {:?} — resuming entries", HANDOFF_TIMEOUT);
+                        if let Err(e) = notifier.alert("Handoff timed out — resuming entries").await {
+                            tracing::warn!("Failed to send handoff alert: {}", e);
+                        }
+                        safety::clear_handoff();
+                        handing_off = false;
+                        handoff_deadline = None;
+                    }
+                    _ => {}
+                }
+            }
+
+            _ = equity_timer.tick() => {
+                if let Err(e) = engine::record_equity(&exchange, storage.as_ref(), &position_mgr).await {
+                    tracing::warn!("Failed to record equity sample: {}", e);
+                }
+            }
+
+            _ = metrics_timer.tick(), if metrics_sink.is_some() => {
+                if let Some(sink) = &metrics_sink {
+                    let mut labelled = exchange.api_metrics();
+                    labelled.extend(kalshi_ws_sender.health_gauges());
+                    labelled.extend(kalshi_bus.gauges());
+                    labelled.extend(binance_health.gauges());
+                    if let Err(e) = engine::push_metrics(
+                        &exchange, storage.as_ref(), &position_mgr, sink.as_ref(), &config, labelled
+                    ).await {
+                        tracing::warn!("Metrics push failed: {}", e);
+                    }
+                }
+            }
+
+            Some(msg) = feed_alert_rx.recv() => {
+                if let Err(e) = notifier.alert(&msg).await {
+                    tracing::error!("{} (alert failed: {})", msg, e);
+                }
+            }
+
+            Some(tick) = price_ticks.next() => {
+                last_price_at.insert(tick.symbol.clone(), Instant::now());
+                if stale_symbols.remove(&tick.symbol) {
+                    tracing::info!("Binance {} prices flowing again (${:.2}) — its entries resume", tick.symbol, tick.price);
+                }
+            }
+
+            _ = price_stale_timer.tick(), if config.price_stale_secs > 0 => {
+                let stale_after = Duration::from_secs(config.price_stale_secs);
+                let mut any_stale = false;
+                for (symbol, at) in &last_price_at {
+                    let age = at.elapsed();
+                    if age < stale_after {
+                        continue;
+                    }
+                    any_stale = true;
+                    if stale_symbols.insert(symbol.clone()) {
+                        let msg = format!(
+                            "Binance {} prices stale: none for {}s — halting its entries and reconnecting",
+                            symbol, age.as_secs()
+                        );
+                        if let Err(e) = notifier.alert(&msg).await {
+                            tracing::error!("{} (alert failed: {})", msg, e);
+                        }
                     }
                 }
+                // At most one forced reconnect per stale period, so it has time to deliver
+                if any_stale && stale_reconnect_at.is_none_or(|at| at.elapsed() >= stale_after) {
+                    binance_health.request_reconnect();
+                    stale_reconnect_at = Some(Instant::now());
+                }
+            }
+
+            _ = backup_timer.tick(), if backup_store.is_some() => {
+                // Uploads run off the event loop; a slow target never delays exits
+                if let Some(store) = backup_store.clone() {
+                    tokio::spawn(async move {
+                        if let Err(e) = backup::run(store.as_ref()).await {
+                            tracing::error!("Backup failed: {}", e);
+                        }
+                    });
+                }
             }
 
-            _ = shutdown_rx.changed() => {
-                if *shutdown_rx.borrow() {
-                    tracing::info!("Shutdown signal received — exiting event loop");
-                    break;
+            _ = shutdown.cancelled() => {
+                tracing::info!("Shutdown signal received — exiting event loop");
+                break;
+            }
+        }
+    }
+
+    // A standby taking over keeps the orders; a plain shutdown leaves none resting
+    if shutdown.is_cancelled() && !handing_off && !config.paper_trade {
+        match engine::cancel_on_shutdown(&exchange, storage.as_ref(), &config.series_tickers).await {
+            Ok(fills) => {
+                for fill in fills {
+                    tracing::warn!(
+                        "Shutdown: order {} had filled {}x on {} — position left open",
+                        fill.order_id, fill.shares, fill.ticker
+                    );
                 }
             }
+            Err(e) => tracing::error!("Shutdown: failed to cancel resting orders: {}", e),
         }
     }
 
+    // Unsubscribe and send Close frames rather than dropping the sockets
+    binance_closing.cancel();
+    kalshi_ws_sender.close(WS_CLOSE_TIMEOUT).await;
+    if tokio::time::timeout(WS_CLOSE_TIMEOUT, binance_task).await.is_err() {
+        tracing::warn!("Binance WS didn't close within {}s", WS_CLOSE_TIMEOUT.as_secs());
+    }
+
+    safety::release_lock();
     tracing::info!("kalshi-bot v2 daemon stopped");
     Ok(())
 }
+
+/// `--series S --ticker T --result R --side yes|no --from D --to D`, where
+/// dates are RFC 3339 or `YYYY-MM-DD` (UTC midnight).
+fn parse_query_filter(args: &[String]) -> anyhow::Result<QueryFilter> {
+    let parse_date = |v: &str| -> anyhow::Result<chrono::DateTime<chrono::Utc>> {
+        if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(v) {
+            return Ok(ts.with_timezone(&chrono::Utc));
+        }
+        let day = chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d")?;
+        Ok(day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+    };
+
+    let mut filter = QueryFilter::default();
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            anyhow::bail!("Missing value for {}", pair[0]);
+        };
+        match flag.as_str() {
+            "--series" => filter.series = Some(value.clone()),
+            "--ticker" => filter.ticker = Some(value.clone()),
+            "--result" => {
+                filter.result = Some(
+                    ResultFilter::parse(value)
+                        .ok_or_else(|| anyhow::anyhow!("Unknown result filter: {}", value))?,
+                )
+            }
+            "--side" => {
+                filter.side = Some(match value.as_str() {
+                    "yes" => Side::Yes,
+                    "no" => Side::No,
+                    other => anyhow::bail!("Unknown side: {}", other),
+                })
+            }
+            "--from" => filter.from = Some(parse_date(value)?),
+            "--to" => filter.to = Some(parse_date(value)?),
+            other => anyhow::bail!("Unknown query-ledger flag: {}", other),
+        }
+    }
+    Ok(filter)
+}
+
+/// A `settle`/`amend` request from the command line.
+struct Correction {
+    amendment: LedgerAmendment,
+    reason: String,
+    by: String,
+}
+
+impl Correction {
+    /// `settle` only closes out a trade: it needs a final result and P&L.
+    fn check_settle(&self) -> anyhow::Result<()> {
+        match self.amendment.result.as_deref() {
+            Some("win" | "loss") if self.amendment.pnl_cents.is_some() => Ok(()),
+            _ => anyhow::bail!("settle needs --result win|loss and --pnl"),
+        }
+    }
+}
+
+fn parse_correction(args: &[String]) -> anyhow::Result<Correction> {
+    let mut amendment = LedgerAmendment::default();
+    let mut reason = None;
+    let mut by = std::env::var("USER").unwrap_or_else(|_| "unknown".into());
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            anyhow::bail!("Missing value for {}", pair[0]);
+        };
+        match flag.as_str() {
+            "--result" => {
+                if !matches!(value.as_str(), "pending" | "win" | "loss" | "cancelled") && !value.starts_with("exit_") {
+                    anyhow::bail!("Unknown result: {} (pending, win, loss, cancelled, exit_*)", value);
+                }
+                amendment.result = Some(value.clone());
+            }
+            "--pnl" => amendment.pnl_cents = Some(value.parse()?),
+            "--side" => {
+                if !matches!(value.as_str(), "yes" | "no") {
+                    anyhow::bail!("Unknown side: {}", value);
+                }
+                amendment.side = Some(value.clone());
+            }
+            "--shares" => amendment.shares = Some(value.parse()?),
+            "--price" => amendment.price = Some(value.parse()?),
+            "--reason" => reason = Some(value.clone()),
+            "--by" => by = value.clone(),
+            other => anyhow::bail!("Unknown flag: {}", other),
+        }
+    }
+    if amendment.is_empty() {
+        anyhow::bail!("Nothing to change");
+    }
+    let Some(reason) = reason.filter(|r| !r.trim().is_empty()) else {
+        anyhow::bail!("--reason is required for manual corrections");
+    };
+    Ok(Correction { amendment, reason, by })
+}
+
+/// Apply an audited manual correction: snapshot (file backend), amend the
+/// row, journal who/when/why with the before and after rows, refresh stats.
+async fn correct_ledger(config: &Config, cmd: &str, order_id: &str, c: Correction) -> anyhow::Result<()> {
+    // The file ledger must not be rewritten under a running daemon
+    let file_backend = config.database_url.is_none();
+    if file_backend {
+        safety::acquire_lock()?;
+    }
+    let result = async {
+        let storage = open_storage(config).await?;
+        if cmd == "settle" {
+            let rows = storage
+                .query_ledger(&QueryFilter { result: Some(ResultFilter::Pending), ..Default::default() })
+                .await?;
+            if !rows.iter().any(|r| r.order_id == order_id) {
+                anyhow::bail!("{} is not a pending trade — use `amend` to change a settled row", order_id);
+            }
+        }
+        if file_backend {
+            snapshot::take(cmd)?;
+        }
+
+        let (before, after) = storage.amend_ledger(order_id, &c.amendment).await?;
+        storage
+            .journal(&JournalEvent::Amended {
+                order_id: order_id.to_string(),
+                by: c.by.clone(),
+                reason: c.reason.clone(),
+                before: Box::new(before.clone()),
+                after: Box::new(after.clone()),
+            })
+            .await?;
+        storage.write_stats(&core::stats::compute(&storage.read_ledger().await?)).await?;
+
+        println!(
+            "{} {} by {}: {} {}¢ (cum {}¢) -> {} {}¢ (cum {}¢) | {}",
+            cmd, order_id, c.by,
+            before.result, before.pnl_cents, before.cumulative_cents,
+            after.result, after.pnl_cents, after.cumulative_cents,
+            c.reason
+        );
+        Ok(())
+    }
+    .await;
+    if file_backend {
+        safety::release_lock();
+    }
+    result
+}
+
+/// Auto-discover series to trade from the configured category and frequency.
+async fn discover_series(config: &Config) -> anyhow::Result<Vec<SeriesInfo>> {
+    let exchange = KalshiClient::new(config)?;
+    engine::discover_series(&exchange, config).await
+}
+
+/// The configured OpenRouter model, behind the complexity router when
+/// OPENROUTER_CHEAP_MODEL is set.
+fn open_brain(config: &Config) -> anyhow::Result<Box<dyn Brain>> {
+    let expensive = Box::new(OpenRouterClient::new(config)?);
+    let Some(policy) = config.model_routing.clone() else {
+        return Ok(expensive);
+    };
+    let cheap: Option<Box<dyn Brain>> = match &policy.cheap_model {
+        Some(model) => Some(Box::new(OpenRouterClient::with_model(config, model)?)),
+        None => None,
+    };
+    tracing::info!(
+        "Model routing: calm markets → {}, complex → {}",
+        policy.cheap_model.as_deref().unwrap_or("quant"), config.openrouter_model
+    );
+    Ok(Box::new(RoutedBrain::new(policy, expensive, config.openrouter_model.clone(), cheap)))
+}
+
+/// If `series`' current market is within the rollover lead of expiry, queue
+/// its successor and return the successor's ticker to pre-subscribe.
+async fn prefetch_rollover(
+    exchange: &dyn Exchange,
+    series: &str,
+    rollovers: &mut Rollovers,
+) -> anyhow::Result<Option<String>> {
+    let open = exchange.open_markets(series).await?;
+    let Some(current) = open.first() else {
+        return Ok(None);
+    };
+    if !rollovers.wants_prefetch(series, current) {
+        return Ok(None);
+    }
+    // A later-expiring market already open, else the first one listed but unopened
+    let next = match open.iter().find(|m| m.minutes_to_expiry > current.minutes_to_expiry + 1.0) {
+        Some(m) => Some(m.clone()),
+        None => exchange.upcoming_markets(series).await?.into_iter().next(),
+    };
+    let Some(next) = next else {
+        tracing::debug!("[{}] Rollover: no successor to {} listed yet", series, current.ticker);
+        return Ok(None);
+    };
+    if !rollovers.schedule(series, current, &next) {
+        return Ok(None);
+    }
+    tracing::info!(
+        "[{}] Rollover: {} expires in {:.1}min — queued {} (opens {})",
+        series, current.ticker, current.minutes_to_expiry, next.ticker, next.open_time
+    );
+    Ok(Some(next.ticker))
+}
+
+/// Check the exchange before a batch of entries: true (skip them) while
+/// trading is halted or in maintenance, or a WS feed is down past
+/// `WS_RECONNECT_HALT_AFTER`. A failed check doesn't block.
+async fn entries_paused(exchange: &dyn Exchange, ws_down: &HashSet<&'static str>) -> bool {
+    if !ws_down.is_empty() {
+        let mut feeds: Vec<&str> = ws_down.iter().copied().collect();
+        feeds.sort();
+        tracing::info!("Skipping entries: {} WS down", feeds.join(" and "));
+        return true;
+    }
+    match engine::entry_block(exchange).await {
+        Ok(Some(reason)) => {
+            tracing::info!("Skipping entries: {}", reason);
+            true
+        }
+        Ok(None) => false,
+        Err(e) => {
+            tracing::warn!("Exchange status check failed: {}", e);
+            false
+        }
+    }
+}
+
+/// Whether `series`' spot symbol has gone without a streamed price past
+/// `PRICE_STALE_SECS`.
+fn price_stale(config: &Config, stale_symbols: &HashSet<String>, series: &str) -> bool {
+    config.price_symbol(series).is_some_and(|s| stale_symbols.contains(s))
+}
+
+/// The market `series`' entry cycle would pick right now.
+async fn active_market(
+    exchange: &dyn Exchange,
+    series: &str,
+    config: &Config,
+) -> anyhow::Result<Option<MarketState>> {
+    let open = exchange.open_markets(series).await?;
+    Ok(market_select::select(open, series, &config.market_overrides))
+}
+
+fn is_watched(watchlist: &HashMap<String, (String, Instant)>, ticker: &str) -> bool {
+    watchlist.values().any(|(t, _)| t == ticker)
+}
+
+/// Sleep until `due`, for the rollover branch of the event loop.
+async fn sleep_until_utc(due: Option<chrono::DateTime<chrono::Utc>>) {
+    let wait = due
+        .and_then(|d| (d - chrono::Utc::now()).to_std().ok())
+        .unwrap_or_default();
+    tokio::time::sleep(wait).await
+}
+
+/// Apply an entry outcome: open filled positions immediately, arm the fill
+/// timeout for resting orders, queue BUYs held back by the position cap.
+fn track_entry(
+    outcome: EntryOutcome,
+    position_mgr: &mut PositionManager,
+    resting_orders: &mut HashMap<String, Instant>,
+    entry_queue: &mut EntryQueue,
+    fill_timeout: Duration,
+) {
+    match outcome {
+        EntryOutcome::Filled(fill) => position_mgr.on_fill(&fill),
+        EntryOutcome::Resting { order_id, ticker } => {
+            tracing::info!(
+                "Order {} resting on {} — fill timeout {}s",
+                order_id, ticker, fill_timeout.as_secs()
+            );
+            resting_orders.insert(order_id, Instant::now() + fill_timeout);
+        }
+        EntryOutcome::Queued(entry) => {
+            entry_queue.push(*entry);
+            tracing::info!("Entry queue: {} waiting for a slot", entry_queue.len());
+        }
+        EntryOutcome::NoTrade | EntryOutcome::Paper => {}
+    }
+}
@@ -0,0 +1,10 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Off-box object store for brain/ backups.
+#[async_trait]
+pub trait BackupStore: Send + Sync {
+    /// Upload `body` under `key` (a `/`-separated relative path), replacing
+    /// any existing object.
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()>;
+}
@@ -4,13 +4,77 @@ use async_trait::async_trait;
 
 #[async_trait]
 pub trait Exchange: Send + Sync {
-    async fn active_market(&self, series_ticker: &str) -> Result<Option<MarketState>>;
+    /// Open markets in a series that haven't expired, soonest expiry first.
+    async fn open_markets(&self, series_ticker: &str) -> Result<Vec<MarketState>>;
+    /// Listed but not yet open markets in a series, soonest open first.
+    async fn upcoming_markets(&self, series_ticker: &str) -> Result<Vec<MarketState>>;
+    /// Current state of one market by ticker.
+    async fn market(&self, ticker: &str) -> Result<MarketState>;
+    /// Series listed under a Kalshi category (e.g. "Crypto").
+    async fn series(&self, category: &str) -> Result<Vec<SeriesInfo>>;
+    /// An event with all of its markets — the full strike ladder.
+    async fn event(&self, event_ticker: &str) -> Result<Event>;
     async fn orderbook(&self, ticker: &str) -> Result<Orderbook>;
+    /// The market's own price history since `since`, in `period_minutes`
+    /// candles (1, 60, or 1440), oldest first.
+    async fn market_candles(
+        &self,
+        ticker: &str,
+        since: chrono::DateTime<chrono::Utc>,
+        period_minutes: u32,
+    ) -> Result<Vec<ContractCandle>>;
     async fn resting_orders(&self) -> Result<Vec<RestingOrder>>;
+    /// Look up an order by the client order id it was submitted with.
+    async fn order_by_client_id(
+        &self,
+        ticker: &str,
+        client_order_id: &str,
+    ) -> Result<Option<OrderResult>>;
+    /// Current status and fill progress of an order.
+    async fn get_order(&self, order_id: &str) -> Result<OrderState>;
+    /// Where a resting buy sits in its price level's queue, estimated from
+    /// orderbook depth and the order's remaining size.
+    async fn queue_position(&self, order_id: &str) -> Result<QueuePosition> {
+        let order = self.get_order(order_id).await?;
+        let book = self.orderbook(&order.ticker).await?;
+        Ok(QueuePosition::estimate(&order, &book))
+    }
     async fn cancel_order(&self, order_id: &str) -> Result<()>;
+    /// Cancel every resting order — or only those on `scope`, a market or
+    /// series ticker — in batches. Returns the ids actually canceled.
+    async fn cancel_all_orders(&self, scope: Option<&str>) -> Result<Vec<String>>;
+    /// Reprice/resize a resting order in place, keeping its queue position
+    /// where the exchange allows. `new_price_cents` is on the order's own side.
+    async fn amend_order(
+        &self,
+        order_id: &str,
+        new_price_cents: u32,
+        new_count: u32,
+    ) -> Result<OrderResult>;
     async fn place_order(&self, order: &OrderRequest) -> Result<OrderResult>;
-    async fn sell_order(&self, order: &OrderRequest) -> Result<OrderResult>;
+    /// Close (part of) a position: a reduce-only `action: sell` on the
+    /// order's side, clamped to the position currently held on that side.
+    /// Fails with `SellExceedsPosition`, placing nothing, when none is held.
+    async fn sell_order(&self, order: &OrderRequest) -> Result<SellResult>;
     async fn positions(&self) -> Result<Vec<Position>>;
+    /// Buy fills since `since`, oldest first, on one market or (None) all
+    /// of them — for recovering fills the WS feed missed.
+    async fn fills(
+        &self,
+        ticker: Option<&str>,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<FillEvent>>;
     async fn settlements(&self, ticker: &str) -> Result<Vec<Settlement>>;
+    /// Final `result` of a market ("yes"/"no"), or None if not determined yet.
+    async fn market_result(&self, ticker: &str) -> Result<Option<String>>;
     async fn balance(&self) -> Result<u64>;
+    /// Balance, positions, and resting orders in one concurrent fetch, for
+    /// callers that need a consistent view of the account.
+    async fn portfolio_snapshot(&self) -> Result<PortfolioSnapshot> {
+        let (balance_cents, positions, resting_orders) =
+            tokio::try_join!(self.balance(), self.positions(), self.resting_orders())?;
+        Ok(PortfolioSnapshot { balance_cents, positions, resting_orders })
+    }
+    /// Whether the exchange is trading, plus its maintenance schedule.
+    async fn exchange_status(&self) -> Result<ExchangeStatus>;
 }
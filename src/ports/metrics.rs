@@ -0,0 +1,11 @@
+use crate::core::types::MetricsSample;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Shared metrics collector that several bot instances push to.
+#[async_trait]
+pub trait MetricsSink: Send + Sync {
+    /// Publish the sample's gauges under its instance labels, replacing the
+    /// previous push from the same instance.
+    async fn push(&self, sample: &MetricsSample) -> Result<()>;
+}
@@ -1,3 +1,8 @@
+pub mod backup;
 pub mod brain;
 pub mod exchange;
+pub mod metrics;
+pub mod notifier;
 pub mod price_feed;
+pub mod storage;
+pub mod vol_feed;
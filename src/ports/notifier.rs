@@ -0,0 +1,9 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn alert(&self, message: &str) -> Result<()>;
+    /// Informational message (trade placed), as opposed to an alert.
+    async fn notify(&self, message: &str) -> Result<()>;
+}
@@ -1,7 +1,10 @@
-use crate::core::types::Candle;
+use crate::core::types::{Candle, PerpQuote, PriceTick};
 use anyhow::Result;
 use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream, StreamExt};
 
+/// Spot prices and candles. `Ok(None)` means no data; failures err with a
+/// `PriceFeedError` where the feed can tell a blip from an outage.
 #[async_trait]
 pub trait PriceFeed: Send + Sync {
     async fn candles(
@@ -12,4 +15,32 @@ pub trait PriceFeed: Send + Sync {
     ) -> Result<Option<Vec<Candle>>>;
 
     async fn spot_price(&self, symbol: &str) -> Result<Option<f64>>;
+
+    /// Perpetual futures mark price and funding rate.
+    async fn perp(&self, _symbol: &str) -> Result<Option<PerpQuote>> {
+        Ok(None)
+    }
+
+    /// Feed a streamed price (from the WS kline stream) into the realized-vol estimator.
+    fn record_price(&self, _symbol: &str, _price: f64, _ts_ms: i64) {}
+
+    /// Feed a streamed kline (in progress unless `closed`) into any local candle history.
+    fn record_kline(&self, _symbol: &str, _candle: &Candle, _closed: bool) {}
+
+    /// Rolling realized volatility (%) from streamed high-frequency prices.
+    fn realized_vol(&self, _symbol: &str) -> Option<f64> {
+        None
+    }
+
+    /// Push updates of `symbol`'s streamed price, starting with the latest
+    /// one. The daemon's per-symbol staleness check is the one consumer.
+    /// Empty unless the feed publishes ticks (`PriceTicks`).
+    fn subscribe(&self, _symbol: &str) -> BoxStream<'static, PriceTick> {
+        stream::empty().boxed()
+    }
+
+    /// The latest streamed price tick for `symbol`.
+    fn last_tick(&self, _symbol: &str) -> Option<PriceTick> {
+        None
+    }
 }
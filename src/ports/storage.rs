@@ -0,0 +1,38 @@
+use crate::core::stats::CounterSnapshot;
+use crate::core::types::*;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn read_prompt(&self) -> Result<String>;
+    async fn read_ledger(&self) -> Result<Vec<LedgerRow>>;
+    /// Ledger rows matching `filter`, in ledger order.
+    async fn query_ledger(&self, filter: &QueryFilter) -> Result<Vec<LedgerRow>>;
+    async fn append_ledger(&self, row: &LedgerRow) -> Result<()>;
+    async fn settle_trade(&self, settlement: &Settlement) -> Result<()>;
+    async fn cancel_trade(&self, order_id: &str) -> Result<()>;
+    async fn record_early_exit(&self, exit: &ExitEvent) -> Result<()>;
+    /// Apply a manual correction to the row with `order_id`, shifting the
+    /// cumulative P&L of it and every later row. Returns (before, after).
+    async fn amend_ledger(
+        &self,
+        order_id: &str,
+        amendment: &LedgerAmendment,
+    ) -> Result<(LedgerRow, LedgerRow)>;
+    async fn write_stats(&self, stats: &Stats) -> Result<()>;
+    /// Record a point in the stats time series after a settlement or exit.
+    async fn append_stats_history(&self, ticker: &str, stats: &Stats) -> Result<()>;
+    /// Record a top-of-book sample for venue-quality tracking.
+    async fn append_book_sample(&self, sample: &BookSample) -> Result<()>;
+    /// Record a point on the equity curve.
+    async fn append_equity_sample(&self, sample: &EquitySample) -> Result<()>;
+    async fn write_decision(&self, record: &DecisionRecord<'_>) -> Result<()>;
+    /// Durably append to the write-ahead order journal.
+    async fn journal(&self, event: &JournalEvent) -> Result<()>;
+    async fn unresolved_orders(&self) -> Result<Vec<UnresolvedOrder>>;
+    /// Drop resolved entries from the journal.
+    async fn compact_journal(&self) -> Result<()>;
+    /// In-memory risk counters, updated as trades settle.
+    fn counters(&self) -> CounterSnapshot;
+}
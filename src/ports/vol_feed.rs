@@ -0,0 +1,10 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Source of market-implied volatility for an underlying.
+#[async_trait]
+pub trait VolFeed: Send + Sync {
+    /// Current annualized implied volatility (%) for a Binance symbol's
+    /// asset, or None if the source doesn't cover it.
+    async fn implied_vol(&self, symbol: &str) -> Result<Option<f64>>;
+}
@@ -0,0 +1,137 @@
+use crate::adapters::kalshi::event_bus::KalshiEventBus;
+use crate::adapters::ws_tap::{RawFrame, RawTap};
+use crate::core::types::{MarketDataEvent, RecordedEvent};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Events buffered between the event loop and the writer task. When full,
+/// new events are dropped rather than stalling the loop.
+const CHANNEL_CAPACITY: usize = 10_000;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Captures market data to `<dir>/<YYYY-MM-DD>.jsonl` (one file per UTC day)
+/// for replay. Writes happen on a background task so a slow disk never
+/// delays order handling.
+#[derive(Clone)]
+pub struct Recorder {
+    tx: mpsc::Sender<RecordedEvent>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl Recorder {
+    pub fn start(dir: &str) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        tokio::spawn(write_loop(PathBuf::from(dir), rx));
+        tracing::info!("Recording market data to {}/", dir);
+        Ok(Self {
+            tx,
+            dropped: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Raw frames from both WS adapters, recorded with their receive times.
+    pub fn raw_tap(&self) -> RawTap {
+        let (tx, mut rx) = mpsc::channel::<RawFrame>(CHANNEL_CAPACITY);
+        let recorder = self.clone();
+        tokio::spawn(async move {
+            while let Some(frame) = rx.recv().await {
+                let event = MarketDataEvent::Raw { source: frame.source.to_string(), frame: frame.text };
+                recorder.record_at(frame.recv_ms, event);
+            }
+        });
+        RawTap::new(tx)
+    }
+
+    /// Record Kalshi books and lifecycle events straight off the WS event bus.
+    pub fn follow(&self, bus: &KalshiEventBus) {
+        let mut books = bus.orderbooks("recorder", CHANNEL_CAPACITY);
+        let mut lifecycle = bus.lifecycle("recorder", CHANNEL_CAPACITY);
+        let recorder = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    Some(book) = books.recv() => recorder.record(MarketDataEvent::Orderbook(book)),
+                    Some(event) = lifecycle.recv() => recorder.record(MarketDataEvent::Lifecycle(event)),
+                    else => break,
+                }
+            }
+        });
+    }
+
+    pub fn record(&self, event: MarketDataEvent) {
+        self.record_at(chrono::Utc::now().timestamp_millis(), event);
+    }
+
+    /// Record with a receive time taken elsewhere, e.g. a raw WS frame's.
+    pub fn record_at(&self, recv_ms: i64, event: MarketDataEvent) {
+        let recorded = RecordedEvent { recv_ms, event };
+        if self.tx.try_send(recorded).is_err() {
+            let n = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if n.is_power_of_two() {
+                tracing::warn!("Recorder backlog full — {} events dropped so far", n);
+            }
+        }
+    }
+}
+
+/// The day's file, reopened when the UTC date rolls over.
+struct DayFile {
+    day: String,
+    writer: BufWriter<File>,
+}
+
+async fn write_loop(dir: PathBuf, mut rx: mpsc::Receiver<RecordedEvent>) {
+    let mut current: Option<DayFile> = None;
+    let mut flush = tokio::time::interval(FLUSH_INTERVAL);
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let Some(event) = event else { break };
+                if let Err(e) = write_event(&dir, &mut current, &event) {
+                    tracing::warn!("Recorder write failed: {}", e);
+                }
+            }
+            _ = flush.tick() => {
+                if let Some(f) = current.as_mut() {
+                    if let Err(e) = f.writer.flush() {
+                        tracing::warn!("Recorder flush failed: {}", e);
+                    }
+                }
+            }
+        }
+    }
+    if let Some(mut f) = current {
+        let _ = f.writer.flush();
+    }
+}
+
+fn write_event(
+    dir: &std::path::Path,
+    current: &mut Option<DayFile>,
+    event: &RecordedEvent,
+) -> anyhow::Result<()> {
+    let day = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    if current.as_ref().is_none_or(|f| f.day != day) {
+        if let Some(mut old) = current.take() {
+            old.writer.flush()?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(format!("{}.jsonl", day)))?;
+        *current = Some(DayFile {
+            day,
+            writer: BufWriter::new(file),
+        });
+    }
+    let f = current.as_mut().expect("day file opened above");
+    serde_json::to_writer(&mut f.writer, event)?;
+    f.writer.write_all(b"\n")?;
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use crate::core::types::{Action, DecisionContext, LedgerRow, Side};
+use crate::ports::brain::Brain;
+use crate::storage;
+
+#[derive(Debug, Default)]
+struct Summary {
+    replayed: u32,
+    skipped: u32,
+    same: u32,
+    flipped: u32,
+    passed: u32,
+    /// Over settled (win/loss) trades only.
+    scored: u32,
+    original_pnl_cents: i64,
+    replay_pnl_cents: i64,
+}
+
+/// Re-run every persisted decision context through `prompt_md` (and the
+/// configured model) and compare the new decisions against the originals and
+/// the settled outcomes. Offline apart from the model calls — nothing is traded.
+pub async fn redecide(brain: &dyn Brain, prompt_md: &str) -> anyhow::Result<()> {
+    let records = storage::read_decisions()?;
+    let ledger = storage::read_ledger()?;
+    let mut summary = Summary::default();
+
+    for record in &records {
+        let order_id = record["order_id"].as_str().unwrap_or("");
+        let Some(mut context) = record
+            .get("context")
+            .and_then(|c| serde_json::from_value::<DecisionContext>(c.clone()).ok())
+        else {
+            // Recorded before contexts were persisted
+            summary.skipped += 1;
+            continue;
+        };
+        let original_side: Option<Side> = serde_json::from_value(record["decision"]["side"].clone()).ok();
+
+        context.prompt_md = prompt_md.to_string();
+        let decision = match brain.decide(&context).await {
+            Ok(d) => d,
+            Err(e) => {
+                tracing::warn!("Replay of {} failed: {}", order_id, e);
+                summary.skipped += 1;
+                continue;
+            }
+        };
+        summary.replayed += 1;
+
+        let replay_side = match decision.action {
+            Action::Buy => Some(decision.side.clone().unwrap_or(Side::Yes)),
+            Action::Pass => None,
+        };
+        let verdict = match (&replay_side, &original_side) {
+            (None, _) => {
+                summary.passed += 1;
+                "PASS"
+            }
+            (Some(new), Some(old)) if new == old => {
+                summary.same += 1;
+                "same"
+            }
+            (Some(_), _) => {
+                summary.flipped += 1;
+                "FLIP"
+            }
+        };
+
+        let row = ledger.iter().find(|r| r.order_id == order_id);
+        let outcome = row.map(|r| r.result.as_str()).unwrap_or("unknown");
+        if let Some(row) = row.filter(|r| r.result == "win" || r.result == "loss") {
+            summary.scored += 1;
+            summary.original_pnl_cents += row.pnl_cents;
+            summary.replay_pnl_cents += replay_pnl(row, verdict);
+        }
+
+        println!(
+            "{} {} | was {:?} → {} ({:?} est {}) | outcome {} | {}",
+            order_id,
+            context.market.ticker,
+            original_side,
+            verdict,
+            replay_side,
+            decision
+                .estimated_probability
+                .map(|p| format!("{:.0}%", p))
+                .unwrap_or_else(|| "n/a".into()),
+            outcome,
+            decision.reasoning.lines().next().unwrap_or(""),
+        );
+    }
+
+    println!(
+        "\n{} replayed, {} skipped | same {} / flipped {} / pass {}",
+        summary.replayed, summary.skipped, summary.same, summary.flipped, summary.passed
+    );
+    println!(
+        "Settled trades: {} | original P&L {}¢ | replay P&L {}¢ (flips approximated)",
+        summary.scored, summary.original_pnl_cents, summary.replay_pnl_cents
+    );
+    Ok(())
+}
+
+/// What the replayed decision would have made on a settled trade. A flip buys
+/// the other side at the complement price, so its result inverts.
+fn replay_pnl(row: &LedgerRow, verdict: &str) -> i64 {
+    let shares = row.shares as i64;
+    let price = row.price as i64;
+    match verdict {
+        "same" => row.pnl_cents,
+        "FLIP" if row.result == "loss" => shares * price,
+        "FLIP" => -shares * (100 - price),
+        _ => 0,
+    }
+}
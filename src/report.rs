@@ -0,0 +1,378 @@
+use crate::core::{logistic, stats};
+use crate::core::types::{BookSample, LedgerRow, QueryFilter, ResultFilter, Strategy};
+use crate::storage;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// A series is flagged when its median spread widens by this factor…
+const SPREAD_DEGRADED_RATIO: f64 = 1.5;
+/// …or its mean top-of-book depth shrinks below this fraction, week over week.
+const DEPTH_DEGRADED_RATIO: f64 = 0.5;
+
+#[derive(Debug, Default)]
+struct VenueQuality {
+    samples: usize,
+    median_spread: Option<f64>,
+    mean_depth: f64,
+    /// Share of samples without a two-sided book.
+    one_sided: f64,
+}
+
+fn venue_quality<'a>(samples: impl Iterator<Item = &'a BookSample>) -> VenueQuality {
+    let samples: Vec<&BookSample> = samples.collect();
+    if samples.is_empty() {
+        return VenueQuality::default();
+    }
+    let mut spreads: Vec<u32> = samples.iter().filter_map(|s| s.spread_cents).collect();
+    spreads.sort_unstable();
+    let median_spread = match spreads.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(spreads[n / 2] as f64),
+        n => Some((spreads[n / 2 - 1] + spreads[n / 2]) as f64 / 2.0),
+    };
+    let n = samples.len() as f64;
+    VenueQuality {
+        samples: samples.len(),
+        median_spread,
+        mean_depth: samples.iter().map(|s| (s.bid_depth + s.ask_depth) as f64).sum::<f64>() / n,
+        one_sided: (samples.len() - spreads.len()) as f64 / n,
+    }
+}
+
+fn in_range(sample: &BookSample, from: DateTime<Utc>, to: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(&sample.timestamp)
+        .map(|ts| ts >= from && ts < to)
+        .unwrap_or(false)
+}
+
+fn fmt_spread(s: Option<f64>) -> String {
+    s.map(|s| format!("{:.1}¢", s)).unwrap_or_else(|| "n/a".into())
+}
+
+/// Strategy behind each recorded order id, from the decision records.
+/// Records written before strategies existed were momentum.
+fn strategies_by_order() -> anyhow::Result<HashMap<String, Strategy>> {
+    let mut out = HashMap::new();
+    for record in storage::read_decisions()? {
+        let Some(order_id) = record["order_id"].as_str() else { continue };
+        let strategy = record["strategy"]
+            .as_str()
+            .and_then(Strategy::parse)
+            .unwrap_or_default();
+        out.insert(order_id.to_string(), strategy);
+    }
+    Ok(out)
+}
+
+/// Head-to-head stats per strategy over the whole ledger. Rows with no
+/// decision record (e.g. recovered from the journal) are left out.
+fn strategy_section(out: &mut String) -> anyhow::Result<()> {
+    let by_order = strategies_by_order()?;
+    let mut rows: BTreeMap<String, Vec<LedgerRow>> = BTreeMap::new();
+    for row in storage::read_ledger()? {
+        if let Some(strategy) = by_order.get(&row.order_id) {
+            rows.entry(strategy.to_string()).or_default().push(row);
+        }
+    }
+
+    writeln!(out, "\n## Strategies (all time)")?;
+    for (strategy, rows) in &rows {
+        let st = stats::compute(rows);
+        writeln!(
+            out,
+            "- {}: {} settled, {}W/{}L ({:.1}%), P&L {}¢, avg win {:.0}¢ / loss {:.0}¢, max drawdown {}¢",
+            strategy, st.total_trades, st.wins, st.losses, st.win_rate * 100.0,
+            st.total_pnl_cents, st.avg_win_cents, st.avg_loss_cents, st.max_drawdown_cents
+        )?;
+    }
+    Ok(())
+}
+
+/// Outcome stats per model-routing tier, so the cheap/quant tiers can be
+/// checked against the expensive model. Unrouted decisions are left out.
+fn route_section(out: &mut String) -> anyhow::Result<()> {
+    let mut tiers: HashMap<String, String> = HashMap::new();
+    for record in storage::read_decisions()? {
+        let (Some(order_id), Some(tier)) = (
+            record["order_id"].as_str(),
+            record["decision"]["route"]["tier"].as_str(),
+        ) else {
+            continue;
+        };
+        tiers.insert(order_id.to_string(), tier.to_string());
+    }
+    if tiers.is_empty() {
+        return Ok(());
+    }
+
+    let mut rows: BTreeMap<String, Vec<LedgerRow>> = BTreeMap::new();
+    for row in storage::read_ledger()? {
+        if let Some(tier) = tiers.get(&row.order_id) {
+            rows.entry(tier.clone()).or_default().push(row);
+        }
+    }
+
+    writeln!(out, "\n## Model routing (all time)")?;
+    for (tier, rows) in &rows {
+        let st = stats::compute(rows);
+        writeln!(
+            out,
+            "- {}: {} trades, {} settled, {}W/{}L ({:.1}%), P&L {}¢",
+            tier, rows.len(), st.total_trades, st.wins, st.losses, st.win_rate * 100.0, st.total_pnl_cents
+        )?;
+    }
+    Ok(())
+}
+
+/// Combined performance across bot instances, each given as
+/// `name=brain_dir` (or just the directory). Ledgers are merged on one
+/// timeline, so combined drawdown and streak reflect the whole book.
+pub fn aggregate_report(instances: &[String]) -> anyhow::Result<String> {
+    let mut out = String::new();
+    let mut combined: Vec<LedgerRow> = Vec::new();
+    let mut by_series: BTreeMap<String, Vec<LedgerRow>> = BTreeMap::new();
+
+    writeln!(out, "# Aggregate report ({} instances)", instances.len())?;
+    writeln!(out, "\n## Instances")?;
+    for spec in instances {
+        let (name, dir) = spec.split_once('=').unwrap_or((spec.as_str(), spec.as_str()));
+        let rows = storage::read_ledger_in(dir)?;
+        let (submitted, unresolved) = crate::journal::order_counts_in(dir)?;
+        let st = stats::compute(&rows);
+        writeln!(
+            out,
+            "- {}: {} rows, {}W/{}L ({:.1}%), P&L {}¢, max drawdown {}¢, {} orders journaled ({} unresolved)",
+            name, rows.len(), st.wins, st.losses, st.win_rate * 100.0,
+            st.total_pnl_cents, st.max_drawdown_cents, submitted, unresolved
+        )?;
+        for row in rows {
+            by_series.entry(row.series().to_string()).or_default().push(row.clone());
+            combined.push(row);
+        }
+    }
+
+    combined.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    let st = stats::compute(&combined);
+    writeln!(out, "\n## Combined")?;
+    writeln!(
+        out,
+        "- {} rows, {}W/{}L ({:.1}%), P&L {}¢ (today {}¢), avg win {:.0}¢ / loss {:.0}¢, max drawdown {}¢, streak {}",
+        combined.len(), st.wins, st.losses, st.win_rate * 100.0, st.total_pnl_cents,
+        st.today_pnl_cents, st.avg_win_cents, st.avg_loss_cents, st.max_drawdown_cents, st.current_streak
+    )?;
+
+    writeln!(out, "\n## By series (all instances)")?;
+    for (series, rows) in &mut by_series {
+        rows.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        let st = stats::compute(rows);
+        writeln!(
+            out,
+            "- {}: {}W/{}L ({:.1}%), P&L {}¢",
+            series, st.wins, st.losses, st.win_rate * 100.0, st.total_pnl_cents
+        )?;
+    }
+    Ok(out)
+}
+
+/// Rolling window for the feature fit in the weekly report.
+const FEATURE_WINDOW_DAYS: i64 = 30;
+/// Trades needed before a feature fit is reported.
+const MIN_FIT_SAMPLES: usize = 30;
+/// Standardized coefficients smaller than this are treated as noise.
+const MIN_FEATURE_WEIGHT: f64 = 0.1;
+
+/// Indicator features fitted against the outcome (market resolved YES).
+const FEATURES: [&str; 8] = [
+    "pct_change_5m",
+    "pct_change_15m",
+    "pct_change_1h",
+    "trend_alignment",
+    "ema_gap_pct",
+    "rsi_9",
+    "orderbook_imbalance",
+    "volatility_1m",
+];
+
+/// Feature vector from a decision record, in `FEATURES` order.
+fn feature_row(record: &serde_json::Value) -> Option<Vec<f64>> {
+    let ind = &record["context"]["crypto_price"]["indicators"];
+    let summary = &record["signal_summary"];
+    let num = |v: &serde_json::Value| v.as_f64();
+    let spot = num(&ind["spot_price"])?;
+    let ema = num(&ind["ema_9"])?;
+    let trend = match summary["trend"].as_str()? {
+        "ALL_UP" => 1.0,
+        "ALL_DOWN" => -1.0,
+        _ => 0.0,
+    };
+    Some(vec![
+        num(&ind["pct_change_5m"])?,
+        num(&ind["pct_change_15m"])?,
+        num(&ind["pct_change_1h"])?,
+        trend,
+        if ema > 0.0 { (spot - ema) / ema * 100.0 } else { 0.0 },
+        num(&ind["rsi_9"])?,
+        num(&summary["orderbook_imbalance"])?.ln(),
+        num(&ind["volatility_1m"])?,
+    ])
+}
+
+/// Direction compute_signal_summary pushes P(YES) as each feature rises,
+/// or None where it has no hand-coded adjustment.
+fn heuristic_sign(strategy: Strategy, feature: &str) -> Option<f64> {
+    match (strategy, feature) {
+        (_, "orderbook_imbalance") => Some(1.0),
+        (Strategy::Momentum, "pct_change_15m" | "trend_alignment" | "ema_gap_pct" | "rsi_9") => Some(1.0),
+        (Strategy::MeanReversion, "pct_change_15m" | "ema_gap_pct" | "rsi_9") => Some(-1.0),
+        _ => None,
+    }
+}
+
+/// Settled trades of one strategy: feature rows and whether YES won.
+struct FeatureSamples {
+    strategy: Strategy,
+    rows: Vec<Vec<f64>>,
+    outcomes: Vec<bool>,
+}
+
+/// Fit a logistic model per strategy over the last `days` of settled trades
+/// and rank the indicator features by standardized weight, flagging any
+/// whose sign contradicts the heuristic in compute_signal_summary.
+pub fn feature_report(days: i64) -> anyhow::Result<String> {
+    let since = Utc::now() - Duration::days(days);
+    let ledger: HashMap<String, LedgerRow> = storage::query_ledger(&QueryFilter {
+        from: Some(since),
+        ..Default::default()
+    })?
+    .into_iter()
+    .map(|r| (r.order_id.clone(), r))
+    .collect();
+
+    let mut samples: BTreeMap<String, FeatureSamples> = BTreeMap::new();
+    for record in storage::read_decisions()? {
+        let Some(row) = record["order_id"].as_str().and_then(|id| ledger.get(id)) else {
+            continue;
+        };
+        // Only settled trades reveal the market outcome
+        let won = match row.result.as_str() {
+            "win" => true,
+            "loss" => false,
+            _ => continue,
+        };
+        let Some(features) = feature_row(&record) else { continue };
+        let strategy = record["strategy"].as_str().and_then(Strategy::parse).unwrap_or_default();
+        let entry = samples.entry(strategy.to_string()).or_insert_with(|| FeatureSamples {
+            strategy,
+            rows: Vec::new(),
+            outcomes: Vec::new(),
+        });
+        entry.rows.push(features);
+        entry.outcomes.push((row.side == "yes") == won);
+    }
+
+    let mut out = String::new();
+    writeln!(out, "\n## Feature importance (last {} days)", days)?;
+    if samples.is_empty() {
+        writeln!(out, "- no settled trades with decision records")?;
+    }
+    for (name, FeatureSamples { strategy, rows, outcomes }) in &samples {
+        if rows.len() < MIN_FIT_SAMPLES {
+            writeln!(out, "- {}: {} trades, need {} to fit", name, rows.len(), MIN_FIT_SAMPLES)?;
+            continue;
+        }
+        let fit = logistic::fit(rows, outcomes, 0.01, 2000);
+        writeln!(
+            out,
+            "- {}: {} trades, in-sample accuracy {:.1}% (base rate YES {:.1}%)",
+            name,
+            rows.len(),
+            fit.accuracy * 100.0,
+            outcomes.iter().filter(|&&o| o).count() as f64 / rows.len() as f64 * 100.0
+        )?;
+        let mut ranked: Vec<(&str, f64)> = FEATURES.iter().copied().zip(fit.weights.iter().copied()).collect();
+        ranked.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+        for (feature, weight) in ranked {
+            let flag = match heuristic_sign(*strategy, feature) {
+                Some(sign) if weight.abs() >= MIN_FEATURE_WEIGHT && weight.signum() != sign => {
+                    format!("  ⚠ heuristic assumes {}", if sign > 0.0 { "+" } else { "-" })
+                }
+                _ => String::new(),
+            };
+            writeln!(out, "  - {}: {:+.3}{}", feature, weight, flag)?;
+        }
+    }
+    Ok(out)
+}
+
+/// Weekly summary: per-series trading results, per-strategy and per-route
+/// stats, a rolling feature-importance fit, and venue-quality trends (spread and top-of-book depth, this week vs the
+/// week before).
+pub fn weekly_report() -> anyhow::Result<String> {
+    let now = Utc::now();
+    let week_start = now - Duration::days(7);
+    let prev_start = now - Duration::days(14);
+
+    let samples = storage::read_book_samples()?;
+    let week_rows = storage::query_ledger(&QueryFilter {
+        from: Some(week_start),
+        ..Default::default()
+    })?;
+
+    let series: BTreeSet<String> = samples
+        .iter()
+        .map(|s| s.series().to_string())
+        .chain(week_rows.iter().map(|r| r.series().to_string()))
+        .collect();
+
+    let mut out = String::new();
+    writeln!(out, "# Weekly report ({} – {})", week_start.format("%Y-%m-%d"), now.format("%Y-%m-%d"))?;
+
+    writeln!(out, "\n## Trading")?;
+    for s in &series {
+        let rows: Vec<_> = week_rows.iter().filter(|r| r.series() == s).collect();
+        let settled = rows.iter().filter(|r| ResultFilter::Settled.matches(&r.result)).count();
+        let wins = rows.iter().filter(|r| ResultFilter::Win.matches(&r.result)).count();
+        let exits = rows.iter().filter(|r| ResultFilter::Exit.matches(&r.result)).count();
+        let pnl: i64 = rows.iter().map(|r| r.pnl_cents).sum();
+        writeln!(
+            out,
+            "- {}: {} trades, {}W/{}L, {} early exits, P&L {}¢",
+            s, rows.len(), wins, settled - wins, exits, pnl
+        )?;
+    }
+
+    strategy_section(&mut out)?;
+    route_section(&mut out)?;
+    out.push_str(&feature_report(FEATURE_WINDOW_DAYS)?);
+
+    writeln!(out, "\n## Venue quality (this week vs last)")?;
+    for s in &series {
+        let of_series = || samples.iter().filter(move |x| x.series() == s);
+        let this = venue_quality(of_series().filter(|x| in_range(x, week_start, now)));
+        let prev = venue_quality(of_series().filter(|x| in_range(x, prev_start, week_start)));
+
+        let spread_worse = matches!(
+            (this.median_spread, prev.median_spread),
+            (Some(t), Some(p)) if p > 0.0 && t >= p * SPREAD_DEGRADED_RATIO
+        );
+        let depth_worse = prev.samples > 0 && this.mean_depth < prev.mean_depth * DEPTH_DEGRADED_RATIO;
+        let flag = if this.samples > 0 && (spread_worse || depth_worse) { "  ⚠ DEGRADED" } else { "" };
+
+        writeln!(
+            out,
+            "- {}: spread {} (was {}), depth {:.0} (was {:.0}), one-sided {:.0}% (was {:.0}%), {} samples{}",
+            s,
+            fmt_spread(this.median_spread),
+            fmt_spread(prev.median_spread),
+            this.mean_depth,
+            prev.mean_depth,
+            this.one_sided * 100.0,
+            prev.one_sided * 100.0,
+            this.samples,
+            flag
+        )?;
+    }
+
+    Ok(out)
+}
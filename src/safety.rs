@@ -1,25 +1,45 @@
-use crate::core::types::Config;
+use crate::core::types::{Config, KalshiAuthMode, KalshiEnv};
 use crate::storage;
-use tokio::sync::watch;
+use std::io::Write;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+const LOCK_PATH: &str = "/tmp/kalshi-bot.lock";
+const HANDOFF_PATH: &str = "/tmp/kalshi-bot.handoff";
+/// A lockfile without a readable PID this young is still being written.
+const LOCK_WRITE_GRACE: Duration = Duration::from_secs(5);
 
 pub fn validate_startup(config: &Config) -> anyhow::Result<()> {
-    if config.kalshi_private_key_pem.is_empty() {
-        anyhow::bail!("KALSHI_PRIVATE_KEY_PATH is empty or file not found");
-    }
-    if !config.kalshi_private_key_pem.contains("BEGIN") {
-        anyhow::bail!("PEM file doesn't look like a private key");
+    let env = config.kalshi_env;
+    match config.kalshi_auth_mode {
+        KalshiAuthMode::Rsa => {
+            if config.kalshi_private_key_pem.is_empty() {
+                anyhow::bail!("{} is empty or file not found", env.key_path_var());
+            }
+            if !config.kalshi_private_key_pem.contains("BEGIN") {
+                anyhow::bail!("PEM file doesn't look like a private key");
+            }
+            if config.kalshi_key_id.is_empty() {
+                anyhow::bail!("{} not set", env.key_id_var());
+            }
+        }
+        KalshiAuthMode::Token => {
+            if config.kalshi_api_token.is_empty()
+                && (config.kalshi_email.is_empty() || config.kalshi_password.is_empty())
+            {
+                anyhow::bail!("KALSHI_AUTH=token needs KALSHI_API_TOKEN or KALSHI_EMAIL and KALSHI_PASSWORD");
+            }
+        }
     }
 
     if config.series_tickers.is_empty() {
-        anyhow::bail!("KALSHI_SERIES_TICKERS not set — run discovery first");
+        anyhow::bail!("KALSHI_SERIES_TICKERS not set and series discovery found none");
     }
 
     if config.openrouter_api_key.is_empty() {
         anyhow::bail!("OPENROUTER_API_KEY not set");
     }
-    if config.kalshi_key_id.is_empty() {
-        anyhow::bail!("KALSHI_API_KEY_ID not set");
-    }
+    validate_environment(config)?;
 
     if !std::path::Path::new("brain/ledger.md").exists() {
         anyhow::bail!("brain/ledger.md not found");
@@ -30,7 +50,7 @@ pub fn validate_startup(config: &Config) -> anyhow::Result<()> {
         anyhow::bail!("brain/prompt.md not found");
     }
 
-    if !config.paper_trade && !config.confirm_live {
+    if !config.paper_trade && env == KalshiEnv::Prod && !config.confirm_live {
         anyhow::bail!(
             "PAPER_TRADE=false but CONFIRM_LIVE is not true. \
              Set CONFIRM_LIVE=true to acknowledge real money trading."
@@ -38,16 +58,52 @@ pub fn validate_startup(config: &Config) -> anyhow::Result<()> {
     }
 
     if !config.paper_trade {
-        tracing::warn!("LIVE TRADING ENABLED — real money at risk");
+        match env {
+            KalshiEnv::Prod => tracing::warn!("LIVE TRADING ENABLED — real money at risk"),
+            KalshiEnv::Demo => tracing::info!("Trading on the Kalshi demo exchange"),
+        }
     }
 
     Ok(())
 }
 
+/// Hosts must belong to `KALSHI_ENV`, and its key pair must not be the
+/// other environment's — live keys never reach demo and vice versa.
+fn validate_environment(config: &Config) -> anyhow::Result<()> {
+    let env = config.kalshi_env;
+    for (var, url) in [("KALSHI_BASE_URL", &config.kalshi_base_url), ("KALSHI_WS_URL", &config.kalshi_ws_url)] {
+        if !env.owns_url(url) {
+            anyhow::bail!(
+                "KALSHI_ENV={} but {}={} is a {} host",
+                env.as_str(), var, url, env.other().as_str()
+            );
+        }
+    }
+    let other = env.other();
+    if !config.kalshi_other_env_key_id.is_empty() && config.kalshi_other_env_key_id == config.kalshi_key_id {
+        anyhow::bail!(
+            "{} and {} are the same key — {} and {} need separate key pairs",
+            env.key_id_var(), other.key_id_var(), env.as_str(), other.as_str()
+        );
+    }
+    if !config.kalshi_other_env_key_pem.is_empty()
+        && config.kalshi_other_env_key_pem.trim() == config.kalshi_private_key_pem.trim()
+    {
+        anyhow::bail!(
+            "{} and {} hold the same private key — {} and {} need separate key pairs",
+            env.key_path_var(), other.key_path_var(), env.as_str(), other.as_str()
+        );
+    }
+    Ok(())
+}
+
 /// Set up a signal handler for graceful shutdown (SIGINT, SIGTERM).
-/// Returns a watch receiver that becomes `true` when shutdown is requested.
-pub fn setup_signal_handler() -> watch::Receiver<bool> {
-    let (tx, rx) = watch::channel(false);
+/// Returns a token that is cancelled when shutdown is requested; cycles check
+/// it before placing orders so a signal never lands between placement and
+/// persistence.
+pub fn setup_signal_handler() -> CancellationToken {
+    let token = CancellationToken::new();
+    let tx = token.clone();
     tokio::spawn(async move {
         let ctrl_c = tokio::signal::ctrl_c();
         #[cfg(unix)]
@@ -68,7 +124,156 @@ pub fn setup_signal_handler() -> watch::Receiver<bool> {
             let _ = ctrl_c.await;
             tracing::info!("Received Ctrl+C — shutting down gracefully");
         }
-        let _ = tx.send(true);
+        tx.cancel();
     });
-    rx
+    token
+}
+
+fn pid_alive(pid: u32) -> bool {
+    std::path::Path::new(&format!("/proc/{}", pid)).exists()
+}
+
+/// PID of the live process holding the lockfile, if any.
+fn lock_holder() -> Option<u32> {
+    let pid: u32 = std::fs::read_to_string(LOCK_PATH).ok()?.trim().parse().ok()?;
+    if pid_alive(pid) {
+        Some(pid)
+    } else {
+        None
+    }
+}
+
+/// Take the PID lockfile, created atomically so two instances starting
+/// together can't both get it. Fails if another live instance holds it; a
+/// lock left behind by a dead process is removed and taken over.
+pub fn acquire_lock() -> anyhow::Result<()> {
+    let own = std::process::id();
+    loop {
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(LOCK_PATH) {
+            Ok(mut file) => {
+                write!(file, "{}", own)?;
+                break;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                let holder = std::fs::read_to_string(LOCK_PATH).ok().and_then(|s| s.trim().parse::<u32>().ok());
+                match holder {
+                    Some(pid) if pid == own => break,
+                    Some(pid) if pid_alive(pid) => anyhow::bail!(
+                        "Another instance (pid {}) holds {} — use --handoff to replace it",
+                        pid, LOCK_PATH
+                    ),
+                    None if lock_age().is_some_and(|age| age < LOCK_WRITE_GRACE) => anyhow::bail!(
+                        "Another instance is taking {} — use --handoff to replace it",
+                        LOCK_PATH
+                    ),
+                    _ => {
+                        tracing::warn!(
+                            "Removing stale lock {} (pid {} not running)",
+                            LOCK_PATH, holder.map_or("unknown".to_string(), |p| p.to_string())
+                        );
+                        match std::fs::remove_file(LOCK_PATH) {
+                            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    // No live peer holds the lock, so any handoff state is left over from a
+    // crashed or abandoned handoff and would otherwise pause entries forever
+    clear_handoff();
+    Ok(())
+}
+
+fn lock_age() -> Option<Duration> {
+    std::fs::metadata(LOCK_PATH).ok()?.modified().ok()?.elapsed().ok()
+}
+
+/// Remove the lockfile if we hold it.
+pub fn release_lock() {
+    let ours = std::fs::read_to_string(LOCK_PATH)
+        .map(|s| s.trim() == std::process::id().to_string())
+        .unwrap_or(false);
+    if ours {
+        let _ = std::fs::remove_file(LOCK_PATH);
+    }
+}
+
+// ── Warm standby handoff ──
+//
+// 1. New instance (started with --handoff) writes `requested`.
+// 2. Old instance stops entries, writes the position snapshot, then `snapshot`.
+// 3. New instance loads + reconciles the snapshot, subscribes, writes `ready`.
+// 4. Old instance releases the lockfile and exits; new instance takes the lock.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HandoffState {
+    Requested,
+    SnapshotReady,
+    Ready,
+}
+
+pub fn read_handoff() -> Option<HandoffState> {
+    match std::fs::read_to_string(HANDOFF_PATH).ok()?.trim() {
+        "requested" => Some(HandoffState::Requested),
+        "snapshot" => Some(HandoffState::SnapshotReady),
+        "ready" => Some(HandoffState::Ready),
+        _ => None,
+    }
+}
+
+pub fn write_handoff(state: HandoffState) -> anyhow::Result<()> {
+    let s = match state {
+        HandoffState::Requested => "requested",
+        HandoffState::SnapshotReady => "snapshot",
+        HandoffState::Ready => "ready",
+    };
+    std::fs::write(HANDOFF_PATH, s)?;
+    Ok(())
+}
+
+/// Remove the handoff state file.
+pub fn clear_handoff() {
+    let _ = std::fs::remove_file(HANDOFF_PATH);
+}
+
+/// Ask the running instance to hand off and wait for its position snapshot.
+pub async fn request_handoff(timeout: Duration) -> anyhow::Result<()> {
+    let pid = lock_holder()
+        .ok_or_else(|| anyhow::anyhow!("--handoff given but no running instance holds the lock"))?;
+    tracing::info!("Requesting handoff from pid {}", pid);
+    write_handoff(HandoffState::Requested)?;
+    wait_for(timeout, || read_handoff() == Some(HandoffState::SnapshotReady)).await
+        .map_err(|_| {
+            clear_handoff();
+            anyhow::anyhow!("pid {} did not publish a snapshot within {:?}", pid, timeout)
+        })
+}
+
+/// Signal readiness, then wait for the old instance to release the lock and
+/// take it over.
+pub async fn complete_handoff(timeout: Duration) -> anyhow::Result<()> {
+    write_handoff(HandoffState::Ready)?;
+    wait_for(timeout, || lock_holder().is_none()).await
+        .map_err(|_| {
+            clear_handoff();
+            anyhow::anyhow!("old instance did not release the lock within {:?}", timeout)
+        })?;
+    acquire_lock()?;
+    clear_handoff();
+    tracing::info!("Handoff complete — lock acquired");
+    Ok(())
+}
+
+async fn wait_for(timeout: Duration, mut done: impl FnMut() -> bool) -> Result<(), ()> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    while !done() {
+        if tokio::time::Instant::now() >= deadline {
+            return Err(());
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+    Ok(())
 }
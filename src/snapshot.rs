@@ -0,0 +1,111 @@
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+const BRAIN_DIR: &str = "brain";
+const SNAPSHOT_DIR: &str = "brain/snapshots";
+const MANIFEST: &str = "MANIFEST.sha256";
+
+/// Mutable state under brain/ that a snapshot captures. prompt.md is
+/// hand-edited and versioned in git, so it is left out.
+const STATE_FILES: &[&str] = &[
+    "ledger.md",
+    "stats.md",
+    "positions.json",
+    "journal.jsonl",
+    "stats_history.csv",
+    "book_samples.csv",
+    "equity.csv",
+];
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn snapshot_path(id: &str) -> PathBuf {
+    Path::new(SNAPSHOT_DIR).join(id)
+}
+
+/// Copy the brain/ state files into `brain/snapshots/<id>/` with a checksum
+/// manifest. The directory is built under a temporary name and renamed into
+/// place, so a listed snapshot is always complete. Returns the snapshot id.
+pub fn take(label: &str) -> anyhow::Result<String> {
+    let id = format!("{}-{}", chrono::Utc::now().format("%Y%m%dT%H%M%S%.3f"), label);
+    let tmp = Path::new(SNAPSHOT_DIR).join(format!(".tmp-{}", id));
+    std::fs::create_dir_all(&tmp)?;
+
+    let mut manifest = String::new();
+    for name in STATE_FILES {
+        let bytes = match std::fs::read(Path::new(BRAIN_DIR).join(name)) {
+            Ok(b) => b,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        std::fs::write(tmp.join(name), &bytes)?;
+        manifest.push_str(&format!("{}  {}\n", sha256_hex(&bytes), name));
+    }
+    std::fs::write(tmp.join(MANIFEST), manifest)?;
+    std::fs::rename(&tmp, snapshot_path(&id))?;
+
+    tracing::info!("Snapshot {} taken", id);
+    Ok(id)
+}
+
+/// Snapshot ids, oldest first.
+pub fn list() -> anyhow::Result<Vec<String>> {
+    let dir = match std::fs::read_dir(SNAPSHOT_DIR) {
+        Ok(d) => d,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut ids = Vec::new();
+    for entry in dir {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        if !name.starts_with('.') {
+            ids.push(name);
+        }
+    }
+    ids.sort();
+    Ok(ids)
+}
+
+/// Files recorded in a snapshot's manifest, after checking every checksum.
+fn verified_files(id: &str) -> anyhow::Result<Vec<String>> {
+    let dir = snapshot_path(id);
+    let manifest = std::fs::read_to_string(dir.join(MANIFEST))
+        .map_err(|e| anyhow::anyhow!("Snapshot {} has no readable manifest: {}", id, e))?;
+
+    let mut files = Vec::new();
+    for line in manifest.lines() {
+        let Some((hash, name)) = line.split_once("  ") else {
+            anyhow::bail!("Snapshot {}: malformed manifest line {:?}", id, line);
+        };
+        let bytes = std::fs::read(dir.join(name))?;
+        if sha256_hex(&bytes) != hash {
+            anyhow::bail!("Snapshot {}: checksum mismatch for {}", id, name);
+        }
+        files.push(name.to_string());
+    }
+    Ok(files)
+}
+
+/// Roll brain/ back to snapshot `id`. The current state is snapshotted first
+/// (`pre-restore`), and state files absent from the snapshot are removed so
+/// the result matches it exactly. The daemon must not be running.
+pub fn restore(id: &str) -> anyhow::Result<()> {
+    let files = verified_files(id)?;
+    let backup = take("pre-restore")?;
+
+    for name in STATE_FILES {
+        let live = Path::new(BRAIN_DIR).join(name);
+        if files.iter().any(|f| f == name) {
+            let tmp = live.with_extension("restore.tmp");
+            std::fs::copy(snapshot_path(id).join(name), &tmp)?;
+            std::fs::rename(&tmp, &live)?;
+        } else if live.exists() {
+            std::fs::remove_file(&live)?;
+        }
+    }
+
+    tracing::info!("Restored snapshot {} (previous state saved as {})", id, backup);
+    Ok(())
+}
@@ -1,4 +1,10 @@
-use crate::core::types::{LedgerRow, Settlement, Stats};
+use crate::core::stats::{CounterSnapshot, StatCounters};
+use crate::core::types::{
+    BookSample, DecisionRecord, EquitySample, ExitEvent, HandoffSnapshot, JournalEvent, LedgerAmendment,
+    LedgerRow, OpenPosition, QueryFilter, Settlement, Stats, UnresolvedOrder,
+};
+use crate::ports::storage::Storage;
+use async_trait::async_trait;
 use std::io::Write;
 
 pub fn read_prompt() -> anyhow::Result<String> {
@@ -19,10 +25,7 @@ pub fn read_ledger() -> anyhow::Result<Vec<LedgerRow>> {
 
     let rows = parse_ledger_content(&content);
 
-    let data_lines = content
-        .lines()
-        .filter(|l| l.starts_with('|') && !l.contains("---") && !l.contains("Timestamp"))
-        .count();
+    let data_lines = content.lines().filter(|l| is_data_line(l)).count();
 
     if data_lines > 0 && rows.is_empty() {
         tracing::error!(
@@ -36,45 +39,67 @@ pub fn read_ledger() -> anyhow::Result<Vec<LedgerRow>> {
     Ok(rows)
 }
 
+/// Another instance's ledger, read from its brain directory (no backup
+/// fallback — for reports only).
+pub fn read_ledger_in(dir: &str) -> anyhow::Result<Vec<LedgerRow>> {
+    let content = std::fs::read_to_string(format!("{}/ledger.md", dir.trim_end_matches('/')))?;
+    Ok(parse_ledger_content(&content))
+}
+
+fn is_data_line(line: &str) -> bool {
+    line.starts_with('|') && !line.contains("---") && !line.contains("Timestamp")
+}
+
 fn parse_ledger_content(content: &str) -> Vec<LedgerRow> {
-    content
+    let mut skipped = 0;
+    let rows: Vec<LedgerRow> = content
         .lines()
-        .filter(|l| l.starts_with('|') && !l.contains("---") && !l.contains("Timestamp"))
+        .filter(|l| is_data_line(l))
         .filter_map(|line| {
-            let cols: Vec<&str> = line.split('|').map(|s| s.trim()).collect();
-            if cols.len() < 9 {
-                return None;
+            let row = parse_row(line);
+            if row.is_none() {
+                skipped += 1;
             }
-            let order_id = if cols.len() >= 10 {
-                cols[9].to_string()
-            } else {
-                String::new()
-            };
-            Some(LedgerRow {
-                timestamp: cols[1].to_string(),
-                ticker: cols[2].to_string(),
-                side: cols[3].to_string(),
-                shares: cols[4].parse().ok()?,
-                price: cols[5].parse().ok()?,
-                result: cols[6].to_string(),
-                pnl_cents: cols[7].parse().ok()?,
-                cumulative_cents: cols[8].parse().ok()?,
-                order_id,
-            })
+            row
         })
-        .collect()
+        .collect();
+    if skipped > 0 && !rows.is_empty() {
+        tracing::warn!(
+            "ledger.md: {} malformed rows skipped — run `kalshi-bot ledger-check`",
+            skipped
+        );
+    }
+    rows
 }
 
-pub fn append_ledger(row: &LedgerRow) -> anyhow::Result<()> {
-    let path = "brain/ledger.md";
-    let backup = "brain/ledger.md.bak";
-
-    if std::path::Path::new(path).exists() {
-        std::fs::copy(path, backup)?;
+fn parse_row(line: &str) -> Option<LedgerRow> {
+    let cols: Vec<&str> = line.split('|').map(|s| s.trim()).collect();
+    if cols.len() < 9 {
+        return None;
     }
+    let order_id = if cols.len() >= 10 {
+        cols[9].to_string()
+    } else {
+        String::new()
+    };
+    let signal = cols.get(10).map(|s| s.to_string()).unwrap_or_default();
+    Some(LedgerRow {
+        timestamp: cols[1].to_string(),
+        ticker: cols[2].to_string(),
+        side: cols[3].to_string(),
+        shares: cols[4].parse().ok()?,
+        price: cols[5].parse().ok()?,
+        result: cols[6].to_string(),
+        pnl_cents: cols[7].parse().ok()?,
+        cumulative_cents: cols[8].parse().ok()?,
+        order_id,
+        signal,
+    })
+}
 
-    let line = format!(
-        "| {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+fn format_row(row: &LedgerRow) -> String {
+    format!(
+        "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
         row.timestamp,
         row.ticker,
         row.side,
@@ -83,16 +108,40 @@ pub fn append_ledger(row: &LedgerRow) -> anyhow::Result<()> {
         row.result,
         row.pnl_cents,
         row.cumulative_cents,
-        row.order_id
-    );
+        row.order_id,
+        // The narrative's `|` separators would split the column
+        row.signal.replace('|', ";")
+    )
+}
+
+/// Ticker column of a ledger table row.
+fn row_ticker(line: &str) -> Option<&str> {
+    line.split('|').nth(2).map(|s| s.trim())
+}
+
+pub fn append_ledger(row: &LedgerRow) -> anyhow::Result<()> {
+    let path = "brain/ledger.md";
+    let backup = "brain/ledger.md.bak";
+
+    if std::path::Path::new(path).exists() {
+        std::fs::copy(path, backup)?;
+    }
 
     let mut file = std::fs::OpenOptions::new().append(true).open(path)?;
-    writeln!(file, "{}", line)?;
+    writeln!(file, "{}", format_row(row))?;
 
     Ok(())
 }
 
-pub fn settle_last_trade(settlement: &Settlement) -> anyhow::Result<()> {
+/// Ledger rows matching `filter`, in file order.
+pub fn query_ledger(filter: &QueryFilter) -> anyhow::Result<Vec<LedgerRow>> {
+    Ok(read_ledger()?.into_iter().filter(|r| filter.matches(r)).collect())
+}
+
+/// Settle the most recent pending row for `settlement.ticker`. Rows are
+/// matched on the ticker column, so concurrent series never cross-settle.
+/// Returns the settled row, if a pending one was found.
+pub fn settle_trade(settlement: &Settlement) -> anyhow::Result<Option<LedgerRow>> {
     let path = "brain/ledger.md";
     let backup = "brain/ledger.md.bak";
 
@@ -102,21 +151,22 @@ pub fn settle_last_trade(settlement: &Settlement) -> anyhow::Result<()> {
 
     let content = std::fs::read_to_string(path)?;
     let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let mut settled = None;
 
-    // Find the last pending line and update it
     for line in lines.iter_mut().rev() {
-        if line.contains("| pending |") {
+        if line.contains("| pending |") && row_ticker(line) == Some(settlement.ticker.as_str()) {
             let cols: Vec<&str> = line.split('|').map(|s| s.trim()).collect();
             if cols.len() >= 9 {
                 let shares: i64 = cols[4].parse().unwrap_or(1);
                 let price: i64 = cols[5].parse().unwrap_or(0);
                 let cost = price * shares;
-                let pnl = settlement.pnl_cents - cost;
+                let pnl = settlement.pnl_cents - cost - settlement.fee_cents;
                 let prev_cumulative: i64 = cols[8].parse().unwrap_or(0);
                 let new_cumulative = prev_cumulative + pnl;
                 let order_id = if cols.len() >= 10 { cols[9] } else { "" };
+                let signal = cols.get(10).copied().unwrap_or("");
                 *line = format!(
-                    "| {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+                    "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
                     cols[1],
                     cols[2],
                     cols[3],
@@ -125,15 +175,17 @@ pub fn settle_last_trade(settlement: &Settlement) -> anyhow::Result<()> {
                     settlement.result,
                     pnl,
                     new_cumulative,
-                    order_id
+                    order_id,
+                    signal
                 );
+                settled = parse_row(line);
             }
             break;
         }
     }
 
     std::fs::write(path, lines.join("\n") + "\n")?;
-    Ok(())
+    Ok(settled)
 }
 
 pub fn cancel_trade(order_id: &str) -> anyhow::Result<()> {
@@ -152,9 +204,10 @@ pub fn cancel_trade(order_id: &str) -> anyhow::Result<()> {
             let cols: Vec<&str> = line.split('|').map(|s| s.trim()).collect();
             if cols.len() >= 9 {
                 let oid = if cols.len() >= 10 { cols[9] } else { "" };
+                let signal = cols.get(10).copied().unwrap_or("");
                 *line = format!(
-                    "| {} | {} | {} | {} | {} | cancelled | 0 | {} | {} |",
-                    cols[1], cols[2], cols[3], cols[4], cols[5], cols[8], oid
+                    "| {} | {} | {} | {} | {} | cancelled | 0 | {} | {} | {} |",
+                    cols[1], cols[2], cols[3], cols[4], cols[5], cols[8], oid, signal
                 );
             }
             break;
@@ -165,7 +218,7 @@ pub fn cancel_trade(order_id: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-pub fn record_early_exit(exit: &crate::core::types::ExitEvent) -> anyhow::Result<()> {
+pub fn record_early_exit(exit: &ExitEvent) -> anyhow::Result<()> {
     let path = "brain/ledger.md";
     let backup = "brain/ledger.md.bak";
 
@@ -178,15 +231,16 @@ pub fn record_early_exit(exit: &crate::core::types::ExitEvent) -> anyhow::Result
 
     // Find the last pending line for this ticker and update it
     for line in lines.iter_mut().rev() {
-        if line.contains("| pending |") && line.contains(&exit.ticker) {
+        if line.contains("| pending |") && row_ticker(line) == Some(exit.ticker.as_str()) {
             let cols: Vec<&str> = line.split('|').map(|s| s.trim()).collect();
             if cols.len() >= 9 {
                 let prev_cumulative: i64 = cols[8].parse().unwrap_or(0);
                 let new_cumulative = prev_cumulative + exit.pnl_cents;
                 let order_id = if cols.len() >= 10 { cols[9] } else { "" };
+                let signal = cols.get(10).copied().unwrap_or("");
                 let result_str = format!("exit_{}", exit.reason);
                 *line = format!(
-                    "| {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+                    "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
                     cols[1],
                     cols[2],
                     cols[3],
@@ -195,7 +249,8 @@ pub fn record_early_exit(exit: &crate::core::types::ExitEvent) -> anyhow::Result
                     result_str,
                     exit.pnl_cents,
                     new_cumulative,
-                    order_id
+                    order_id,
+                    signal
                 );
             }
             break;
@@ -206,6 +261,120 @@ pub fn record_early_exit(exit: &crate::core::types::ExitEvent) -> anyhow::Result
     Ok(())
 }
 
+/// Apply `amendment` to the single row whose order id is `order_id`, and
+/// shift the cumulative column of every later row by the P&L change so the
+/// running sum still holds. Returns the row before and after.
+pub fn amend_ledger(order_id: &str, amendment: &LedgerAmendment) -> anyhow::Result<(LedgerRow, LedgerRow)> {
+    let path = "brain/ledger.md";
+    let backup = "brain/ledger.md.bak";
+
+    let content = std::fs::read_to_string(path)?;
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+
+    let matches: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| is_data_line(l) && parse_row(l).is_some_and(|r| r.order_id == order_id))
+        .map(|(i, _)| i)
+        .collect();
+    let idx = match matches.as_slice() {
+        [i] => *i,
+        [] => anyhow::bail!("No ledger row with order id {}", order_id),
+        _ => anyhow::bail!("{} ledger rows share order id {} — fix by hand", matches.len(), order_id),
+    };
+
+    let before = parse_row(&lines[idx]).expect("matched rows parse");
+    let after = amendment.apply(&before);
+    let delta = after.pnl_cents - before.pnl_cents;
+    lines[idx] = format_row(&after);
+    if delta != 0 {
+        for line in lines.iter_mut().skip(idx + 1).filter(|l| is_data_line(l)) {
+            if let Some(mut row) = parse_row(line) {
+                row.cumulative_cents += delta;
+                *line = format_row(&row);
+            }
+        }
+    }
+
+    std::fs::copy(path, backup)?;
+    std::fs::write(path, lines.join("\n") + "\n")?;
+    Ok((before, after))
+}
+
+#[derive(Debug, Default)]
+pub struct LedgerReport {
+    pub rows: usize,
+    pub issues: Vec<String>,
+    /// Rows whose `cumulative_cents` was rewritten (repair mode only).
+    pub repaired: usize,
+}
+
+/// Validate ledger.md: column counts, monotonic timestamps, and that
+/// `cumulative_cents` is the running sum of `pnl_cents` in file order.
+/// With `repair`, rebuilds `cumulative_cents` from scratch (malformed rows are
+/// reported but left untouched).
+pub fn verify_ledger(repair: bool) -> anyhow::Result<LedgerReport> {
+    let path = "brain/ledger.md";
+    let backup = "brain/ledger.md.bak";
+
+    let content = std::fs::read_to_string(path)?;
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let mut report = LedgerReport::default();
+    let mut running: i64 = 0;
+    let mut last_ts: Option<chrono::DateTime<chrono::FixedOffset>> = None;
+
+    for (i, line) in lines.iter_mut().enumerate() {
+        if !is_data_line(line) {
+            continue;
+        }
+        let line_no = i + 1;
+        // "| a | … | i |" splits into 9 columns plus the two empty edges;
+        // rows written since the signal column was added have 10
+        let col_count = line.split('|').count().saturating_sub(2);
+        if col_count != 9 && col_count != 10 {
+            report.issues.push(format!("line {}: expected 9 or 10 columns, found {}", line_no, col_count));
+        }
+        let Some(mut row) = parse_row(line) else {
+            report.issues.push(format!("line {}: unparseable row (trade dropped from stats)", line_no));
+            continue;
+        };
+        report.rows += 1;
+
+        match chrono::DateTime::parse_from_rfc3339(&row.timestamp) {
+            Ok(ts) => {
+                if last_ts.is_some_and(|prev| ts < prev) {
+                    report.issues.push(format!(
+                        "line {}: timestamp {} earlier than previous row",
+                        line_no, row.timestamp
+                    ));
+                }
+                last_ts = Some(ts);
+            }
+            Err(_) => report.issues.push(format!("line {}: bad timestamp {:?}", line_no, row.timestamp)),
+        }
+
+        running += row.pnl_cents;
+        if row.cumulative_cents != running {
+            report.issues.push(format!(
+                "line {}: cumulative {}¢ != running total {}¢",
+                line_no, row.cumulative_cents, running
+            ));
+            if repair {
+                row.cumulative_cents = running;
+                *line = format_row(&row);
+                report.repaired += 1;
+            }
+        }
+    }
+
+    if report.repaired > 0 {
+        std::fs::copy(path, backup)?;
+        std::fs::write(path, lines.join("\n") + "\n")?;
+    }
+
+    Ok(report)
+}
+
 pub fn write_stats(stats: &Stats) -> anyhow::Result<()> {
     let content = format!(
         "# Stats\n\
@@ -233,3 +402,254 @@ pub fn write_stats(stats: &Stats) -> anyhow::Result<()> {
     std::fs::rename("brain/stats.md.tmp", "brain/stats.md")?;
     Ok(())
 }
+
+const STATS_HISTORY_HEADER: &str = "timestamp,ticker,total_trades,wins,losses,win_rate,\
+total_pnl_cents,today_pnl_cents,current_streak,max_drawdown_cents";
+
+/// Append one stats row to `brain/stats_history.csv`, recorded after the
+/// settlement or exit of `ticker`, for plotting how stats evolve.
+pub fn append_stats_history(ticker: &str, stats: &Stats) -> anyhow::Result<()> {
+    let path = "brain/stats_history.csv";
+    let new_file = !std::path::Path::new(path).exists();
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    if new_file {
+        writeln!(file, "{}", STATS_HISTORY_HEADER)?;
+    }
+    writeln!(
+        file,
+        "{},{},{},{},{},{:.4},{},{},{},{}",
+        chrono::Utc::now().to_rfc3339(),
+        ticker,
+        stats.total_trades,
+        stats.wins,
+        stats.losses,
+        stats.win_rate,
+        stats.total_pnl_cents,
+        stats.today_pnl_cents,
+        stats.current_streak,
+        stats.max_drawdown_cents,
+    )?;
+    Ok(())
+}
+
+const BOOK_SAMPLES_PATH: &str = "brain/book_samples.csv";
+
+/// Append one top-of-book sample to `brain/book_samples.csv`.
+pub fn append_book_sample(sample: &BookSample) -> anyhow::Result<()> {
+    let new_file = !std::path::Path::new(BOOK_SAMPLES_PATH).exists();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(BOOK_SAMPLES_PATH)?;
+    if new_file {
+        writeln!(file, "timestamp,ticker,spread_cents,bid_depth,ask_depth")?;
+    }
+    writeln!(
+        file,
+        "{},{},{},{},{}",
+        sample.timestamp,
+        sample.ticker,
+        sample.spread_cents.map(|s| s.to_string()).unwrap_or_default(),
+        sample.bid_depth,
+        sample.ask_depth,
+    )?;
+    Ok(())
+}
+
+pub fn read_book_samples() -> anyhow::Result<Vec<BookSample>> {
+    let content = match std::fs::read_to_string(BOOK_SAMPLES_PATH) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let cols: Vec<&str> = line.split(',').collect();
+            if cols.len() != 5 {
+                return None;
+            }
+            Some(BookSample {
+                timestamp: cols[0].to_string(),
+                ticker: cols[1].to_string(),
+                spread_cents: cols[2].parse().ok(),
+                bid_depth: cols[3].parse().ok()?,
+                ask_depth: cols[4].parse().ok()?,
+            })
+        })
+        .collect())
+}
+
+const EQUITY_PATH: &str = "brain/equity.csv";
+
+/// Append one equity sample to `brain/equity.csv`.
+pub fn append_equity_sample(sample: &EquitySample) -> anyhow::Result<()> {
+    let new_file = !std::path::Path::new(EQUITY_PATH).exists();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(EQUITY_PATH)?;
+    if new_file {
+        writeln!(file, "timestamp,balance_cents,open_positions,cost_basis_cents,marked_value_cents,equity_cents")?;
+    }
+    writeln!(
+        file,
+        "{},{},{},{},{},{}",
+        sample.timestamp,
+        sample.balance_cents,
+        sample.open_positions,
+        sample.cost_basis_cents,
+        sample.marked_value_cents,
+        sample.equity_cents(),
+    )?;
+    Ok(())
+}
+
+/// Persist open positions so a standby instance can take over monitoring.
+pub fn write_handoff_snapshot(snapshot: &HandoffSnapshot) -> anyhow::Result<()> {
+    let content = serde_json::to_string_pretty(snapshot)?;
+    std::fs::write("brain/positions.json.tmp", content)?;
+    std::fs::rename("brain/positions.json.tmp", "brain/positions.json")?;
+    Ok(())
+}
+
+/// The handoff snapshot; a bare position list (written by builds before
+/// resting orders were handed over) reads as one with none resting.
+pub fn read_handoff_snapshot() -> anyhow::Result<HandoffSnapshot> {
+    let content = std::fs::read_to_string("brain/positions.json")?;
+    match serde_json::from_str(&content) {
+        Ok(snapshot) => Ok(snapshot),
+        Err(e) => match serde_json::from_str::<Vec<OpenPosition>>(&content) {
+            Ok(positions) => Ok(HandoffSnapshot { positions, ..Default::default() }),
+            Err(_) => Err(e.into()),
+        },
+    }
+}
+
+/// Persist the full context of an entry decision to `brain/decisions/<order_id>.json`.
+pub fn write_decision(record: &DecisionRecord) -> anyhow::Result<()> {
+    std::fs::create_dir_all("brain/decisions")?;
+    let path = format!("brain/decisions/{}.json", record.order_id);
+    std::fs::write(path, serde_json::to_string_pretty(record)?)?;
+    Ok(())
+}
+
+/// All persisted decision records, as raw JSON, oldest first.
+pub fn read_decisions() -> anyhow::Result<Vec<serde_json::Value>> {
+    let dir = match std::fs::read_dir("brain/decisions") {
+        Ok(d) => d,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut records = Vec::new();
+    for entry in dir {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        match serde_json::from_str(&std::fs::read_to_string(&path)?) {
+            Ok(v) => records.push(v),
+            Err(e) => tracing::warn!("Skipping unreadable decision {}: {}", path.display(), e),
+        }
+    }
+    records.sort_by(|a: &serde_json::Value, b| {
+        a["timestamp"].as_str().unwrap_or("").cmp(b["timestamp"].as_str().unwrap_or(""))
+    });
+    Ok(records)
+}
+
+/// Markdown/JSON files under `brain/` — the default `Storage` backend.
+pub struct FileStorage {
+    counters: StatCounters,
+}
+
+impl FileStorage {
+    /// Open the file backend, seeding the risk counters from the ledger.
+    pub fn open() -> anyhow::Result<Self> {
+        Ok(Self {
+            counters: StatCounters::from_ledger(&read_ledger()?),
+        })
+    }
+}
+
+#[async_trait]
+impl Storage for FileStorage {
+    async fn read_prompt(&self) -> anyhow::Result<String> {
+        read_prompt()
+    }
+
+    async fn read_ledger(&self) -> anyhow::Result<Vec<LedgerRow>> {
+        read_ledger()
+    }
+
+    async fn query_ledger(&self, filter: &QueryFilter) -> anyhow::Result<Vec<LedgerRow>> {
+        query_ledger(filter)
+    }
+
+    async fn append_ledger(&self, row: &LedgerRow) -> anyhow::Result<()> {
+        append_ledger(row)
+    }
+
+    async fn settle_trade(&self, settlement: &Settlement) -> anyhow::Result<()> {
+        if let Some(row) = settle_trade(settlement)? {
+            self.counters.record_result(&row.result, row.pnl_cents);
+        }
+        Ok(())
+    }
+
+    async fn cancel_trade(&self, order_id: &str) -> anyhow::Result<()> {
+        cancel_trade(order_id)
+    }
+
+    async fn record_early_exit(&self, exit: &ExitEvent) -> anyhow::Result<()> {
+        record_early_exit(exit)
+    }
+
+    async fn amend_ledger(
+        &self,
+        order_id: &str,
+        amendment: &LedgerAmendment,
+    ) -> anyhow::Result<(LedgerRow, LedgerRow)> {
+        let amended = amend_ledger(order_id, amendment)?;
+        // A rewritten result or P&L changes today's counts and the streak
+        self.counters.reseed(&read_ledger()?);
+        Ok(amended)
+    }
+
+    async fn write_stats(&self, stats: &Stats) -> anyhow::Result<()> {
+        write_stats(stats)
+    }
+
+    async fn append_stats_history(&self, ticker: &str, stats: &Stats) -> anyhow::Result<()> {
+        append_stats_history(ticker, stats)
+    }
+
+    async fn append_book_sample(&self, sample: &BookSample) -> anyhow::Result<()> {
+        append_book_sample(sample)
+    }
+
+    async fn append_equity_sample(&self, sample: &EquitySample) -> anyhow::Result<()> {
+        append_equity_sample(sample)
+    }
+
+    async fn write_decision(&self, record: &DecisionRecord<'_>) -> anyhow::Result<()> {
+        write_decision(record)
+    }
+
+    async fn journal(&self, event: &JournalEvent) -> anyhow::Result<()> {
+        crate::journal::append(event)
+    }
+
+    async fn unresolved_orders(&self) -> anyhow::Result<Vec<UnresolvedOrder>> {
+        crate::journal::unresolved()
+    }
+
+    async fn compact_journal(&self) -> anyhow::Result<()> {
+        crate::journal::compact()
+    }
+
+    fn counters(&self) -> CounterSnapshot {
+        self.counters.snapshot()
+    }
+}